@@ -0,0 +1,95 @@
+// Rough timings of the Rc<RefCell> LinkedList against boxed::LinkedList, run
+// with `cargo bench`. Same harness-free setup as benches/unrolled.rs.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_linked_list::{boxed, LinkedList};
+
+const LEN: usize = 200_000;
+const RUNS: usize = 5;
+
+fn best_of(mut run: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(case: &str, shared: Duration, boxed: Duration) {
+    println!(
+        "{case:<16} LinkedList {:>10.2?}  boxed::LinkedList {:>10.2?}  ({:.1}x)",
+        shared,
+        boxed,
+        shared.as_secs_f64() / boxed.as_secs_f64()
+    );
+}
+
+fn main() {
+    report(
+        "push_back",
+        best_of(|| {
+            let mut list = LinkedList::new();
+            for i in 0..LEN {
+                list.push_back(i);
+            }
+            black_box(list);
+        }),
+        best_of(|| {
+            let mut list = boxed::LinkedList::new();
+            for i in 0..LEN {
+                list.push_back(i);
+            }
+            black_box(list);
+        }),
+    );
+
+    let shared: LinkedList<usize> = (0..LEN).collect();
+    let owned: boxed::LinkedList<usize> = (0..LEN).collect();
+    report(
+        "iterate and sum",
+        best_of(|| {
+            black_box(shared.values().sum::<usize>());
+        }),
+        best_of(|| {
+            black_box(owned.iter().sum::<usize>());
+        }),
+    );
+    report(
+        "pop_front all",
+        best_of(|| {
+            let mut list = shared.clone();
+            while list.pop_front().is_some() {}
+        }),
+        best_of(|| {
+            let mut list = owned.clone();
+            while list.pop_front().is_some() {}
+        }),
+    );
+    // a short queue pushed and popped many times, where boxed::LinkedList
+    // reuses the popped nodes
+    report(
+        "push/pop churn",
+        best_of(|| {
+            let mut list = LinkedList::new();
+            for i in 0..LEN {
+                list.push_back(i);
+                if list.len() > 8 {
+                    black_box(list.pop_front());
+                }
+            }
+        }),
+        best_of(|| {
+            let mut list = boxed::LinkedList::with_capacity(9);
+            for i in 0..LEN {
+                list.push_back(i);
+                if list.len() > 8 {
+                    black_box(list.pop_front());
+                }
+            }
+        }),
+    );
+}
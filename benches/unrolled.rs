@@ -0,0 +1,82 @@
+// Rough timings of LinkedList against UnrolledList, run with `cargo bench`.
+// No harness: each case is timed a few times with Instant and the best run
+// is printed.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use rust_linked_list::{LinkedList, UnrolledList};
+
+const LEN: usize = 200_000;
+const RUNS: usize = 5;
+
+fn best_of(mut run: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn report(case: &str, linked: Duration, unrolled: Duration) {
+    println!(
+        "{case:<16} LinkedList {:>10.2?}  UnrolledList {:>10.2?}  ({:.1}x)",
+        linked,
+        unrolled,
+        linked.as_secs_f64() / unrolled.as_secs_f64()
+    );
+}
+
+fn main() {
+    report(
+        "push_back",
+        best_of(|| {
+            let mut list = LinkedList::new();
+            for i in 0..LEN {
+                list.push_back(i);
+            }
+            black_box(list);
+        }),
+        best_of(|| {
+            let mut list = UnrolledList::<usize>::new();
+            for i in 0..LEN {
+                list.push_back(i);
+            }
+            black_box(list);
+        }),
+    );
+
+    let linked: LinkedList<usize> = (0..LEN).collect();
+    let unrolled: UnrolledList<usize> = (0..LEN).collect();
+    report(
+        "iterate and sum",
+        best_of(|| {
+            black_box(linked.values().sum::<usize>());
+        }),
+        best_of(|| {
+            black_box(unrolled.iter().sum::<usize>());
+        }),
+    );
+    report(
+        "get middle",
+        best_of(|| {
+            black_box(*linked.get(LEN / 2).unwrap());
+        }),
+        best_of(|| {
+            black_box(*unrolled.get(LEN / 2).unwrap());
+        }),
+    );
+    report(
+        "pop_front all",
+        best_of(|| {
+            let mut list = linked.clone();
+            while list.pop_front().is_some() {}
+        }),
+        best_of(|| {
+            let mut list: UnrolledList<usize> = unrolled.iter().copied().collect();
+            while list.pop_front().is_some() {}
+        }),
+    );
+}
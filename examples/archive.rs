@@ -0,0 +1,24 @@
+use std::fs::{self, File};
+
+use rust_linked_list::{ArchivedList, LinkedList};
+
+fn main() {
+    let path = std::env::temp_dir().join("rust_linked_list_demo.archive");
+    let mut numbers = LinkedList::new();
+    for i in 0..5_u32 {
+        numbers.push_back(i * i);
+    }
+    let frozen = File::create(&path).and_then(|file| numbers.freeze(file));
+    // nothing else touches the file while it is mapped
+    let archived = frozen.and_then(|_| unsafe { ArchivedList::<u32>::open(&path) });
+    if let Ok(archived) = archived {
+        println!(
+            "Archived list of {} elements (empty {}), nth (3) element {:?}, all {:?}",
+            archived.len(),
+            archived.is_empty(),
+            archived.get_nth(3),
+            archived.iter().collect::<Vec<_>>()
+        );
+    }
+    let _ = fs::remove_file(path);
+}
@@ -0,0 +1,18 @@
+use rust_linked_list::BigNum;
+
+fn main() {
+    let ten = BigNum::from(10);
+    let googol = (0..100).fold(BigNum::from(1), |power, _| &power * &ten);
+    let mut doubled = BigNum::default();
+    doubled += &googol;
+    doubled += &googol;
+    println!(
+        "Googol {googol} (zero {}), doubled {doubled}, minus one {:?}, parsed back {}",
+        googol.is_zero(),
+        googol.checked_sub(&BigNum::from(1)).map(|n| n.to_string()),
+        googol.to_string().parse::<BigNum>() == Ok(googol.clone()) && doubled > googol
+    );
+    if let Err(error) = "12e3".parse::<BigNum>() {
+        println!("Not a number: {error}");
+    }
+}
@@ -0,0 +1,345 @@
+use std::rc::Rc;
+
+use rust_linked_list::adapters::{Queue, Stack};
+use rust_linked_list::boxed;
+use rust_linked_list::{
+    linked_list, ArenaList, BumpAlloc, CircularList, CopyList, DoublyLinkedList, GhostList,
+    GhostToken, HashChainList, LocalLock, NodePool, PairingHeap, PersistentList, PoolList,
+    PriorityList, SkipList, SmallList, SortedLinkedList, StaticList, UnrolledList, WeakList,
+};
+
+fn main() {
+    let mut history = DoublyLinkedList::new();
+    for page in ["home", "search", "item"] {
+        history.push_back(page);
+    }
+    history.push_front("start");
+    let back: Vec<_> = history
+        .iter()
+        .rev()
+        .map(|node| node.borrow().value)
+        .collect();
+    let mut at = history.cursor_front();
+    at.move_prev();
+    at.move_prev();
+    at.move_prev();
+    println!(
+        "History of {} backwards {back:?}, cursor wrapped round to {:?}",
+        history.len(),
+        at.current().map(|node| node.borrow().value)
+    );
+    println!(
+        "History pops {:?} {:?}, empty {}",
+        history.pop_back(),
+        history.pop_front(),
+        history.is_empty()
+    );
+
+    let mut backlog = linked_list![3, 1];
+    backlog.insert_sorted(2);
+    let mut by_priority: SortedLinkedList<_> = backlog.into_iter().collect();
+    by_priority.insert(0);
+    println!(
+        "Backlog {}, priority 2 at {:?}, 4 would go to {:?}",
+        by_priority.as_list(),
+        by_priority.search(&2),
+        by_priority.search(&4)
+    );
+    println!("Next up {:?}", by_priority.pop_first());
+    let mut jobs: PriorityList<_> = [(3, "deploy"), (1, "fix")].into_iter().collect();
+    jobs.push((2, "review"));
+    println!(
+        "Jobs: {} queued, lowest {:?}, highest {:?}, then {:?}",
+        jobs.len(),
+        jobs.peek_min().map(|job| *job),
+        jobs.pop_max(),
+        jobs.pop_min()
+    );
+    let mut undo = Stack::new();
+    let mut inbox = Queue::new();
+    for action in ["type", "bold", "paste"] {
+        undo.push(action);
+        inbox.enqueue(action);
+    }
+    println!(
+        "Undo {:?} (now on top {:?}), oldest in inbox {:?}, {} left",
+        undo.pop(),
+        undo.peek().map(|action| *action),
+        inbox.dequeue(),
+        inbox.len()
+    );
+
+    let mut servers = CircularList::new();
+    for server in ["eu", "us", "asia"] {
+        servers.push(server);
+    }
+    let picks: Vec<_> = servers.cycle_iter().take(4).collect();
+    servers.rotate();
+    let drained = servers.pop_current();
+    println!(
+        "Servers picked {picks:?}, drained {drained:?}, now at {:?}, ring {:?} of {} (empty {})",
+        servers.current().map(|server| *server),
+        servers.iter().collect::<Vec<_>>(),
+        servers.len(),
+        servers.is_empty()
+    );
+    let base: PersistentList<_> = ["b", "c"].into_iter().collect();
+    let draft = base.push_front("a");
+    let undo = draft.tail();
+    println!(
+        "Versions {:?} and {:?}, head {:?}, undo shares base {}",
+        base.iter().collect::<Vec<_>>(),
+        draft.iter().collect::<Vec<_>>(),
+        draft.head(),
+        undo.ptr_eq(&base)
+    );
+    let mut samples: UnrolledList<u32, 4> = (1..=9).collect();
+    samples.insert(2, 100).unwrap();
+    samples.push_front(0);
+    let dropped = (samples.remove(5), samples.pop_back(), samples.pop_front());
+    if let Some(first) = samples.get_mut(0) {
+        *first *= 10;
+    }
+    println!(
+        "Unrolled samples {samples:?} ({} of them, empty {}), dropped {dropped:?}, third {:?}, sum {}",
+        samples.len(),
+        samples.is_empty(),
+        samples.get(2),
+        samples.iter().sum::<u32>()
+    );
+    let mut ids = SkipList::with_seed(2024);
+    for id in [42, 7, 19, 7, 88] {
+        ids.insert(id);
+    }
+    let retired = ids.remove(&19);
+    let seen: SkipList<_> = [3, 1, 2].into_iter().collect();
+    println!(
+        "Ids {:?} ({} unique, empty {}), lowest {:?}, has 42 {}, retired {retired:?}, seen {:?}",
+        ids.iter().collect::<Vec<_>>(),
+        ids.len(),
+        ids.is_empty(),
+        ids.first().map(|id| *id),
+        ids.contains(&42),
+        seen.iter().collect::<Vec<_>>()
+    );
+    let mut frames: boxed::LinkedList<u32> = (1..=3).collect();
+    frames.push_front(0);
+    frames.extend([4, 5]);
+    for frame in &mut frames {
+        *frame *= 2;
+    }
+    let _ = frames.insert(2, 99);
+    let cut = (frames.remove(2), frames.pop_back());
+    if let Some(first) = frames.front_mut() {
+        *first += 1;
+    }
+    println!(
+        "Boxed frames {frames:?} ({} long), front {:?} back {:?} third {:?}, cut {cut:?}",
+        frames.len(),
+        frames.front(),
+        frames.back(),
+        frames.get(2)
+    );
+    *frames.get_mut(0).unwrap() = 7;
+    *frames.back_mut().unwrap() = 8;
+    let total: u32 = frames.clone().into_iter().sum();
+    let first = frames.pop_front();
+    println!(
+        "Boxed frames summed {total}, popped {first:?}, largest {:?}, same as copy {}, empty {}",
+        frames.iter().max(),
+        frames == frames.clone(),
+        frames.is_empty()
+    );
+    let mut particles = ArenaList::with_capacity(4);
+    let spark = particles.push_back("spark");
+    particles.push_front("smoke");
+    let ember = particles.insert_after(spark, "ember").unwrap();
+    let burnt = particles.remove_after(spark);
+    // the key is stale until a later push reuses its slot
+    let ember_gone = particles.get(ember).is_none();
+    particles.push_back("ash");
+    if let Some(particle) = particles.get_mut(spark) {
+        *particle = "flare";
+    }
+    let first = particles.pop_front();
+    println!(
+        "Particles {particles:?} ({} live in {} slots, empty {}), ember gone {ember_gone}, burnt {burnt:?}, first {first:?}",
+        particles.len(),
+        particles.capacity(),
+        particles.is_empty()
+    );
+    let mut reused: ArenaList<u8> = (1..=3).collect();
+    reused.clear();
+    println!(
+        "Cleared arena keeps {} slots, {:?}",
+        reused.capacity(),
+        reused.iter().count()
+    );
+    let mut window = boxed::LinkedList::with_capacity(3);
+    for reading in 1..=10 {
+        window.push_back(reading);
+        if window.len() > 3 {
+            window.pop_front();
+        }
+    }
+    window.pop_front();
+    let spare = window.capacity();
+    window.shrink_to_fit();
+    window.reserve(2);
+    println!(
+        "Readings window {window:?}, {spare} nodes before shrinking, {} after reserving",
+        window.capacity()
+    );
+    let mut scratch = BumpAlloc::<512>::new();
+    {
+        let mut events = boxed::LinkedList::new_in(&scratch);
+        events.extend(["spawn", "hit", "despawn"]);
+        let on_heap: boxed::LinkedList<_> = events.iter().copied().collect();
+        println!(
+            "Bump events {events:?}, {} of {} bytes used, same as heap copy {}",
+            events.allocator().used(),
+            scratch.capacity(),
+            events == on_heap
+        );
+    }
+    scratch.reset();
+    println!("Bump reset to {} bytes", scratch.used());
+    let mut tags: SmallList<&str, 4> = ["red", "round"].into_iter().collect();
+    tags.push_front("new");
+    let inline = !tags.spilled();
+    tags.extend(["ripe", "sweet"]);
+    if let Some(tag) = tags.get_mut(1) {
+        *tag = "crimson";
+    }
+    let (first, last) = (tags.pop_front(), tags.pop_back());
+    println!(
+        "Tags {tags:?} ({} long, empty {}), inline at 3 {inline}, spilled at 5 {}, popped {first:?} {last:?}",
+        tags.len(),
+        tags.is_empty(),
+        tags.spilled()
+    );
+    println!(
+        "Tags front {:?} back {:?} second {:?}, {} chars, same as copy {}",
+        tags.front(),
+        tags.back(),
+        tags.get(1),
+        tags.iter().map(|tag| tag.len()).sum::<usize>(),
+        tags == tags.clone()
+    );
+
+    let mut tasks = PairingHeap::default();
+    let slow = tasks.push(9);
+    tasks.push(4);
+    let mut urgent = PairingHeap::new();
+    urgent.push(2);
+    tasks.merge(urgent);
+    let _ = tasks.decrease_key(&slow, 1);
+    println!(
+        "Heap of {} tasks (empty {}), next {:?}, popped {:?}",
+        tasks.len(),
+        tasks.is_empty(),
+        tasks.peek().map(|task| *task),
+        [tasks.pop(), tasks.pop(), tasks.pop()]
+    );
+
+    GhostToken::scope(|mut token| {
+        let mut ghost = GhostList::default();
+        ghost.push_back(2, &mut token);
+        ghost.push_front(1);
+        ghost.for_each_mut(&mut token, |value| *value *= 10);
+        let _ = ghost.update_nth(0, 11, &mut token);
+        let popped = ghost.pop_front(&mut token);
+        println!(
+            "Ghost list of {} (empty {}) popped {popped:?}, left {:?}, second {:?}",
+            ghost.len(),
+            ghost.is_empty(),
+            ghost.iter(&token).collect::<Vec<_>>(),
+            ghost.get_nth(1, &token)
+        );
+    });
+
+    let mut samples = CopyList::default();
+    for sample in [0.5, 1.5, 2.0] {
+        samples.push_back(sample);
+    }
+    samples.push_front(0.0);
+    samples.map_in_place(|sample| sample * 2.0);
+    let _ = samples.update_nth(0, -1.0);
+    println!(
+        "Copy list of {} samples (empty {}), nth (1) {:?}, popped {:?}, sum of rest {}",
+        samples.len(),
+        samples.is_empty(),
+        samples.get_nth(1),
+        samples.pop_front(),
+        samples.iter().sum::<f64>()
+    );
+
+    let mut audit = HashChainList::default();
+    for event in ["login", "grant", "logout"] {
+        audit.push(event);
+    }
+    let intact = audit.verify_chain();
+    if let Ok(Some(node)) = audit.list().get_nth(1) {
+        node.borrow_mut().value.value = "deny";
+    }
+    println!(
+        "Audit chain ending in {:x} verified {intact:?}, after tampering {:?}",
+        audit.last_hash(),
+        audit.verify_chain()
+    );
+
+    let mut fixed = StaticList::<i32, 3>::new();
+    for i in 1..=4 {
+        if let Err(err) = fixed.push_back(i) {
+            println!("Static list push of {i} failed: {err}");
+        }
+    }
+    let _ = fixed.push_front(0);
+    println!(
+        "Static list {fixed} with {} of {} slots used, empty {}",
+        fixed.len(),
+        fixed.capacity(),
+        fixed.is_empty()
+    );
+    println!(
+        "Static list pop front {:?}, nth (1) element {:?}",
+        fixed.pop_front(),
+        fixed.get_nth(1)
+    );
+
+    static POOL: NodePool<u32, 4> = NodePool::new();
+    let mut jobs = PoolList::new(&POOL);
+    let mut retries = PoolList::new(&POOL);
+    let _ = jobs.push_back(1);
+    let _ = jobs.push_back(2);
+    let _ = retries.push_front(3);
+    println!(
+        "Pool lists {jobs} and {retries}, {} of {} pool nodes free",
+        POOL.available(),
+        POOL.capacity()
+    );
+    let mut job_sum = 0;
+    jobs.for_each(|job| job_sum += job);
+    println!(
+        "Pool list sum {job_sum}, pop front {:?}, {} left, empty {}",
+        jobs.pop_front(),
+        jobs.len(),
+        jobs.is_empty()
+    );
+    // a pool kept to one context needs no lock at all
+    let scratch = NodePool::<u32, 2, LocalLock>::new();
+    let mut pending = PoolList::new(&scratch);
+    let _ = pending.push_back(4);
+    println!("Local pool list {pending}, {} free", scratch.available());
+
+    let mut observers = WeakList::new();
+    let logger = Rc::new("logger");
+    let metrics = Rc::new("metrics");
+    observers.push(&logger);
+    observers.push(&metrics);
+    drop(metrics);
+    for observer in observers.iter() {
+        println!("Notify live observer {observer}");
+    }
+    println!("Live observers after pruning {}", observers.prune());
+}
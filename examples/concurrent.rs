@@ -0,0 +1,70 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use rust_linked_list::concurrent::Queue;
+use rust_linked_list::{ArcLinkedList, BoundedBlockingQueue};
+
+fn main() {
+    let shared = Arc::new(ArcLinkedList::default());
+    let writers: Vec<_> = (0..3)
+        .map(|t| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                shared.push_back(t);
+                shared.push_front(t + 10);
+            })
+        })
+        .collect();
+    for writer in writers {
+        let _ = writer.join();
+    }
+    let _ = shared.update_nth(0, 0);
+    let first = shared.get_nth(0).ok().flatten();
+    let tail_half = shared.split_off(3);
+    println!(
+        "Shared list of {} (empty {}) after threads pushed, first {:?}, split off {:?}, walked {}",
+        shared.len(),
+        shared.is_empty(),
+        first.map(|node| node.read().map(|node| node.value).unwrap_or_default()),
+        tail_half.map(|half| half.values()),
+        shared.iter().count()
+    );
+
+    let queue = Arc::new(Queue::default());
+    let producers: Vec<_> = (0..3)
+        .map(|p| {
+            let queue = queue.clone();
+            thread::spawn(move || queue.push(p))
+        })
+        .collect();
+    for producer in producers {
+        let _ = producer.join();
+    }
+    let mut popped: Vec<i32> = std::iter::from_fn(|| queue.pop()).collect();
+    popped.sort();
+    println!(
+        "Lock-free queue drained {popped:?}, empty {}",
+        queue.is_empty()
+    );
+
+    let queue = Arc::new(BoundedBlockingQueue::new(2));
+    let producer = {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            for job in 0..5 {
+                queue.push(job);
+            }
+            queue.push_timeout(-1, Duration::from_secs(1))
+        })
+    };
+    let jobs: Vec<i32> = (0..6).map(|_| queue.pop()).collect();
+    println!(
+        "Jobs through a queue of capacity {} {jobs:?}, last push {:?}, left {} (empty {}), late pop {:?}",
+        queue.capacity(),
+        producer.join().ok(),
+        queue.len(),
+        queue.is_empty(),
+        queue.pop_timeout(Duration::ZERO)
+    );
+}
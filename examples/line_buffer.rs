@@ -0,0 +1,21 @@
+use rust_linked_list::LineBuffer;
+
+fn main() {
+    let mut buffer = LineBuffer::from_text("fn main() {\n}");
+    let _ = buffer.insert_line(1, "    println!();");
+    buffer.cursor_down(1);
+    let _ = buffer.insert_line(0, "// demo");
+    let _ = buffer.move_line(0, 3);
+    let deleted = buffer.delete_line(3);
+    buffer.cursor_up(1);
+    println!(
+        "Line buffer of {} lines (empty {}) with cursor on {} {:?}, deleted {deleted:?}, line 0 {:?}:\n{}",
+        buffer.len(),
+        buffer.is_empty(),
+        buffer.cursor_line(),
+        buffer.current_line(),
+        buffer.line(0),
+        buffer.text()
+    );
+    let _ = LineBuffer::default();
+}
@@ -0,0 +1,434 @@
+use std::any::Any;
+use std::fmt::Display;
+
+use rust_linked_list::{linked_list, EditOp, LinkedList, NodeRef};
+
+fn main() {
+    let mut list = linked_list![6, 88888, 4];
+    let _ = list.insert(0, 200);
+    println!("List after insert at 0 {list}");
+
+    let mut ring = LinkedList::new();
+    for worker in ["a", "b", "c", "d"] {
+        ring.push_back(worker);
+    }
+    let _ = ring.rotate_to(2);
+    println!("Worker ring rotated to 2 {ring}");
+
+    let mut ring_copy = LinkedList::new();
+    ring_copy.push_back("z");
+    ring_copy.clone_from(&ring);
+    println!(
+        "Ring cloned into existing list {ring_copy}, fresh clone {}",
+        ring.clone()
+    );
+
+    let first_worker = ring.get_nth(0).unwrap().unwrap();
+    let last_worker = ring.get_nth(3).unwrap().unwrap();
+    let _ = ring.swap_nodes(&NodeRef::from(&first_worker), &NodeRef::from(&last_worker));
+    println!("Ring after swapping first and last nodes {ring}");
+
+    println!("Ring repeated twice {}", ring.repeated(2));
+    println!("First 6 of the cycled ring {}", ring.cycle_take(6));
+
+    println!("Ring joined with dashes {}", ring.join("-"));
+    println!(
+        "Ring equals [b, d, a, c] {}, equals vec![a] {}, equals slice of 4 {}",
+        ring == ["b", "d", "a", "c"],
+        ring == vec!["a"],
+        ring == ["b", "d", "a", "c"][..]
+    );
+
+    let hex = list.to_string_with(|value, f| write!(f, "{value:#x}"));
+    println!("List in hex {hex}");
+
+    let mut entities = LinkedList::new();
+    for hp in [10, 0, 7, 0] {
+        entities.push_back(hp);
+    }
+    for node in entities.iter().flatten() {
+        if node.borrow().value == 0 {
+            let _ = entities.mark_remove(&NodeRef::from(&node));
+        }
+    }
+    let swept = entities.sweep();
+    println!("Entities after sweeping {swept} dead ones {entities}");
+    let mut route: LinkedList<&str> = ["depot", "b", "x", "d"].into_iter().collect();
+    let mut stop = route.cursor_front_mut();
+    stop.move_next();
+    stop.insert_before("a");
+    stop.move_next();
+    stop.insert_after("c");
+    let dropped = stop.remove_current();
+    println!(
+        "Route edit dropped {dropped:?}, cursor at {:?} on {:?}",
+        stop.index(),
+        stop.current().map(|node| node.borrow().value)
+    );
+    println!("Route after cursor edits {route}");
+
+    let mut healthy = entities.clone();
+    healthy.retain(|hp| *hp > 7);
+    println!("Healthy entities {healthy}");
+    let mut batch = linked_list![1, 2, 3, 4, 5, 6, 7, 8];
+    let multiples_of_3: Vec<_> = batch.extract_if(|n| n % 3 == 0).collect();
+    let drained: Vec<_> = batch.drain().take(2).collect();
+    println!("Extracted {multiples_of_3:?}, drained {drained:?}, left {batch}");
+    let ends: LinkedList<i32> = (1..=4).collect();
+    if let Some(mut first) = ends.front_mut() {
+        *first = 0;
+    }
+    if let Some(mut last) = ends.back_mut() {
+        *last *= 10;
+    }
+    println!(
+        "Ends {:?} and {:?} of {ends}",
+        ends.front().map(|value| *value),
+        ends.back().map(|value| *value)
+    );
+    let mut log = linked_list![0; 6];
+    log.truncate(2);
+    print!("Log truncated to {log}");
+    log.clear();
+    println!(", cleared {log} (empty {})", log.is_empty());
+    let mut stream = linked_list!["up", "up", "down", "down", "down", "up"];
+    stream.dedup();
+    let mut readings = linked_list![20, 21, 21, 25, 26];
+    readings.dedup_by(|reading, kept| reading - kept <= 1);
+    let mut levels = linked_list![(1, 'a'), (1, 'b'), (2, 'c')];
+    levels.dedup_by_key(|level| level.0);
+    println!("Collapsed runs {stream}, readings {readings}, levels {levels:?}");
+    let mut morning = linked_list![8, 10, 12];
+    morning.merge(linked_list![9, 11]);
+    println!("Merged schedule {morning}");
+    let mut podium = linked_list!["silver", "gold", "bronze"];
+    podium.swap(0, 1).unwrap();
+    println!("Podium {podium}");
+    let mut chapters = linked_list!["intro", "outro"];
+    chapters.splice(1, linked_list!["setup", "usage"]).unwrap();
+    println!("Chapters {chapters}");
+
+    let mut playlist = LinkedList::new();
+    let intro = playlist.push_back("intro");
+    let outro = playlist.push_back("outro");
+    let _ = playlist.insert_after(&intro, "verse");
+    let skipped = playlist.remove(&outro);
+    println!(
+        "Playlist {playlist} starting with {:?}, skipped {skipped:?}, outro still there {}",
+        playlist.value(&intro).map(|track| *track),
+        playlist.value(&outro).is_some()
+    );
+    println!("Playlist cyclic {}", playlist.is_cyclic());
+
+    let primes = LinkedList::from([2, 3, 5, 7]);
+    let countdown: Vec<i32> = primes.values().rev().collect();
+    let mut walk = primes.iter();
+    walk.next_back();
+    println!(
+        "Primes backwards {countdown:?}, last even {:?}, {} left after one from the back",
+        primes.values().rfind(|p| p % 2 == 0),
+        walk.len()
+    );
+    let more_primes = LinkedList::from(vec![11, 13]);
+    let small_primes = LinkedList::from(&primes.to_vec()[..2]);
+    println!(
+        "Primes {primes} and {more_primes} as a Vec {:?}, smallest {small_primes}, \
+         matches the slice {}",
+        Vec::from(more_primes.clone()),
+        small_primes == [2, 3].as_slice()
+    );
+    let words: LinkedList<String> = ["linked", "list"].into_iter().map(String::from).collect();
+    println!(
+        "Words {words}, as debug {words:?}, joined {}",
+        words.format_with(" -> ")
+    );
+    println!("One word per line\n{words:#}");
+    if let Some(mut word) = words.get_mut(1) {
+        word.push('s');
+    }
+    words.at_mut(0).make_ascii_uppercase();
+    println!(
+        "First word {}, second {:?}, third {:?}",
+        *words.at(0),
+        words.get(1).as_deref(),
+        words.get(2).as_deref()
+    );
+    println!(
+        "Words contain \"list\" {}, first long word {:?} at {:?}",
+        words.contains(&"list"),
+        words.find(|word| word.len() > 5).as_deref(),
+        words.position(|word| word.len() > 5)
+    );
+    let mut visits = std::collections::HashMap::new();
+    for path in [["home", "item"], ["home", "cart"], ["home", "item"]] {
+        *visits
+            .entry(path.into_iter().collect::<LinkedList<_>>())
+            .or_insert(0) += 1;
+    }
+    let item_path: LinkedList<_> = ["home", "item"].into_iter().collect();
+    println!(
+        "Paths visited {}, home > item {} times, same as a clone {}",
+        visits.len(),
+        visits[&item_path],
+        item_path == item_path.clone()
+    );
+    let mut paths: Vec<_> = visits.into_keys().collect();
+    paths.sort();
+    println!(
+        "Paths in order {paths:?}, home > cart first {}",
+        paths[0] < item_path
+    );
+
+    for (i, chunk) in ring.clone().split_into(3).iter().enumerate() {
+        println!("Ring chunk {i} {chunk}");
+    }
+
+    for (i, hand) in ring.clone().distribute(2).iter().enumerate() {
+        println!("Ring dealt to hand {i} {hand}");
+    }
+
+    println!(
+        "Ring dealt and riffled back {}",
+        LinkedList::riffle(ring.clone().distribute(3))
+    );
+
+    let workers: Vec<_> = ring.values().collect();
+    let shouted: Vec<_> = (&ring)
+        .into_iter()
+        .map(|worker| worker.to_uppercase())
+        .collect();
+    let mut owned = String::new();
+    for worker in ring.clone() {
+        owned.push_str(worker);
+    }
+    println!("Ring values {workers:?}, shouted {shouted:?}, owned {owned}");
+
+    let strided: Vec<_> = ring
+        .step_by(2)
+        .flatten()
+        .map(|node| node.borrow().value)
+        .collect();
+    println!(
+        "Every 2nd worker {strided:?}, every 3rd kept {}",
+        ring.clone().every_nth(3)
+    );
+
+    let mut frames = entities.tuples::<2>();
+    for [hp, armor] in frames.by_ref() {
+        println!(
+            "Entity frame hp {} armor {}",
+            hp.borrow().value,
+            armor.borrow().value
+        );
+    }
+    println!("Entity frame remainder {}", frames.remainder().len());
+
+    let mut tokens = entities.iter();
+    while let Some(token) = tokens.next().flatten() {
+        let lookahead = tokens.peek().map(|next| next.borrow().value);
+        let second = tokens.peek_nth(1).map(|node| node.borrow().value);
+        println!(
+            "Token {} followed by {lookahead:?} then {second:?}",
+            token.borrow().value
+        );
+    }
+
+    let mut buckets = LinkedList::new();
+    for (bucket, amount) in [(2, 5), (0, 1), (2, 3)] {
+        *buckets.get_or_insert_default(bucket) += amount;
+    }
+    buckets.get_or_insert_with(4, || -1);
+    println!("Sparse buckets {buckets}");
+
+    let transfer = buckets.transaction(|staged| {
+        staged.update_nth(0, 0)?;
+        staged.update_nth(9, 1)
+    });
+    println!("Buckets after failed transaction {transfer:?} {buckets}");
+
+    let mut queue = LinkedList::new();
+    for job in ["build", "test", "deploy"] {
+        queue.push_back(job.to_string());
+    }
+    let held = queue.get_nth(1).unwrap().unwrap();
+    queue.push_back("rollback".to_string());
+    queue.push_front("lint".to_string());
+    let removed = queue.remove_nth(1);
+    let out_of_range = queue.remove_nth(9);
+    println!(
+        "Removed job {removed:?}, then {}",
+        out_of_range.unwrap_err()
+    );
+    let popped = [queue.pop_front(), queue.pop_front(), queue.pop_back()];
+    println!(
+        "Jobs popped {popped:?} (held one cloned, still {}), left {queue} of length {} (empty {})",
+        held.borrow().value,
+        queue.len(),
+        queue.is_empty()
+    );
+
+    let mut long = LinkedList::new();
+    for i in 0..1_000 {
+        long.push_back(i);
+    }
+    long.set_display_limit(5);
+    println!(
+        "Long list capped at {} {long}, debug {long:?}, full length {}",
+        long.display_limit(),
+        long.display_full().to_string().len()
+    );
+
+    let mut readings = LinkedList::new();
+    for reading in [0.1 + 0.2, 0.7] {
+        readings.push_back(reading);
+    }
+    let mut expected = LinkedList::new();
+    for reading in [0.3, 0.7] {
+        expected.push_back(reading);
+    }
+    let close = |a: &f64, b: &f64| (a - b).abs() < 1e-9;
+    println!(
+        "Readings {readings} equal {expected} within tolerance {}, case-insensitive ring match {}",
+        readings.eq_by(&expected, close),
+        ring.eq_by_iter(["B", "D", "A", "C"], |a, b| a.eq_ignore_ascii_case(b))
+    );
+
+    let mut errors = LinkedList::new();
+    errors.push_back("E: disk full");
+    let mut log = LinkedList::new();
+    for line in ["I: start", "E: timeout", "I: retry", "E: gave up"] {
+        log.push_back(line);
+    }
+    let moved = log.drain_matching_into(&mut errors, |line| line.starts_with("E:"));
+    println!("Moved {moved} errors {errors}, log left {log}");
+
+    if let Some(stats) = readings.stats() {
+        println!(
+            "Readings count {} min {} max {} sum {} mean {}",
+            stats.count, stats.min, stats.max, stats.sum, stats.mean
+        );
+    }
+
+    let mut hundred: LinkedList<i32> = (1..=100).collect();
+    hundred.extend(vec![101, 102]);
+    let mut more: LinkedList<i32> = (103..=105).collect();
+    hundred.append(&mut more);
+    println!(
+        "Collected {} numbers, sum {}, appended list left empty {}",
+        hundred.len(),
+        hundred.values().sum::<i32>(),
+        more.is_empty()
+    );
+
+    let mut squares = LinkedList::from_fn(5, |i| i * i);
+    squares.reverse();
+    let mut by_digits: LinkedList<i32> = [31, 4, 159, 26, 5].into_iter().collect();
+    by_digits.sort_by_key(|n| n.to_string().len());
+    let mut sorted = by_digits.clone();
+    sorted.sort();
+    sorted.sort_by(|a, b| b.cmp(a));
+    println!("Sorted by digit count {by_digits}, descending {sorted}");
+    let halvings = LinkedList::successors(Some(100), |n| (*n > 1).then_some(n / 2));
+    println!("Generated squares reversed {squares} and halvings {halvings}");
+
+    let mut evens = LinkedList::new();
+    let mut odds = LinkedList::new();
+    let mut primes = LinkedList::new();
+    for i in 1..=10 {
+        if i % 2 == 0 {
+            evens.push_back(i);
+        } else {
+            odds.push_back(i);
+        }
+    }
+    for i in [2, 3, 5, 7] {
+        primes.push_back(i);
+    }
+    println!("Evens {evens}, odds {odds}, primes {primes}");
+
+    let all = evens.union_sorted(odds);
+    println!("Union of evens and odds {all}");
+
+    let mut squares = LinkedList::new();
+    for i in [1, 4, 9] {
+        squares.push_back(i);
+    }
+    let not_primes = all.difference_sorted(primes);
+    println!("Difference with primes {not_primes}");
+    println!(
+        "Intersection with squares {}",
+        not_primes.intersection_sorted(squares)
+    );
+
+    let mut stages: LinkedList<Box<dyn Display>> = LinkedList::new();
+    stages.push_boxed(Box::new("parse"));
+    stages.push_boxed(Box::new(2));
+    for stage in stages.iter_dyn() {
+        println!("Pipeline stage {}", &*stage);
+    }
+
+    let mut mixed: LinkedList<Box<dyn Any>> = LinkedList::new();
+    mixed.push_boxed(Box::new("text"));
+    mixed.push_boxed(Box::new(42_u8));
+    mixed.for_each_dyn_mut(|item| {
+        if let Some(number) = item.downcast_mut::<u8>() {
+            *number += 1;
+        }
+    });
+    println!("Removed u8 element {:?}", mixed.remove_downcast::<u8>());
+
+    let mut old_ring = LinkedList::new();
+    for worker in ["a", "b", "c"] {
+        old_ring.push_back(worker);
+    }
+    if let Some((i, old, new)) = old_ring.first_divergence(&ring) {
+        println!("Rings first differ at {i}: {old} vs {new}");
+    }
+    let edits = old_ring.diff(&ring);
+    let _ = old_ring.apply(&edits);
+    println!("Old ring patched to {old_ring}");
+    for edit in edits {
+        match edit {
+            EditOp::Keep(worker) => println!("Ring diff keep {worker}"),
+            EditOp::Delete(worker) => println!("Ring diff delete {worker}"),
+            EditOp::Insert(worker) => println!("Ring diff insert {worker}"),
+        }
+    }
+
+    let mut tracked = LinkedList::new();
+    tracked.enable_content_hash();
+    tracked.push_back(1);
+    tracked.push_front(0);
+    let before = tracked.content_hash();
+    let _ = tracked.update_nth(1, 2);
+    println!(
+        "Content hash changed after update {}",
+        before != tracked.content_hash()
+    );
+    tracked.disable_content_hash();
+
+    let mut letters = LinkedList::new();
+    letters.push_back('b');
+    letters.push_back('c');
+    let mut reader = letters.cursor();
+    if let Ok(mut writer) = letters.cursor_at(1) {
+        letters.push_front('a');
+        let _ = reader.move_next();
+        let _ = writer.move_next();
+        println!(
+            "Cursors over {letters} at {:?} and {:?} (past the end {:?})",
+            reader.index(),
+            writer.index(),
+            writer.current().map(|node| node.is_none())
+        );
+        let _ = letters.rotate_to(1);
+        println!("Cursor still valid after rotate {}", reader.is_valid());
+        let mut seeker = letters.cursor();
+        let forward = seeker.seek_forward(5);
+        let backward = seeker.seek_backward(&letters, 2);
+        println!(
+            "Cursor sought {forward:?} forward and {backward:?} back to {:?}",
+            seeker.index()
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use serde::de::value::{Error, SeqDeserializer};
+use serde::de::DeserializeSeed;
+use serde::Deserialize;
+
+use rust_linked_list::{linked_list, LinkedList, NodePool};
+
+fn main() {
+    let pool = NodePool::<u8, 2>::new();
+    let parsed = LinkedList::<u8>::deserialize(SeqDeserializer::<_, Error>::new(1..=3_u8));
+    let pooled = (&pool).deserialize(SeqDeserializer::<_, Error>::new(4..=5_u8));
+    if let (Ok(parsed), Ok(pooled)) = (&parsed, &pooled) {
+        println!("Deserialized list {parsed} and pooled list {pooled}");
+    }
+
+    let healthy = linked_list![10];
+    if let Ok(json) = serde_json::to_string(&healthy) {
+        let back: Result<LinkedList<i32>, _> = serde_json::from_str(&json);
+        println!("Healthy entities as JSON {json}, read back {back:?}");
+    }
+}
@@ -0,0 +1,16 @@
+use rust_linked_list::SpillingList;
+
+fn main() {
+    if let Ok(mut spilling) = SpillingList::new(4) {
+        for i in 0..10 {
+            let _ = spilling.push_back(i);
+        }
+        let values: Vec<i32> = spilling.iter().flatten().collect();
+        println!(
+            "Spilling list of {} elements (empty {}) with {} in memory {values:?}",
+            spilling.len(),
+            spilling.is_empty(),
+            spilling.hot_len()
+        );
+    }
+}
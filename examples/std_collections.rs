@@ -0,0 +1,49 @@
+use rust_linked_list::{IndexedList, JournaledList, LruCache};
+
+fn main() {
+    let mut journaled = JournaledList::new(Vec::new()).with_sync(|_| Ok(()));
+    let _ = journaled.push_back(2);
+    let _ = journaled.push_front(1);
+    let _ = journaled.push_after_n(1, 3);
+    let _ = journaled.update_nth(0, 0);
+    let _ = journaled.rotate_to(1);
+    let (_, journal) = journaled.into_parts();
+    if let Ok(restored) = JournaledList::<i32, _>::replay(&journal[..], std::io::sink()) {
+        println!(
+            "List restored from the first {} journal bytes {}",
+            restored.replayed_len(),
+            restored.list()
+        );
+    }
+
+    let mut pages = LruCache::new(2);
+    pages.put("/", "home");
+    pages.put("/about", "about");
+    let home = pages.get(&"/").map(|page| *page);
+    let replaced = pages.put("/", "home v2");
+    pages.put("/blog", "blog");
+    println!(
+        "Page cache {} of {}: home {home:?} replaced {replaced:?}, about cached {}, blog {:?}",
+        pages.len(),
+        pages.capacity(),
+        pages.contains(&"/about"),
+        pages.peek(&"/blog").map(|page| *page)
+    );
+    let _ = pages.remove(&"/blog");
+    println!("Page cache after removing blog empty {}", pages.is_empty());
+
+    let mut users = IndexedList::new(|user: &(u32, &str)| user.0);
+    let _ = users.push_back((1, "ann"));
+    let _ = users.push_back((2, "bob"));
+    let _ = users.push_front((3, "cid"));
+    users.move_to_front(&2);
+    let removed = users.remove(&3);
+    println!(
+        "Indexed list {users:?} of {} users (empty {}), removed {removed:?}, has 1 {}, user 2 {:?}, names {:?}",
+        users.len(),
+        users.is_empty(),
+        users.contains_key(&1),
+        users.get(&2).map(|user| user.1),
+        users.iter().map(|user| user.1).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,113 @@
+use crate::{LinkedList, ValueRef};
+
+// Thin wrappers naming the O(1) ends of LinkedList, so callers don't have to
+// remember which one is cheap. LIFO, the top of the stack is the head.
+pub struct Stack<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack {
+            list: LinkedList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.list.push_front(value);
+    }
+
+    pub fn peek(&self) -> Option<ValueRef<'_, T>> {
+        self.list.front()
+    }
+}
+
+impl<T: Clone> Stack<T> {
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+// FIFO, values are enqueued at the tail and dequeued from the head.
+pub struct Queue<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Queue<T> {
+        Queue {
+            list: LinkedList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn enqueue(&mut self, value: T) {
+        self.list.push_back(value);
+    }
+
+    // The value dequeued next.
+    pub fn peek(&self) -> Option<ValueRef<'_, T>> {
+        self.list.front()
+    }
+}
+
+impl<T: Clone> Queue<T> {
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stack_and_queue_order() {
+        let mut stack = Stack::default();
+        let mut queue = Queue::default();
+        for value in 1..=3 {
+            stack.push(value);
+            queue.enqueue(value);
+        }
+        assert_eq!((stack.len(), queue.len()), (3, 3));
+        assert_eq!(*stack.peek().unwrap(), 3);
+        assert_eq!(*queue.peek().unwrap(), 1);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(queue.dequeue(), Some(1));
+        stack.push(4);
+        queue.enqueue(4);
+        let popped: Vec<_> = core::iter::from_fn(|| stack.pop()).collect();
+        let dequeued: Vec<_> = core::iter::from_fn(|| queue.dequeue()).collect();
+        assert_eq!(popped, [4, 2, 1]);
+        assert_eq!(dequeued, [2, 3, 4]);
+        assert!(stack.is_empty() && queue.is_empty());
+        assert!(stack.peek().is_none() && queue.peek().is_none());
+    }
+}
@@ -0,0 +1,254 @@
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, Weak};
+
+use crate::ListError;
+
+pub type ArcNodeHandle<T> = Arc<RwLock<ArcNode<T>>>;
+type ArcLink<T> = Option<ArcNodeHandle<T>>;
+
+#[derive(Debug)]
+pub struct ArcNode<T> {
+    pub value: T,
+    next: ArcLink<T>,
+}
+
+struct Ends<T> {
+    head: ArcLink<T>,
+    // Weak like the tail of LinkedList, every node has a single owning link
+    tail: Option<Weak<RwLock<ArcNode<T>>>>,
+    len: usize,
+}
+
+// LinkedList counterpart that is Send + Sync: nodes are Arc<RwLock<..>> and the
+// ends sit behind their own lock, so one list (e.g. in an Arc) can be pushed to
+// and split from several threads. Every method takes &self. Structural changes
+// hold the list lock for writing and then lock at most one node at a time, so
+// a handle must not be kept locked while calling into the list.
+pub struct ArcLinkedList<T> {
+    ends: RwLock<Ends<T>>,
+}
+
+// Follows the next links from the head the list had when `iter` was called;
+// each node is only locked while stepping past it.
+pub struct ArcListIter<T> {
+    current: ArcLink<T>,
+}
+
+impl<T> Iterator for ArcListIter<T> {
+    type Item = ArcNodeHandle<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = node.read().unwrap().next.clone();
+        Some(node)
+    }
+}
+
+impl<T> ArcLinkedList<T> {
+    pub fn new() -> ArcLinkedList<T> {
+        ArcLinkedList {
+            ends: RwLock::new(Ends {
+                head: None,
+                tail: None,
+                len: 0,
+            }),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Ends<T>> {
+        self.ends.read().unwrap()
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Ends<T>> {
+        self.ends.write().unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push_back(&self, value: T) {
+        let new = Arc::new(RwLock::new(ArcNode { value, next: None }));
+        let mut ends = self.write();
+        match ends.tail.as_ref().and_then(Weak::upgrade) {
+            None => ends.head = Some(new.clone()),
+            Some(tail) => tail.write().unwrap().next = Some(new.clone()),
+        }
+        ends.tail = Some(Arc::downgrade(&new));
+        ends.len += 1;
+    }
+
+    pub fn push_front(&self, value: T) {
+        let mut ends = self.write();
+        let new = Arc::new(RwLock::new(ArcNode {
+            value,
+            next: ends.head.take(),
+        }));
+        if ends.tail.is_none() {
+            ends.tail = Some(Arc::downgrade(&new));
+        }
+        ends.head = Some(new);
+        ends.len += 1;
+    }
+
+    pub fn iter(&self) -> ArcListIter<T> {
+        ArcListIter {
+            current: self.read().head.clone(),
+        }
+    }
+
+    pub fn get_nth(&self, nth: usize) -> Result<ArcLink<T>, ListError> {
+        let ends = self.read();
+        if nth >= ends.len {
+            return Err(ListError::IndexOutOfBounds {
+                index: nth,
+                len: ends.len,
+            });
+        }
+        let mut iter = ArcListIter {
+            current: ends.head.clone(),
+        };
+        Ok(iter.nth(nth))
+    }
+
+    pub fn update_nth(&self, nth: usize, value: T) -> Result<(), ListError> {
+        let node = self.get_nth(nth)?.unwrap();
+        node.write().unwrap().value = value;
+        Ok(())
+    }
+
+    // Like `LinkedList::split_off`: self keeps the first `at` elements and the
+    // rest is returned.
+    pub fn split_off(&self, at: usize) -> Result<ArcLinkedList<T>, ListError> {
+        let mut ends = self.write();
+        if at > ends.len {
+            return Err(ListError::IndexOutOfBounds {
+                index: at,
+                len: ends.len,
+            });
+        }
+        let suffix = ArcLinkedList::new();
+        let mut rest = suffix.write();
+        if at == 0 {
+            rest.head = ends.head.take();
+            rest.tail = ends.tail.take();
+        } else {
+            let mut iter = ArcListIter {
+                current: ends.head.clone(),
+            };
+            let last = iter.nth(at - 1).unwrap();
+            rest.head = last.write().unwrap().next.take();
+            if rest.head.is_some() {
+                rest.tail = ends.tail.replace(Arc::downgrade(&last));
+            }
+        }
+        rest.len = ends.len - at;
+        ends.len = at;
+        drop(rest);
+        Ok(suffix)
+    }
+}
+
+impl<T: Clone> ArcLinkedList<T> {
+    pub fn values(&self) -> Vec<T> {
+        self.iter()
+            .map(|node| node.read().unwrap().value.clone())
+            .collect()
+    }
+}
+
+impl<T> Default for ArcLinkedList<T> {
+    fn default() -> Self {
+        ArcLinkedList::new()
+    }
+}
+
+// Unlinks front to back like LinkedList, instead of recursing through `next`.
+impl<T> Drop for ArcLinkedList<T> {
+    fn drop(&mut self) {
+        let mut current = self.ends.get_mut().unwrap().head.take();
+        while let Some(node) = current {
+            current = match Arc::try_unwrap(node) {
+                Ok(node) => node.into_inner().unwrap().next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_push_get_split() {
+        assert_send_sync::<ArcLinkedList<String>>();
+        let list = ArcLinkedList::default();
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        assert_eq!(list.values(), [1, 2, 3]);
+        assert_eq!(list.get_nth(1).unwrap().unwrap().read().unwrap().value, 2);
+        assert_eq!(
+            list.get_nth(3).unwrap_err(),
+            ListError::IndexOutOfBounds { index: 3, len: 3 }
+        );
+        list.update_nth(0, 10).unwrap();
+
+        let rest = list.split_off(1).unwrap();
+        assert_eq!(list.values(), [10]);
+        assert_eq!(rest.values(), [2, 3]);
+        rest.push_back(4);
+        list.push_back(5);
+        assert_eq!(rest.values(), [2, 3, 4]);
+        assert_eq!(list.values(), [10, 5]);
+
+        let all = rest.split_off(0).unwrap();
+        assert!(rest.is_empty() && rest.iter().next().is_none());
+        assert_eq!(all.len(), 3);
+        assert!(all.split_off(4).is_err());
+        assert!(all.split_off(3).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let list = Arc::new(ArcLinkedList::new());
+        let workers: Vec<_> = (0..4)
+            .map(|t| {
+                let list = list.clone();
+                thread::spawn(move || {
+                    for i in 0..250 {
+                        if i % 2 == 0 {
+                            list.push_back(t * 1000 + i);
+                        } else {
+                            list.push_front(t * 1000 + i);
+                        }
+                        let _ = list.update_nth(0, t * 1000 + i);
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        assert_eq!(list.len(), 1000);
+        assert_eq!(list.iter().count(), 1000);
+        let rest = list.split_off(600).unwrap();
+        assert_eq!((list.len(), rest.len()), (600, 400));
+        assert_eq!(rest.iter().count(), 400);
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let list = ArcLinkedList::new();
+        for i in 0..1_000_000 {
+            list.push_back(i);
+        }
+        drop(list);
+    }
+}
@@ -0,0 +1,258 @@
+use std::fs::File;
+use std::io::{self, ErrorKind, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::{LinkedList, ListError};
+
+// Archive layout, all integers little endian:
+//
+//     MAGIC | count: u64 | offsets: (count + 1) x u64 | element bytes
+//
+// Element i is stored in data[offsets[i]..offsets[i + 1]], so any element is
+// found without reading the ones before it.
+const MAGIC: &[u8; 8] = b"RLLARCH1";
+const HEADER_LEN: usize = MAGIC.len() + 8;
+
+// Byte encoding of the elements stored in an archive.
+pub trait Archivable: Sized {
+    fn write_bytes(&self, out: &mut Vec<u8>);
+    fn read_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! archivable_number {
+    ($($number:ty),*) => {
+        $(
+            impl Archivable for $number {
+                fn write_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_bytes(bytes: &[u8]) -> Option<Self> {
+                    bytes.try_into().ok().map(<$number>::from_le_bytes)
+                }
+            }
+        )*
+    };
+}
+
+archivable_number!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl Archivable for String {
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+impl Archivable for Vec<u8> {
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self);
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(bytes.to_vec())
+    }
+}
+
+impl<T: Archivable> LinkedList<T> {
+    // Writes the list in the archive format read by `ArchivedList::open`.
+    pub fn freeze<W: Write>(&self, mut out: W) -> io::Result<()> {
+        let mut offsets = vec![0u64];
+        let mut data = Vec::new();
+        for node in self.iter().flatten() {
+            node.borrow().value.write_bytes(&mut data);
+            offsets.push(data.len() as u64);
+        }
+        out.write_all(MAGIC)?;
+        out.write_all(&(offsets.len() as u64 - 1).to_le_bytes())?;
+        for offset in offsets {
+            out.write_all(&offset.to_le_bytes())?;
+        }
+        out.write_all(&data)?;
+        out.flush()
+    }
+}
+
+// Read-only view of a frozen list. Elements are decoded from the mapping on
+// access, nothing is copied into heap nodes up front.
+pub struct ArchivedList<T> {
+    map: Mmap,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+pub struct ArchivedListIter<'a, T> {
+    list: &'a ArchivedList<T>,
+    next: usize,
+}
+
+impl<T: Archivable> Iterator for ArchivedListIter<'_, T> {
+    type Item = Result<T, ListError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.list.len {
+            return None;
+        }
+        self.next += 1;
+        Some(self.list.get_nth(self.next - 1))
+    }
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+impl<T: Archivable> ArchivedList<T> {
+    /// # Safety
+    ///
+    /// The file is mapped, not copied: it must not be modified or truncated,
+    /// by this process or any other, until the `ArchivedList` is dropped.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<ArchivedList<T>> {
+        let file = File::open(path)?;
+        // the view is read-only and the caller keeps the file unchanged while mapped
+        let map = unsafe { Mmap::map(&file)? };
+        let invalid = |msg| io::Error::new(ErrorKind::InvalidData, msg);
+
+        if map.len() < HEADER_LEN || &map[..MAGIC.len()] != MAGIC {
+            return Err(invalid("not a list archive"));
+        }
+        let len = usize::try_from(read_u64(&map, MAGIC.len()))
+            .map_err(|_| invalid("archive too large"))?;
+        let data_start = len
+            .checked_add(1)
+            .and_then(|offsets| offsets.checked_mul(8))
+            .and_then(|table| table.checked_add(HEADER_LEN))
+            .filter(|data_start| *data_start <= map.len())
+            .ok_or_else(|| invalid("truncated offset table"))?;
+        let data_len = read_u64(&map, data_start - 8);
+        if data_len != (map.len() - data_start) as u64 {
+            return Err(invalid("truncated element data"));
+        }
+
+        Ok(ArchivedList {
+            map,
+            len,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn element_bytes(&self, nth: usize) -> Option<&[u8]> {
+        let data = &self.map[HEADER_LEN + (self.len + 1) * 8..];
+        let start = usize::try_from(read_u64(&self.map, HEADER_LEN + nth * 8)).ok()?;
+        let end = usize::try_from(read_u64(&self.map, HEADER_LEN + (nth + 1) * 8)).ok()?;
+        data.get(start..end)
+    }
+
+    // O(1), only the element itself is read from the mapping.
+    pub fn get_nth(&self, nth: usize) -> Result<T, ListError> {
+        if nth >= self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index: nth,
+                len: self.len,
+            });
+        }
+        self.element_bytes(nth)
+            .and_then(T::read_bytes)
+            .ok_or(ListError::CorruptElement { index: nth })
+    }
+
+    pub fn iter(&self) -> ArchivedListIter<'_, T> {
+        ArchivedListIter {
+            list: self,
+            next: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // Each test owns its file and leaves it alone while it is mapped.
+    fn archive_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rll-{}-{name}.archive", std::process::id()))
+    }
+
+    #[test]
+    fn test_freeze_and_open() {
+        let mut list = LinkedList::<String>::new();
+        list.push_back("first".to_string());
+        list.push_back("".to_string());
+        list.push_back("third".to_string());
+        let path = archive_path("strings");
+        list.freeze(File::create(&path).unwrap()).unwrap();
+
+        let archived = unsafe { ArchivedList::<String>::open(&path) }.unwrap();
+
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived.get_nth(2).unwrap(), "third");
+        assert_eq!(archived.get_nth(1).unwrap(), "");
+        assert_eq!(
+            archived.get_nth(3),
+            Err(ListError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        let values: Vec<String> = archived.iter().map(Result::unwrap).collect();
+        assert_eq!(values, ["first", "", "third"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_open_empty_and_numbers() {
+        let path = archive_path("empty");
+        LinkedList::<u64>::new()
+            .freeze(File::create(&path).unwrap())
+            .unwrap();
+        let archived = unsafe { ArchivedList::<u64>::open(&path) }.unwrap();
+        assert!(archived.is_empty());
+        assert_eq!(archived.iter().count(), 0);
+
+        let mut list = LinkedList::<i32>::new();
+        for value in [-1, 0, 7] {
+            list.push_back(value);
+        }
+        list.freeze(File::create(&path).unwrap()).unwrap();
+        let archived = unsafe { ArchivedList::<i32>::open(&path) }.unwrap();
+        let values: Vec<i32> = archived.iter().map(Result::unwrap).collect();
+        assert_eq!(values, [-1, 0, 7]);
+        // read back with a wider element type every element is malformed
+        let mistyped = unsafe { ArchivedList::<i64>::open(&path) }.unwrap();
+        assert_eq!(
+            mistyped.get_nth(0),
+            Err(ListError::CorruptElement { index: 0 })
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_open_rejects_damaged_files() {
+        let mut list = LinkedList::<u32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        let mut bytes = Vec::new();
+        list.freeze(&mut bytes).unwrap();
+
+        let path = archive_path("damaged");
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(unsafe { ArchivedList::<u32>::open(&path) }.is_err());
+        fs::write(&path, &bytes[..HEADER_LEN + 4]).unwrap();
+        assert!(unsafe { ArchivedList::<u32>::open(&path) }.is_err());
+        fs::write(&path, b"not an archive").unwrap();
+        assert!(unsafe { ArchivedList::<u32>::open(&path) }.is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::mem;
+
+// Index of a node in the slab of one ArenaList, with the generation of the
+// slot when the value was pushed. Once the value is removed the slot moves on
+// to the next generation, so the key is stale even after the slot is reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaKey {
+    index: u32,
+    generation: u32,
+}
+
+enum ArenaSlot<T> {
+    Occupied {
+        value: T,
+        next: Option<u32>,
+        generation: u32,
+    },
+    // chained through the free slots like the free list of StaticList
+    Free {
+        next_free: Option<u32>,
+        generation: u32,
+    },
+}
+
+// Growable counterpart of StaticList: nodes live side by side in one Vec and
+// link to each other by u32 index, so a push is one slot write instead of an
+// allocation and freed slots are reused before the slab grows. Keys handed out
+// by the pushes reach a node directly, `insert_after` and `get` are O(1).
+pub struct ArenaList<T> {
+    slots: Vec<ArenaSlot<T>>,
+    head: Option<u32>,
+    tail: Option<u32>,
+    free: Option<u32>,
+    len: usize,
+}
+
+pub struct ArenaListIter<'a, T> {
+    list: &'a ArenaList<T>,
+    current: Option<u32>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for ArenaListIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, next) = self.list.occupied(self.current?);
+        self.current = next;
+        self.remaining -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for ArenaListIter<'_, T> {}
+
+impl<T> ArenaList<T> {
+    pub fn new() -> ArenaList<T> {
+        ArenaList::with_capacity(0)
+    }
+
+    // Room for `capacity` nodes before the slab has to grow.
+    pub fn with_capacity(capacity: usize) -> ArenaList<T> {
+        ArenaList {
+            slots: Vec::with_capacity(capacity),
+            head: None,
+            tail: None,
+            free: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Nodes the slab holds without growing, free slots included.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    // Linked slots are always occupied.
+    fn occupied(&self, slot: u32) -> (&T, Option<u32>) {
+        match &self.slots[slot as usize] {
+            ArenaSlot::Occupied { value, next, .. } => (value, *next),
+            ArenaSlot::Free { .. } => unreachable!("free slot {slot} is linked"),
+        }
+    }
+
+    fn next_mut(&mut self, slot: u32) -> &mut Option<u32> {
+        match &mut self.slots[slot as usize] {
+            ArenaSlot::Occupied { next, .. } => next,
+            ArenaSlot::Free { .. } => unreachable!("free slot {slot} is linked"),
+        }
+    }
+
+    // Panics once the slab would need more than u32::MAX slots.
+    fn alloc(&mut self, value: T, next: Option<u32>) -> ArenaKey {
+        self.len += 1;
+        match self.free {
+            Some(slot) => {
+                let ArenaSlot::Free {
+                    next_free,
+                    generation,
+                } = self.slots[slot as usize]
+                else {
+                    unreachable!("occupied slot {slot} is free")
+                };
+                self.free = next_free;
+                self.slots[slot as usize] = ArenaSlot::Occupied {
+                    value,
+                    next,
+                    generation,
+                };
+                ArenaKey {
+                    index: slot,
+                    generation,
+                }
+            }
+            None => {
+                let slot = u32::try_from(self.slots.len()).expect("arena full");
+                self.slots.push(ArenaSlot::Occupied {
+                    value,
+                    next,
+                    generation: 0,
+                });
+                ArenaKey {
+                    index: slot,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    // The slot is unlinked by the caller and goes back to the free chain, one
+    // generation on. Generations wrap after 2^32 reuses of the same slot.
+    fn release(&mut self, slot: u32) -> T {
+        let generation = match self.slots[slot as usize] {
+            ArenaSlot::Occupied { generation, .. } => generation.wrapping_add(1),
+            ArenaSlot::Free { .. } => unreachable!("free slot {slot} is linked"),
+        };
+        let free = ArenaSlot::Free {
+            next_free: self.free,
+            generation,
+        };
+        self.free = Some(slot);
+        self.len -= 1;
+        match mem::replace(&mut self.slots[slot as usize], free) {
+            ArenaSlot::Occupied { value, .. } => value,
+            ArenaSlot::Free { .. } => unreachable!("free slot {slot} is linked"),
+        }
+    }
+
+    // The slot of a key whose value is still in the list.
+    fn live(&self, key: ArenaKey) -> Option<u32> {
+        match self.slots.get(key.index as usize)? {
+            ArenaSlot::Occupied { generation, .. } if *generation == key.generation => {
+                Some(key.index)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn push_back(&mut self, value: T) -> ArenaKey {
+        let key = self.alloc(value, None);
+        match self.tail {
+            None => self.head = Some(key.index),
+            Some(tail) => *self.next_mut(tail) = Some(key.index),
+        }
+        self.tail = Some(key.index);
+        key
+    }
+
+    pub fn push_front(&mut self, value: T) -> ArenaKey {
+        let key = self.alloc(value, self.head);
+        if self.head.is_none() {
+            self.tail = Some(key.index);
+        }
+        self.head = Some(key.index);
+        key
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let slot = self.head?;
+        self.head = self.occupied(slot).1;
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        Some(self.release(slot))
+    }
+
+    // None if the key's value was removed, even if its slot holds a newer one.
+    pub fn get(&self, key: ArenaKey) -> Option<&T> {
+        let slot = self.live(key)?;
+        Some(self.occupied(slot).0)
+    }
+
+    pub fn get_mut(&mut self, key: ArenaKey) -> Option<&mut T> {
+        let slot = self.live(key)?;
+        match &mut self.slots[slot as usize] {
+            ArenaSlot::Occupied { value, .. } => Some(value),
+            ArenaSlot::Free { .. } => None,
+        }
+    }
+
+    // O(1), None if the key's value was removed.
+    pub fn insert_after(&mut self, key: ArenaKey, value: T) -> Option<ArenaKey> {
+        let slot = self.live(key)?;
+        let next = *self.next_mut(slot);
+        let new = self.alloc(value, next);
+        *self.next_mut(slot) = Some(new.index);
+        if self.tail == Some(slot) {
+            self.tail = Some(new.index);
+        }
+        Some(new)
+    }
+
+    // Removes the value after the key's one, O(1).
+    pub fn remove_after(&mut self, key: ArenaKey) -> Option<T> {
+        let slot = self.live(key)?;
+        let removed = (*self.next_mut(slot))?;
+        let next = self.occupied(removed).1;
+        *self.next_mut(slot) = next;
+        if next.is_none() {
+            self.tail = Some(slot);
+        }
+        Some(self.release(removed))
+    }
+
+    // Drops the values but keeps the slab for the next pushes. The slots go
+    // through `release`, so keys from before stay stale.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn iter(&self) -> ArenaListIter<'_, T> {
+        ArenaListIter {
+            list: self,
+            current: self.head,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        ArenaList::new()
+    }
+}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut list = ArenaList::with_capacity(iter.size_hint().0);
+        for value in iter {
+            list.push_back(value);
+        }
+        list
+    }
+}
+
+impl<T: Debug> Debug for ArenaList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_and_slot_reuse() {
+        let mut list = ArenaList::with_capacity(4);
+        let b = list.push_back('b');
+        let a = list.push_front('a');
+        let d = list.push_back('d');
+        let c = list.insert_after(b, 'c').unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), [&'a', &'b', &'c', &'d']);
+        assert_eq!(list.capacity(), 4);
+
+        assert_eq!(list.remove_after(c), Some('d'));
+        assert_eq!(list.get(d), None);
+        assert_eq!(list.remove_after(c), None);
+        // the freed slot is reused before the slab grows, the old key stays stale
+        let e = list.push_back('e');
+        assert_eq!(e.index, d.index);
+        assert_ne!(e, d);
+        assert_eq!(list.get(d), None);
+        assert!(list.insert_after(d, 'x').is_none());
+        assert_eq!(list.get(e), Some(&'e'));
+        assert_eq!(list.pop_front(), Some('a'));
+        assert!(list.insert_after(a, 'x').is_none());
+        *list.get_mut(b).unwrap() = 'B';
+        assert_eq!(list.iter().collect::<Vec<_>>(), [&'B', &'c', &'e']);
+        assert_eq!(list.iter().len(), list.len());
+        assert_eq!(list.slots.len(), 4);
+
+        list.clear();
+        assert!(list.is_empty() && list.iter().next().is_none());
+        let f = list.push_back('f');
+        assert_eq!(format!("{list:?}"), "['f']");
+        assert_eq!(list.get(e), None);
+        assert_eq!(list.get(f), Some(&'f'));
+        assert_eq!(list.slots.len(), 4);
+    }
+
+    #[test]
+    fn test_matches_vec_model() {
+        let mut list: ArenaList<u32> = (0..10).collect();
+        let mut model: Vec<u32> = (0..10).collect();
+        let mut seed = 5u32;
+        for step in 0..2000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            match (seed >> 16) % 3 {
+                0 => {
+                    list.push_back(step);
+                    model.push(step);
+                }
+                1 => {
+                    list.push_front(step);
+                    model.insert(0, step);
+                }
+                _ => {
+                    assert_eq!(
+                        list.pop_front(),
+                        (!model.is_empty()).then(|| model.remove(0))
+                    );
+                }
+            }
+            assert_eq!(list.len(), model.len());
+        }
+        assert!(list.iter().eq(model.iter()));
+        // every slot is either linked or on the free chain
+        let free = list
+            .slots
+            .iter()
+            .filter(|slot| matches!(slot, ArenaSlot::Free { .. }))
+            .count();
+        assert_eq!(free + list.len(), list.slots.len());
+    }
+}
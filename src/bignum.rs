@@ -0,0 +1,337 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign, Mul};
+use core::str::FromStr;
+
+use crate::LinkedList;
+
+const BASE: u64 = 1_000_000_000;
+const LIMB_DIGITS: usize = 9;
+
+// Unsigned integer of any size, one base 10^9 limb per node, least significant
+// first so carries run along the next links. There are no trailing zero limbs,
+// zero is the empty list.
+#[derive(Debug, Clone)]
+pub struct BigNum {
+    limbs: LinkedList<u32>,
+}
+
+impl BigNum {
+    pub fn zero() -> BigNum {
+        BigNum {
+            limbs: LinkedList::new(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.head.is_none()
+    }
+
+    fn limb_values(&self) -> Vec<u32> {
+        self.limbs
+            .iter()
+            .flatten()
+            .map(|node| node.borrow().value)
+            .collect()
+    }
+
+    // Cuts off the zero limbs at the most significant end.
+    fn normalize(&mut self) {
+        let mut last_non_zero = None;
+        let mut len = 0;
+        for (i, node) in self.limbs.iter().flatten().enumerate() {
+            if node.borrow().value != 0 {
+                last_non_zero = Some(node);
+                len = i + 1;
+            }
+        }
+        match &last_non_zero {
+            None => self.limbs.head = None,
+            Some(last) => last.borrow_mut().next = None,
+        }
+        self.limbs.set_tail(last_non_zero.as_ref());
+        self.limbs.len = len;
+    }
+
+    pub fn checked_sub(&self, other: &BigNum) -> Option<BigNum> {
+        if self < other {
+            return None;
+        }
+        let mut result = self.clone();
+        let mut others = other.limbs.iter().flatten();
+        let mut borrow = 0;
+        for node in result.limbs.iter().flatten() {
+            let subtrahend = match others.next() {
+                Some(other) => u64::from(other.borrow().value) + borrow,
+                None if borrow == 0 => break,
+                None => borrow,
+            };
+            let mut node = node.borrow_mut();
+            let value = u64::from(node.value);
+            (node.value, borrow) = if value >= subtrahend {
+                ((value - subtrahend) as u32, 0)
+            } else {
+                ((value + BASE - subtrahend) as u32, 1)
+            };
+        }
+        result.normalize();
+        Some(result)
+    }
+}
+
+impl Default for BigNum {
+    fn default() -> Self {
+        BigNum::zero()
+    }
+}
+
+impl From<u64> for BigNum {
+    fn from(mut value: u64) -> Self {
+        let mut number = BigNum::zero();
+        while value > 0 {
+            number.limbs.push_back((value % BASE) as u32);
+            value /= BASE;
+        }
+        number
+    }
+}
+
+// Why a string didn't parse as a BigNum, like `core::num::ParseIntError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseBigNumError {
+    Empty,
+    // the byte offset of the first character that isn't an ASCII digit
+    InvalidDigit { index: usize },
+}
+
+impl Display for ParseBigNumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseBigNumError::Empty => write!(f, "cannot parse a number from an empty string"),
+            ParseBigNumError::InvalidDigit { index } => {
+                write!(f, "invalid digit at index {index}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseBigNumError {}
+
+impl FromStr for BigNum {
+    type Err = ParseBigNumError;
+
+    fn from_str(digits: &str) -> Result<Self, Self::Err> {
+        if digits.is_empty() {
+            return Err(ParseBigNumError::Empty);
+        }
+        if let Some(index) = digits.bytes().position(|digit| !digit.is_ascii_digit()) {
+            return Err(ParseBigNumError::InvalidDigit { index });
+        }
+        let mut number = BigNum::zero();
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(LIMB_DIGITS);
+            number.limbs.push_back(digits[start..end].parse().unwrap());
+            end = start;
+        }
+        number.normalize();
+        Ok(number)
+    }
+}
+
+impl Display for BigNum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let limbs = self.limb_values();
+        let mut limbs = limbs.iter().rev();
+        match limbs.next() {
+            None => write!(f, "0"),
+            Some(most_significant) => {
+                write!(f, "{most_significant}")?;
+                for limb in limbs {
+                    write!(f, "{limb:0width$}", width = LIMB_DIGITS)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PartialEq for BigNum {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for BigNum {}
+
+impl PartialOrd for BigNum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BigNum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (ours, theirs) = (self.limb_values(), other.limb_values());
+        ours.len()
+            .cmp(&theirs.len())
+            .then_with(|| ours.iter().rev().cmp(theirs.iter().rev()))
+    }
+}
+
+// Adds into the existing nodes, new limbs are only pushed for a longer addend
+// or a final carry.
+impl AddAssign<&BigNum> for BigNum {
+    fn add_assign(&mut self, other: &BigNum) {
+        let mut others = other.limbs.iter().flatten();
+        let mut current = self.limbs.head.clone();
+        let mut carry = 0;
+        loop {
+            let addend = others.next().map(|node| u64::from(node.borrow().value));
+            if addend.is_none() && carry == 0 {
+                break;
+            }
+            let sum = addend.unwrap_or(0) + carry;
+            match current.take() {
+                Some(node) => {
+                    let sum = sum + u64::from(node.borrow().value);
+                    node.borrow_mut().value = (sum % BASE) as u32;
+                    carry = sum / BASE;
+                    current = node.borrow().next.clone();
+                }
+                None => {
+                    self.limbs.push_back((sum % BASE) as u32);
+                    carry = sum / BASE;
+                }
+            }
+        }
+    }
+}
+
+impl Add for &BigNum {
+    type Output = BigNum;
+
+    fn add(self, other: &BigNum) -> BigNum {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Mul for &BigNum {
+    type Output = BigNum;
+
+    fn mul(self, other: &BigNum) -> BigNum {
+        let mut product = BigNum::zero();
+        if self.is_zero() || other.is_zero() {
+            return product;
+        }
+        for _ in 0..self.limbs.iter().count() + other.limbs.iter().count() {
+            product.limbs.push_back(0);
+        }
+        for (i, factor) in self.limbs.iter().flatten().enumerate() {
+            let factor = u64::from(factor.borrow().value);
+            let mut row = product.limbs.iter().flatten().skip(i);
+            let mut carry = 0;
+            for limb in other.limbs.iter().flatten() {
+                let node = row.next().unwrap();
+                let mut node = node.borrow_mut();
+                let sum = u64::from(node.value) + factor * u64::from(limb.borrow().value) + carry;
+                node.value = (sum % BASE) as u32;
+                carry = sum / BASE;
+            }
+            // the product has room for all carries
+            while carry > 0 {
+                let node = row.next().unwrap();
+                let mut node = node.borrow_mut();
+                let sum = u64::from(node.value) + carry;
+                node.value = (sum % BASE) as u32;
+                carry = sum / BASE;
+            }
+        }
+        product.normalize();
+        product
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn big(digits: &str) -> BigNum {
+        digits.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(big("0").to_string(), "0");
+        assert_eq!(big("000123").to_string(), "123");
+        assert_eq!(
+            big("123456789012345678901").to_string(),
+            "123456789012345678901"
+        );
+        assert_eq!(
+            big("1000000000000000000").to_string(),
+            "1000000000000000000"
+        );
+        assert_eq!(BigNum::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert!(big("0").is_zero());
+        assert_eq!("".parse::<BigNum>(), Err(ParseBigNumError::Empty));
+        assert_eq!(
+            "12a".parse::<BigNum>(),
+            Err(ParseBigNumError::InvalidDigit { index: 2 })
+        );
+        assert!(big("999999999999") > big("99999999999"));
+        assert!(big("2000000000") > big("1999999999"));
+        assert_eq!(big("00"), BigNum::zero());
+    }
+
+    #[test]
+    fn test_add_and_sub() {
+        let mut sum = big("999999999999999999");
+        sum += &BigNum::from(1);
+        assert_eq!(sum.to_string(), "1000000000000000000");
+        assert_eq!(
+            (&big("1") + &big("99999999999999999999")).to_string(),
+            "100000000000000000000"
+        );
+        assert_eq!((&BigNum::zero() + &big("5")).to_string(), "5");
+
+        let difference = big("1000000000000000000").checked_sub(&BigNum::from(1));
+        assert_eq!(difference.unwrap().to_string(), "999999999999999999");
+        assert_eq!(big("123").checked_sub(&big("123")), Some(BigNum::zero()));
+        assert!(big("12").checked_sub(&big("13")).is_none());
+        assert_eq!(
+            big("3000000000000000001").checked_sub(&big("1000000000000000001")),
+            Some(big("2000000000000000000"))
+        );
+        assert_eq!(
+            big("5000000000000000000000")
+                .checked_sub(&big("4999999999999999999999"))
+                .unwrap()
+                .to_string(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(
+            (&BigNum::from(u64::MAX) * &BigNum::from(u64::MAX)).to_string(),
+            (u128::from(u64::MAX) * u128::from(u64::MAX)).to_string()
+        );
+        assert_eq!(
+            (&big("123456789123456789") * &big("987654321987654321")).to_string(),
+            "121932631356500531347203169112635269"
+        );
+        assert!((&big("0") * &big("42")).is_zero());
+
+        let mut factorial = BigNum::from(1);
+        for i in 1..=30 {
+            factorial = &factorial * &BigNum::from(i);
+        }
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+    }
+}
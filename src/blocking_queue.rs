@@ -0,0 +1,196 @@
+use std::rc::Rc;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+
+use crate::LinkedList;
+
+struct Slots<T> {
+    list: LinkedList<T>,
+    len: usize,
+}
+
+// The list's nodes are reference counted with Rc, which is why it isn't Send.
+// Here no handle ever leaves the mutex: values are moved in and out and every
+// refcount change happens while the lock is held, so threads never race on one.
+unsafe impl<T: Send> Send for Slots<T> {}
+
+impl<T> Slots<T> {
+    fn pop(&mut self) -> Option<T> {
+        let node = self.list.pop_front_node()?;
+        self.len -= 1;
+        // unlinked from the list, this is the only handle left
+        Rc::try_unwrap(node)
+            .ok()
+            .map(|node| node.into_inner().value)
+    }
+}
+
+// FIFO queue shared between threads (e.g. in an Arc). `push` waits while the
+// queue holds `capacity` values, `pop` waits while it's empty.
+pub struct BoundedBlockingQueue<T> {
+    slots: Mutex<Slots<T>>,
+    capacity: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T: Send> BoundedBlockingQueue<T> {
+    pub fn new(capacity: usize) -> BoundedBlockingQueue<T> {
+        assert!(capacity > 0, "capacity must be non-zero");
+        BoundedBlockingQueue {
+            slots: Mutex::new(Slots {
+                list: LinkedList::new(),
+                len: 0,
+            }),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Slots<T>> {
+        self.slots.lock().unwrap()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&self, value: T) {
+        let slots = self.lock();
+        let mut slots = self
+            .not_full
+            .wait_while(slots, |slots| slots.len == self.capacity)
+            .unwrap();
+        slots.list.push_back(value);
+        slots.len += 1;
+        self.not_empty.notify_one();
+    }
+
+    // Gives the value back if the queue stayed full for `timeout`.
+    pub fn push_timeout(&self, value: T, timeout: Duration) -> Result<(), T> {
+        let slots = self.lock();
+        let (mut slots, result) = self
+            .not_full
+            .wait_timeout_while(slots, timeout, |slots| slots.len == self.capacity)
+            .unwrap();
+        if result.timed_out() {
+            return Err(value);
+        }
+        slots.list.push_back(value);
+        slots.len += 1;
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn pop(&self) -> T {
+        let slots = self.lock();
+        let mut slots = self
+            .not_empty
+            .wait_while(slots, |slots| slots.len == 0)
+            .unwrap();
+        let value = slots.pop().unwrap();
+        self.not_full.notify_one();
+        value
+    }
+
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let slots = self.lock();
+        let (mut slots, _) = self
+            .not_empty
+            .wait_timeout_while(slots, timeout, |slots| slots.len == 0)
+            .unwrap();
+        let value = slots.pop()?;
+        self.not_full.notify_one();
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_producers_and_consumers() {
+        let queue = Arc::new(BoundedBlockingQueue::new(4));
+        let producers: Vec<_> = (0..3)
+            .map(|producer| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        queue.push(producer * 1000 + i);
+                        assert!(queue.len() <= 4);
+                    }
+                })
+            })
+            .collect();
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = queue.clone();
+                thread::spawn(move || (0..150).map(|_| queue.pop()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let popped: Vec<Vec<i32>> = consumers
+            .into_iter()
+            .map(|consumer| consumer.join().unwrap())
+            .collect();
+        // each consumer sees every producer's values in the order they were pushed
+        for values in &popped {
+            for producer in 0..3 {
+                let from_producer: Vec<_> = values
+                    .iter()
+                    .filter(|value| **value / 1000 == producer)
+                    .collect();
+                assert!(from_producer.windows(2).all(|pair| pair[0] < pair[1]));
+            }
+        }
+        let mut popped: Vec<i32> = popped.into_iter().flatten().collect();
+        popped.sort();
+        popped.dedup();
+        assert_eq!(popped.len(), 300);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_timeouts() {
+        let queue = BoundedBlockingQueue::new(2);
+        assert_eq!(queue.pop_timeout(Duration::from_millis(10)), None);
+
+        queue.push("a".to_string());
+        queue.push_timeout("b".to_string(), Duration::ZERO).unwrap();
+        let rejected = queue.push_timeout("c".to_string(), Duration::from_millis(10));
+        assert_eq!(rejected, Err("c".to_string()));
+        assert_eq!(queue.len(), queue.capacity());
+
+        assert_eq!(queue.pop(), "a");
+        assert_eq!(queue.pop_timeout(Duration::ZERO).as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_push_waits_for_room() {
+        let queue = Arc::new(BoundedBlockingQueue::new(1));
+        queue.push(1);
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || queue.push(2))
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(queue.pop(), 1);
+        producer.join().unwrap();
+        assert_eq!(queue.pop(), 2);
+    }
+}
@@ -0,0 +1,616 @@
+// Single-owner list without reference counting or RefCell: every node is
+// allocated on its own and owned by the list alone, so values are reached as
+// plain `&T` / `&mut T`. The price is that there are no node handles to share,
+// for those use the crate's root `LinkedList`.
+use alloc::alloc::handle_alloc_error;
+use core::alloc::Layout;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::ptr::{addr_of, addr_of_mut, NonNull};
+
+use crate::{Global, ListError, NodeAlloc};
+
+type BoxedLink<T> = Option<NonNull<BoxedNode<T>>>;
+
+struct BoxedNode<T> {
+    value: T,
+    next: BoxedLink<T>,
+}
+
+// The links are raw pointers to nodes from the list's allocator, like std's
+// LinkedList, so the tail can point into the chain the head owns. A node is
+// given back exactly once, when it's unlinked. Nodes come from the global
+// heap unless the list is built with `new_in`.
+//
+// Unlinked nodes are kept on a spare chain and reused by the next pushes, so a
+// list that is pushed and popped at a steady length stops allocating. Like a
+// Vec's buffer, the spares are only given back by `shrink_to_fit` or drop.
+pub struct LinkedList<T, A: NodeAlloc = Global> {
+    head: BoxedLink<T>,
+    tail: BoxedLink<T>,
+    len: usize,
+    // nodes without a value, chained through `next`
+    spare: BoxedLink<T>,
+    spare_len: usize,
+    alloc: A,
+    // the nodes the links point to are owned by the list
+    _nodes: PhantomData<BoxedNode<T>>,
+}
+
+// nodes are only reached through the list that owns them
+unsafe impl<T: Send, A: NodeAlloc + Send> Send for LinkedList<T, A> {}
+unsafe impl<T: Sync, A: NodeAlloc + Sync> Sync for LinkedList<T, A> {}
+
+pub struct Iter<'a, T> {
+    current: BoxedLink<T>,
+    len: usize,
+    _list: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T> {
+    current: BoxedLink<T>,
+    len: usize,
+    _list: PhantomData<&'a mut T>,
+}
+
+pub struct IntoIter<T, A: NodeAlloc = Global> {
+    list: LinkedList<T, A>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // the list is borrowed for 'a, its nodes stay where they are
+        let node = unsafe { self.current?.as_ref() };
+        self.current = node.next;
+        self.len -= 1;
+        Some(&node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        // borrowed mutably for 'a, and every node is handed out once
+        let node = unsafe { self.current?.as_mut() };
+        self.current = node.next;
+        self.len -= 1;
+        Some(&mut node.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+impl<T, A: NodeAlloc> Iterator for IntoIter<T, A> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T, A: NodeAlloc> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList::new_in(Global)
+    }
+
+    pub fn with_capacity(capacity: usize) -> LinkedList<T> {
+        LinkedList::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: NodeAlloc> LinkedList<T, A> {
+    // Empty list whose nodes are allocated through `alloc`.
+    pub fn new_in(alloc: A) -> LinkedList<T, A> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            spare: None,
+            spare_len: 0,
+            alloc,
+            _nodes: PhantomData,
+        }
+    }
+
+    // Allocates nodes for `capacity` values up front.
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> LinkedList<T, A> {
+        let mut list = LinkedList::new_in(alloc);
+        list.reserve(capacity);
+        list
+    }
+
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Values the list holds without allocating, spare nodes included.
+    pub fn capacity(&self) -> usize {
+        self.len + self.spare_len
+    }
+
+    // Makes sure `additional` more values can be pushed without allocating.
+    pub fn reserve(&mut self, additional: usize) {
+        while self.spare_len < additional {
+            let node = self.allocate_node();
+            self.push_spare(node);
+        }
+    }
+
+    // Gives the spare nodes back to the allocator.
+    pub fn shrink_to_fit(&mut self) {
+        while let Some(node) = self.pop_spare() {
+            let layout = Layout::new::<BoxedNode<T>>();
+            // spare nodes hold no value, only the link
+            unsafe { self.alloc.deallocate(node.cast(), layout) };
+        }
+    }
+
+    fn out_of_bounds(&self, index: usize) -> ListError {
+        ListError::IndexOutOfBounds {
+            index,
+            len: self.len,
+        }
+    }
+
+    fn allocate_node(&self) -> NonNull<BoxedNode<T>> {
+        let layout = Layout::new::<BoxedNode<T>>();
+        match self.alloc.allocate(layout) {
+            Some(block) => block.cast(),
+            None => handle_alloc_error(layout),
+        }
+    }
+
+    // Only the link of a spare node is initialized, it's reached through raw
+    // field pointers and never as a whole `BoxedNode`.
+    fn push_spare(&mut self, node: NonNull<BoxedNode<T>>) {
+        unsafe { addr_of_mut!((*node.as_ptr()).next).write(self.spare) };
+        self.spare = Some(node);
+        self.spare_len += 1;
+    }
+
+    fn pop_spare(&mut self) -> BoxedLink<T> {
+        let node = self.spare?;
+        self.spare = unsafe { addr_of!((*node.as_ptr()).next).read() };
+        self.spare_len -= 1;
+        Some(node)
+    }
+
+    fn alloc(&mut self, value: T, next: BoxedLink<T>) -> NonNull<BoxedNode<T>> {
+        let node = match self.pop_spare() {
+            Some(node) => node,
+            None => self.allocate_node(),
+        };
+        // the block fits a node and nothing else points to it yet
+        unsafe { node.as_ptr().write(BoxedNode { value, next }) };
+        node
+    }
+
+    // Moves the value out of a node that was just unlinked, the node becomes
+    // a spare.
+    fn free(&mut self, node: NonNull<BoxedNode<T>>) -> T {
+        let value = unsafe { addr_of!((*node.as_ptr()).value).read() };
+        self.push_spare(node);
+        value
+    }
+
+    // Linked nodes are only reached through the list, so a borrow of one
+    // follows the borrow of the list.
+    fn node(&self, node: NonNull<BoxedNode<T>>) -> &BoxedNode<T> {
+        unsafe { node.as_ref() }
+    }
+
+    fn node_mut(&mut self, mut node: NonNull<BoxedNode<T>>) -> &mut BoxedNode<T> {
+        unsafe { node.as_mut() }
+    }
+
+    // Node at an index in bounds.
+    fn nth_node(&self, index: usize) -> NonNull<BoxedNode<T>> {
+        let mut node = self.head.unwrap();
+        for _ in 0..index {
+            node = self.node(node).next.unwrap();
+        }
+        node
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let new = self.alloc(value, None);
+        match self.tail {
+            None => self.head = Some(new),
+            Some(tail) => self.node_mut(tail).next = Some(new),
+        }
+        self.tail = Some(new);
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let new = self.alloc(value, self.head);
+        if self.tail.is_none() {
+            self.tail = Some(new);
+        }
+        self.head = Some(new);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        self.head = self.node(head).next;
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        self.len -= 1;
+        Some(self.free(head))
+    }
+
+    // O(n): nodes only link forward, the new tail is found from the head.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len <= 1 {
+            return self.pop_front();
+        }
+        let new_tail = self.nth_node(self.len - 2);
+        let tail = self.node_mut(new_tail).next.take().unwrap();
+        self.tail = Some(new_tail);
+        self.len -= 1;
+        Some(self.free(tail))
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        Some(&self.node(self.head?).value)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        Some(&self.node(self.tail?).value)
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        Some(&mut self.node_mut(self.head?).value)
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        Some(&mut self.node_mut(self.tail?).value)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        Some(&self.node(self.nth_node(index)).value)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let node = self.nth_node(index);
+        Some(&mut self.node_mut(node).value)
+    }
+
+    // Like `Vec::insert`: the value ends up at `index`.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), ListError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        if index == 0 {
+            self.push_front(value);
+        } else if index == self.len {
+            self.push_back(value);
+        } else {
+            let prev = self.nth_node(index - 1);
+            let new = self.alloc(value, self.node(prev).next);
+            self.node_mut(prev).next = Some(new);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, ListError> {
+        if index >= self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        if index == 0 {
+            return Ok(self.pop_front().unwrap());
+        }
+        let prev = self.nth_node(index - 1);
+        let node = self.node(prev).next.unwrap();
+        let next = self.node(node).next;
+        self.node_mut(prev).next = next;
+        if next.is_none() {
+            self.tail = Some(prev);
+        }
+        self.len -= 1;
+        Ok(self.free(node))
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            current: self.head,
+            len: self.len,
+            _list: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            current: self.head,
+            len: self.len,
+            _list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+// The copy allocates from a clone of the allocator.
+impl<T: Clone, A: NodeAlloc + Clone> Clone for LinkedList<T, A> {
+    fn clone(&self) -> Self {
+        let mut list = LinkedList::new_in(self.alloc.clone());
+        list.extend(self.iter().cloned());
+        list
+    }
+}
+
+impl<T: PartialEq, A: NodeAlloc, B: NodeAlloc> PartialEq<LinkedList<T, B>> for LinkedList<T, A> {
+    fn eq(&self, other: &LinkedList<T, B>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, A: NodeAlloc> Eq for LinkedList<T, A> {}
+
+impl<T: Debug, A: NodeAlloc> Debug for LinkedList<T, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, A: NodeAlloc> Extend<T> for LinkedList<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T, A: NodeAlloc> IntoIterator for LinkedList<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T, A: NodeAlloc> IntoIterator for &'a LinkedList<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: NodeAlloc> IntoIterator for &'a mut LinkedList<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+// Frees front to back, no recursion through `next`.
+impl<T, A: NodeAlloc> Drop for LinkedList<T, A> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+        self.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BumpAlloc;
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+    use core::cell::Cell;
+
+    // Global heap that counts the nodes it has out.
+    #[derive(Default)]
+    struct Counting {
+        live: Cell<usize>,
+    }
+
+    unsafe impl NodeAlloc for &Counting {
+        fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+            self.live.set(self.live.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.live.set(self.live.get() - 1);
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    #[test]
+    fn test_matches_vecdeque() {
+        let mut list = LinkedList::default();
+        let mut model = VecDeque::new();
+        let mut seed = 3u32;
+        for _ in 0..3000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let pick = (seed >> 16) as usize;
+            match pick % 6 {
+                0 => {
+                    list.push_back(seed);
+                    model.push_back(seed);
+                }
+                1 => {
+                    list.push_front(seed);
+                    model.push_front(seed);
+                }
+                2 => assert_eq!(list.pop_front(), model.pop_front()),
+                3 => assert_eq!(list.pop_back(), model.pop_back()),
+                4 => {
+                    let index = pick % (model.len() + 1);
+                    list.insert(index, seed).unwrap();
+                    model.insert(index, seed);
+                }
+                _ if !model.is_empty() => {
+                    let index = pick % model.len();
+                    assert_eq!(list.remove(index).ok(), model.remove(index));
+                }
+                _ => assert!(list.remove(0).is_err()),
+            }
+            assert_eq!(list.len(), model.len());
+            assert_eq!(list.front(), model.front());
+            assert_eq!(list.back(), model.back());
+        }
+        assert!(list.iter().eq(model.iter()));
+        assert_eq!(list.iter().len(), model.len());
+    }
+
+    #[test]
+    fn test_borrows_and_owned_iteration() {
+        let mut list: LinkedList<i32> = (1..=4).collect();
+        for value in &mut list {
+            *value *= 10;
+        }
+        *list.front_mut().unwrap() += 1;
+        *list.back_mut().unwrap() += 2;
+        *list.get_mut(1).unwrap() += 3;
+        assert_eq!(list.get(1), Some(&23));
+        assert_eq!(list.get(4), None);
+        assert_eq!(
+            list.insert(5, 0),
+            Err(ListError::IndexOutOfBounds { index: 5, len: 4 })
+        );
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(format!("{list:?}"), "[11, 23, 30, 42]");
+        let owned: Vec<_> = list.into_iter().collect();
+        assert_eq!(owned, [11, 23, 30, 42]);
+        assert_eq!((&copy).into_iter().count(), 4);
+    }
+
+    #[test]
+    fn test_drop_frees_values() {
+        let value = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..3 {
+            list.push_back(value.clone());
+        }
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(iter);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let long: LinkedList<u32> = (0..1_000_000).collect();
+        drop(long);
+    }
+
+    #[test]
+    fn test_custom_allocators() {
+        let counting = Counting::default();
+        let mut list = LinkedList::new_in(&counting);
+        list.extend(0..10);
+        list.remove(3).unwrap();
+        list.pop_back();
+        list.insert(1, 99).unwrap();
+        // the two unlinked nodes are kept, one of them was reused
+        assert_eq!(counting.live.get(), 10);
+        list.shrink_to_fit();
+        assert_eq!(counting.live.get(), 9);
+        let copy = list.clone();
+        assert_eq!(counting.live.get(), 18);
+        assert_eq!(copy.allocator().live.get(), 18);
+        drop(list);
+        let rest: Vec<_> = copy.into_iter().take(2).collect();
+        assert_eq!(rest, [0, 99]);
+        assert_eq!(counting.live.get(), 0);
+
+        let bump = BumpAlloc::<256>::new();
+        let mut frames = LinkedList::new_in(&bump);
+        frames.push_back(1u64);
+        frames.push_front(0);
+        let used = bump.used();
+        assert!(used >= 2 * Layout::new::<BoxedNode<u64>>().size());
+        // a popped node is reused, the buffer only grows for new ones
+        frames.pop_front();
+        frames.push_back(2);
+        assert_eq!(bump.used(), used);
+        frames.shrink_to_fit();
+        frames.push_back(3);
+        assert!(bump.used() > used);
+        assert_eq!(frames, LinkedList::from_iter([1u64, 2, 3]));
+    }
+
+    #[test]
+    fn test_recycles_nodes() {
+        let counting = Counting::default();
+        let mut list = LinkedList::with_capacity_in(4, &counting);
+        assert_eq!((list.len(), list.capacity()), (0, 4));
+        assert_eq!(counting.live.get(), 4);
+        for round in 0..1000 {
+            list.extend([round, round + 1, round + 2]);
+            list.remove(1).unwrap();
+            list.pop_back();
+            list.pop_front();
+        }
+        assert!(list.is_empty());
+        assert_eq!(counting.live.get(), 4);
+
+        list.reserve(6);
+        list.push_back(0);
+        assert_eq!((list.capacity(), counting.live.get()), (6, 6));
+        list.shrink_to_fit();
+        assert_eq!((list.capacity(), counting.live.get()), (1, 1));
+        drop(list);
+        assert_eq!(counting.live.get(), 0);
+
+        // spare nodes don't keep their values alive
+        let value = Rc::new(());
+        let mut shared = LinkedList::with_capacity(2);
+        shared.push_back(value.clone());
+        shared.pop_back();
+        assert_eq!(Rc::strong_count(&value), 1);
+        shared.push_front(value.clone());
+        assert_eq!(shared.capacity(), 2);
+        drop(shared);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,228 @@
+use alloc::rc::{Rc, Weak};
+use core::cell::{Ref, RefCell};
+
+type RingHandle<T> = Rc<RefCell<RingNode<T>>>;
+
+struct RingNode<T> {
+    value: T,
+    // None only while the ring is being taken apart
+    next: Option<RingHandle<T>>,
+}
+
+// Ring of nodes for round-robin use: the last node links back to the first,
+// and `current` marks where the ring is entered. Every node is owned by its
+// predecessor's next, so the ring is a deliberate Rc cycle that Drop breaks.
+pub struct CircularList<T> {
+    current: Option<RingHandle<T>>,
+    // the node before current, Weak like the tail of LinkedList
+    last: Option<Weak<RefCell<RingNode<T>>>>,
+    len: usize,
+}
+
+// Each value once, starting at the current one.
+pub struct CircularIter<'a, T> {
+    _list: &'a CircularList<T>,
+    current: Option<RingHandle<T>>,
+    remaining: usize,
+}
+
+// Round and round from the current value, ends only for an empty list.
+pub struct CircularCycleIter<'a, T> {
+    _list: &'a CircularList<T>,
+    current: Option<RingHandle<T>>,
+}
+
+impl<T: Clone> Iterator for CircularIter<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let node = self.current.take()?;
+        let node = node.borrow();
+        self.current = node.next.clone();
+        Some(node.value.clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for CircularIter<'_, T> {}
+
+impl<T: Clone> Iterator for CircularCycleIter<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        let node = node.borrow();
+        self.current = node.next.clone();
+        Some(node.value.clone())
+    }
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> CircularList<T> {
+        CircularList {
+            current: None,
+            last: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn last_node(&self) -> Option<RingHandle<T>> {
+        self.last.as_ref().and_then(Weak::upgrade)
+    }
+
+    // Adds the value just before the current one, so it's the last reached by
+    // rotating. O(1).
+    pub fn push(&mut self, value: T) {
+        let new = Rc::new(RefCell::new(RingNode { value, next: None }));
+        match self.last_node() {
+            None => {
+                new.borrow_mut().next = Some(new.clone());
+                self.current = Some(new.clone());
+            }
+            Some(last) => {
+                new.borrow_mut().next = self.current.clone();
+                last.borrow_mut().next = Some(new.clone());
+            }
+        }
+        self.last = Some(Rc::downgrade(&new));
+        self.len += 1;
+    }
+
+    pub fn current(&self) -> Option<Ref<'_, T>> {
+        let current = self.current.as_ref()?;
+        Some(Ref::map(current.borrow(), |node| &node.value))
+    }
+
+    // Moves on to the next value, the last one wraps around to the first.
+    pub fn rotate(&mut self) {
+        let Some(current) = self.current.take() else {
+            return;
+        };
+        self.current = current.borrow().next.clone();
+        self.last = Some(Rc::downgrade(&current));
+    }
+
+    // Removes the current value, its successor becomes current.
+    pub fn pop_current(&mut self) -> Option<T> {
+        let current = self.current.take()?;
+        let next = current.borrow_mut().next.take().unwrap();
+        if self.len == 1 {
+            // next was current itself
+            drop(next);
+            self.last = None;
+        } else {
+            self.last_node().unwrap().borrow_mut().next = Some(next.clone());
+            self.current = Some(next);
+        }
+        self.len -= 1;
+        // the ring held the only other handle, iterators borrow the list
+        Some(Rc::try_unwrap(current).ok().unwrap().into_inner().value)
+    }
+
+    pub fn iter(&self) -> CircularIter<'_, T> {
+        CircularIter {
+            _list: self,
+            current: self.current.clone(),
+            remaining: self.len,
+        }
+    }
+
+    pub fn cycle_iter(&self) -> CircularCycleIter<'_, T> {
+        CircularCycleIter {
+            _list: self,
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        CircularList::new()
+    }
+}
+
+// Cuts the ring behind the last node, then unlinks front to back like
+// LinkedList does.
+impl<T> Drop for CircularList<T> {
+    fn drop(&mut self) {
+        if let Some(last) = self.last_node() {
+            last.borrow_mut().next = None;
+        }
+        let mut current = self.current.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_inner().next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_round_robin() {
+        let mut workers = CircularList::default();
+        assert!(workers.current().is_none());
+        workers.rotate();
+        for name in ["a", "b", "c"] {
+            workers.push(name);
+        }
+        assert_eq!(*workers.current().unwrap(), "a");
+        workers.rotate();
+        assert_eq!(workers.iter().collect::<Vec<_>>(), ["b", "c", "a"]);
+        assert_eq!(workers.iter().len(), 3);
+        // pushed just before the current one
+        workers.push("d");
+        assert_eq!(workers.iter().collect::<Vec<_>>(), ["b", "c", "a", "d"]);
+        let turns: Vec<_> = workers.cycle_iter().take(6).collect();
+        assert_eq!(turns, ["b", "c", "a", "d", "b", "c"]);
+
+        assert_eq!(workers.pop_current(), Some("b"));
+        assert_eq!(*workers.current().unwrap(), "c");
+        workers.rotate();
+        workers.rotate();
+        assert_eq!(workers.pop_current(), Some("d"));
+        assert_eq!(workers.iter().collect::<Vec<_>>(), ["c", "a"]);
+        assert_eq!(workers.pop_current(), Some("c"));
+        assert_eq!(workers.pop_current(), Some("a"));
+        assert!(workers.is_empty() && workers.cycle_iter().next().is_none());
+        assert_eq!(workers.pop_current(), None);
+        workers.push("e");
+        assert_eq!(workers.cycle_iter().take(2).collect::<Vec<_>>(), ["e", "e"]);
+    }
+
+    #[test]
+    fn test_drop_frees_ring() {
+        let value = Rc::new(());
+        let mut ring = CircularList::new();
+        for _ in 0..3 {
+            ring.push(value.clone());
+        }
+        ring.rotate();
+        drop(ring);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut long = CircularList::new();
+        for i in 0..1_000_000 {
+            long.push(i);
+        }
+        drop(long);
+    }
+}
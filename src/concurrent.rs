@@ -0,0 +1,174 @@
+use alloc::boxed::Box;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+struct QueueNode<T> {
+    next: AtomicPtr<QueueNode<T>>,
+    // None only in the stub the consumer stands on
+    value: Option<T>,
+}
+
+impl<T> QueueNode<T> {
+    fn boxed(value: Option<T>) -> *mut QueueNode<T> {
+        Box::into_raw(Box::new(QueueNode {
+            next: AtomicPtr::new(ptr::null_mut()),
+            value,
+        }))
+    }
+}
+
+// Lock-free FIFO in the Michael–Scott shape: nodes are linked front to back and
+// the consumer always stands on a stub node whose successor is the next value.
+// With a single consumer the producers don't need the CAS loop on the tail, one
+// swap claims the back and the link to the new node is published after it. A
+// value pushed by one producer is popped after the ones it pushed before.
+//
+// Any number of threads may `push`; `pop` is meant for one consumer at a time
+// and panics if it's entered from two threads at once, like a RefCell borrowed
+// twice.
+pub struct Queue<T> {
+    back: AtomicPtr<QueueNode<T>>,
+    // only written by the thread holding `popping`
+    front: AtomicPtr<QueueNode<T>>,
+    popping: AtomicBool,
+}
+
+// nodes are only freed by the thread holding `popping`, values move between threads
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Send> Sync for Queue<T> {}
+
+impl<T> Queue<T> {
+    pub fn new() -> Queue<T> {
+        let stub = QueueNode::boxed(None);
+        Queue {
+            back: AtomicPtr::new(stub),
+            front: AtomicPtr::new(stub),
+            popping: AtomicBool::new(false),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        let node = QueueNode::boxed(Some(value));
+        let prev = self.back.swap(node, Ordering::AcqRel);
+        // the consumer frees prev only after moving on to its successor, which
+        // can't happen before this store
+        unsafe { (*prev).next.store(node, Ordering::Release) };
+    }
+
+    // None if nothing is pushed, or a push is between its swap and its link.
+    pub fn pop(&self) -> Option<T> {
+        if self.popping.swap(true, Ordering::Acquire) {
+            panic!("Queue::pop entered by two consumers at once");
+        }
+        let stub = self.front.load(Ordering::Relaxed);
+        let next = unsafe { (*stub).next.load(Ordering::Acquire) };
+        let value = if next.is_null() {
+            None
+        } else {
+            // next becomes the stub, the old one isn't reachable by anyone now
+            self.front.store(next, Ordering::Relaxed);
+            unsafe { drop(Box::from_raw(stub)) };
+            unsafe { (*next).value.take() }
+        };
+        self.popping.store(false, Ordering::Release);
+        value
+    }
+
+    // A snapshot, other threads may push right after.
+    pub fn is_empty(&self) -> bool {
+        ptr::eq(
+            self.back.load(Ordering::Acquire),
+            self.front.load(Ordering::Relaxed),
+        )
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<T> Drop for Queue<T> {
+    fn drop(&mut self) {
+        let mut current = *self.front.get_mut();
+        while !current.is_null() {
+            // no other thread holds the queue anymore
+            let node = unsafe { Box::from_raw(current) };
+            current = node.next.load(Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use std::thread;
+
+    #[test]
+    fn test_fifo_single_thread() {
+        let queue = Queue::default();
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop(), None);
+        for i in 0..5 {
+            queue.push(i);
+        }
+        assert!(!queue.is_empty());
+        assert_eq!(queue.pop(), Some(0));
+        queue.push(5);
+        let rest: Vec<_> = core::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(rest, [1, 2, 3, 4, 5]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_many_producers() {
+        const PRODUCERS: usize = 8;
+        const PER_PRODUCER: usize = 20_000;
+        let queue = Arc::new(Queue::new());
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|p| {
+                let queue = queue.clone();
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        queue.push((p, i));
+                    }
+                })
+            })
+            .collect();
+
+        let mut next = [0; PRODUCERS];
+        let mut received = 0;
+        while received < PRODUCERS * PER_PRODUCER {
+            match queue.pop() {
+                Some((p, i)) => {
+                    // each producer's values arrive in the order pushed
+                    assert_eq!(i, next[p]);
+                    next[p] += 1;
+                    received += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        assert_eq!(next, [PER_PRODUCER; PRODUCERS]);
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_drop_frees_values() {
+        let value = Arc::new(());
+        let queue = Queue::new();
+        for _ in 0..3 {
+            queue.push(value.clone());
+        }
+        drop(queue.pop());
+        assert_eq!(Arc::strong_count(&value), 3);
+        drop(queue);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+}
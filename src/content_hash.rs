@@ -0,0 +1,472 @@
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+
+use crate::{LinkedList, NodeHandle};
+
+const BASE: u64 = 0x100_0000_01b3;
+const BASE_INV: u64 = inverse(BASE);
+
+// Multiplicative inverse modulo 2^64 of an odd number, by Newton's iteration:
+// every step doubles the number of correct low bits, starting from 3.
+const fn inverse(odd: u64) -> u64 {
+    let mut inv = odd;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2_u64.wrapping_sub(odd.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+
+// BASE^exp by squaring, the weight of a value with exp values behind it.
+fn base_pow(mut exp: usize) -> u64 {
+    let mut pow = 1_u64;
+    let mut base = BASE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            pow = pow.wrapping_mul(base);
+        }
+        base = base.wrapping_mul(base);
+        exp >>= 1;
+    }
+    pow
+}
+
+// FNV-1a, deterministic across runs and available without std.
+struct Fnv1a(u64);
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(BASE);
+        }
+    }
+}
+
+pub(crate) fn hash_value<T: Hash>(value: &T) -> u64 {
+    let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Polynomial hash sum(h(v_i) * BASE^(len - 1 - i)) kept with BASE^len, so pushing
+// and popping at either end is O(1). Edits in the middle shift only the weights
+// of the values before them, see `hash_replaced`; `rehash` is left for the
+// bulk rebuilds (sorting, reversing, merging).
+// The hashing function is captured when the mode is enabled, which lets the
+// mutating methods keep it up to date without a `T: Hash` bound.
+pub(crate) struct ContentHash<T> {
+    hash_value: fn(&T) -> u64,
+    hash: u64,
+    pow: u64,
+}
+
+impl<T> Clone for ContentHash<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ContentHash<T> {}
+
+impl<T> Debug for ContentHash<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ContentHash")
+            .field("hash", &self.hash)
+            .field("pow", &self.pow)
+            .finish()
+    }
+}
+
+// The hash of a run of consecutive values together with BASE^(run length), in
+// the same form as the list's own.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HashRun {
+    hash: u64,
+    pow: u64,
+}
+
+impl HashRun {
+    pub(crate) const EMPTY: HashRun = HashRun { hash: 0, pow: 1 };
+}
+
+impl<T> LinkedList<T> {
+    // Opt-in: from now on the list keeps an order-sensitive hash of its values.
+    // Values changed directly through node handles are not seen until it is
+    // rebuilt, by calling this again or by a sort, reverse or merge.
+    pub fn enable_content_hash(&mut self)
+    where
+        T: Hash,
+    {
+        self.content_hash.set(Some(ContentHash {
+            hash_value: hash_value::<T>,
+            hash: 0,
+            pow: 1,
+        }));
+        self.rehash();
+    }
+
+    pub fn disable_content_hash(&mut self) {
+        self.content_hash.set(None);
+    }
+
+    // O(1), None unless `enable_content_hash` was called.
+    pub fn content_hash(&self) -> Option<u64> {
+        // BASE^len acts as a leading sentinel, so lengths always make a difference
+        self.content_hash
+            .get()
+            .map(|state| state.hash.wrapping_add(state.pow))
+    }
+
+    pub(crate) fn hash_pushed_back(&self, value: &T) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = state
+                .hash
+                .wrapping_mul(BASE)
+                .wrapping_add((state.hash_value)(value));
+            state.pow = state.pow.wrapping_mul(BASE);
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    pub(crate) fn hash_pushed_front(&self, value: &T) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = state
+                .hash
+                .wrapping_add((state.hash_value)(value).wrapping_mul(state.pow));
+            state.pow = state.pow.wrapping_mul(BASE);
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    pub(crate) fn hash_popped_front(&self, value: &T) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.pow = state.pow.wrapping_mul(BASE_INV);
+            state.hash = state
+                .hash
+                .wrapping_sub((state.hash_value)(value).wrapping_mul(state.pow));
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    pub(crate) fn hash_popped_back(&self, value: &T) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = state
+                .hash
+                .wrapping_sub((state.hash_value)(value))
+                .wrapping_mul(BASE_INV);
+            state.pow = state.pow.wrapping_mul(BASE_INV);
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    // The run behind the values of self, shifting them past it.
+    pub(crate) fn hash_appended(&self, run: HashRun) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = state.hash.wrapping_mul(run.pow).wrapping_add(run.hash);
+            state.pow = state.pow.wrapping_mul(run.pow);
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    // Hashes the nodes only if self keeps a hash, otherwise they aren't visited.
+    pub(crate) fn hash_run(&self, nodes: impl IntoIterator<Item = NodeHandle<T>>) -> HashRun {
+        let mut run = HashRun::EMPTY;
+        if self.content_hash.get().is_some() {
+            for node in nodes {
+                self.hash_extend(&mut run, &node.borrow().value);
+            }
+        }
+        run
+    }
+
+    // The values of other as a run, O(1) if other keeps a hash itself.
+    pub(crate) fn hash_run_of(&self, other: &LinkedList<T>) -> HashRun {
+        match (self.content_hash.get(), other.content_hash.get()) {
+            (Some(_), Some(state)) => HashRun {
+                hash: state.hash,
+                pow: state.pow,
+            },
+            _ => self.hash_run(other.iter().flatten()),
+        }
+    }
+
+    // All values of self as a run.
+    pub(crate) fn hash_run_all(&self) -> HashRun {
+        self.content_hash
+            .get()
+            .map_or(HashRun::EMPTY, |state| HashRun {
+                hash: state.hash,
+                pow: state.pow,
+            })
+    }
+
+    pub(crate) fn hash_one(&self, value: &T) -> HashRun {
+        let mut run = HashRun::EMPTY;
+        self.hash_extend(&mut run, value);
+        run
+    }
+
+    pub(crate) fn hash_extend(&self, run: &mut HashRun, value: &T) {
+        if let Some(state) = self.content_hash.get() {
+            run.hash = run
+                .hash
+                .wrapping_mul(BASE)
+                .wrapping_add((state.hash_value)(value));
+            run.pow = run.pow.wrapping_mul(BASE);
+        }
+    }
+
+    // The removed run, which follows the prefix and has suffix_len values
+    // behind it, is replaced by the inserted one. With the list being
+    // P * B^(r + s) + R * B^s + S this adds B^s * (P * (B^i - B^r) + I - R),
+    // only the prefix has to be known.
+    pub(crate) fn hash_replaced(
+        &self,
+        prefix: HashRun,
+        removed: HashRun,
+        inserted: HashRun,
+        suffix_len: usize,
+    ) {
+        if let Some(mut state) = self.content_hash.get() {
+            let delta = prefix
+                .hash
+                .wrapping_mul(inserted.pow.wrapping_sub(removed.pow))
+                .wrapping_add(inserted.hash)
+                .wrapping_sub(removed.hash);
+            state.hash = state
+                .hash
+                .wrapping_add(delta.wrapping_mul(base_pow(suffix_len)));
+            state.pow = state
+                .pow
+                .wrapping_mul(inserted.pow)
+                .wrapping_mul(inverse(removed.pow));
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    // The list now holds exactly the values of the run.
+    pub(crate) fn hash_reset(&self, run: HashRun) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = run.hash;
+            state.pow = run.pow;
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    // Self keeps the prefix and the suffix gets the rest: from P * B^s + S,
+    // with B^s = pow / B^p, the suffix is left over.
+    pub(crate) fn hash_split(&self, suffix: &LinkedList<T>, prefix: HashRun) {
+        suffix.content_hash.set(self.content_hash.get());
+        if let Some(mut state) = self.content_hash.get() {
+            let suffix_pow = state.pow.wrapping_mul(inverse(prefix.pow));
+            suffix.hash_reset(HashRun {
+                hash: state
+                    .hash
+                    .wrapping_sub(prefix.hash.wrapping_mul(suffix_pow)),
+                pow: suffix_pow,
+            });
+            state.hash = prefix.hash;
+            state.pow = prefix.pow;
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    // The prefix was moved behind the rest of the values.
+    pub(crate) fn hash_rotated(&self, prefix: HashRun) {
+        if let Some(mut state) = self.content_hash.get() {
+            let rest_pow = state.pow.wrapping_mul(inverse(prefix.pow));
+            let rest = state.hash.wrapping_sub(prefix.hash.wrapping_mul(rest_pow));
+            state.hash = rest.wrapping_mul(prefix.pow).wrapping_add(prefix.hash);
+            self.content_hash.set(Some(state));
+        }
+    }
+
+    pub(crate) fn rehash(&self) {
+        if let Some(mut state) = self.content_hash.get() {
+            state.hash = 0;
+            state.pow = 1;
+            for node in self.iter().flatten() {
+                state.hash = state
+                    .hash
+                    .wrapping_mul(BASE)
+                    .wrapping_add((state.hash_value)(&node.borrow().value));
+                state.pow = state.pow.wrapping_mul(BASE);
+            }
+            self.content_hash.set(Some(state));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeRef;
+
+    fn hashed(values: &[i32]) -> LinkedList<i32> {
+        let mut list = LinkedList::new();
+        list.enable_content_hash();
+        for value in values {
+            list.push_back(*value);
+        }
+        list
+    }
+
+    #[test]
+    fn test_content_hash_tracks_pushes() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(2);
+        assert_eq!(list.content_hash(), None);
+
+        list.enable_content_hash();
+        list.push_back(3);
+        list.push_front(1);
+        assert_eq!(list.content_hash(), hashed(&[1, 2, 3]).content_hash());
+        assert_ne!(list.content_hash(), hashed(&[3, 2, 1]).content_hash());
+        assert_ne!(list.content_hash(), hashed(&[1, 2]).content_hash());
+        assert_ne!(hashed(&[]).content_hash(), hashed(&[0]).content_hash());
+
+        list.disable_content_hash();
+        assert_eq!(list.content_hash(), None);
+    }
+
+    #[test]
+    fn test_content_hash_after_mutations() {
+        let mut list = hashed(&[1, 2, 3, 4]);
+
+        list.update_nth(0, 5).unwrap();
+        assert_eq!(list.content_hash(), hashed(&[5, 2, 3, 4]).content_hash());
+
+        list.push_after_n(1, 6).unwrap();
+        assert_eq!(list.content_hash(), hashed(&[5, 2, 6, 3, 4]).content_hash());
+
+        list.rotate_to(2).unwrap();
+        assert_eq!(list.content_hash(), hashed(&[6, 3, 4, 5, 2]).content_hash());
+
+        let node = list.get_nth(0).unwrap().unwrap();
+        list.mark_remove(&NodeRef::from(&node)).unwrap();
+        list.sweep();
+        assert_eq!(list.content_hash(), hashed(&[3, 4, 5, 2]).content_hash());
+
+        let mut copy = LinkedList::new();
+        copy.clone_from(&list);
+        assert_eq!(copy.content_hash(), list.content_hash());
+        assert_eq!(list.clone().content_hash(), list.content_hash());
+
+        let rest = list.split_off(2).unwrap();
+        assert_eq!(list.content_hash(), hashed(&[3, 4]).content_hash());
+        assert_eq!(rest.content_hash(), hashed(&[5, 2]).content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_tracks_pops() {
+        assert_eq!(BASE.wrapping_mul(BASE_INV), 1);
+        let mut list = hashed(&[1, 2, 3]);
+        list.pop_front();
+        assert_eq!(list.content_hash(), hashed(&[2, 3]).content_hash());
+        list.push_back(4);
+        list.pop_back();
+        assert_eq!(list.content_hash(), hashed(&[2, 3]).content_hash());
+        list.pop_back();
+        list.pop_front();
+        assert_eq!(list.content_hash(), hashed(&[]).content_hash());
+    }
+
+    // The incrementally kept hash against one built from scratch.
+    fn assert_fresh(list: &LinkedList<i32>) {
+        assert_eq!(list.content_hash(), hashed(&list.to_vec()).content_hash());
+    }
+
+    #[test]
+    fn test_content_hash_matches_fresh_after_edits() {
+        assert_eq!(base_pow(5), BASE.wrapping_pow(5));
+        let mut list = hashed(&[1, 2, 3, 4, 5, 6]);
+
+        list.insert(2, 7).unwrap();
+        assert_fresh(&list);
+        let node = list.get_nth(1).unwrap().unwrap();
+        list.insert_after(&NodeRef::new(&node), 8).unwrap();
+        assert_fresh(&list);
+        list.splice(3, hashed(&[9, 10])).unwrap();
+        assert_fresh(&list);
+        list.splice(0, LinkedList::from_iter([11, 12])).unwrap();
+        assert_fresh(&list);
+        list.update_nth(4, 13).unwrap();
+        assert_fresh(&list);
+        list.swap(1, 6).unwrap();
+        assert_fresh(&list);
+        let a = list.get_nth(0).unwrap().unwrap();
+        let b = list.get_nth(5).unwrap().unwrap();
+        list.swap_nodes(&NodeRef::from(&a), &NodeRef::from(&b))
+            .unwrap();
+        assert_fresh(&list);
+        list.remove_nth(3).unwrap();
+        assert_fresh(&list);
+        let node = list.get_nth(2).unwrap().unwrap();
+        list.remove(&NodeRef::new(&node)).unwrap();
+        assert_fresh(&list);
+        list.rotate_to(3).unwrap();
+        assert_fresh(&list);
+
+        let rest = list.split_off(4).unwrap();
+        assert_fresh(&list);
+        assert_fresh(&rest);
+        list.append(&mut LinkedList::from_iter([14, 14, 15]));
+        assert_fresh(&list);
+    }
+
+    #[test]
+    fn test_content_hash_matches_fresh_after_removals() {
+        let mut list = hashed(&[1, 1, 2, 3, 3, 3, 4, 5, 6, 7, 8]);
+        list.dedup();
+        assert_fresh(&list);
+        list.retain(|value| *value != 4);
+        assert_fresh(&list);
+
+        let node = list.get_nth(1).unwrap().unwrap();
+        list.mark_remove(&NodeRef::from(&node)).unwrap();
+        list.sweep();
+        assert_fresh(&list);
+
+        let mut target = hashed(&[0]);
+        list.drain_matching_into(&mut target, |value| *value == 5);
+        assert_fresh(&list);
+        assert_fresh(&target);
+
+        // stopped early, the rest is never visited
+        let first = list.extract_if(|value| *value > 6).next();
+        assert_eq!(first, Some(7));
+        assert_fresh(&list);
+        list.extract_if(|value| *value % 2 == 1).for_each(drop);
+        assert_fresh(&list);
+
+        let edits = list.diff(&LinkedList::from_iter([2, 9, 8]));
+        list.apply(&edits).unwrap();
+        assert_fresh(&list);
+    }
+
+    #[test]
+    fn test_content_hash_matches_fresh_after_cursor_edits() {
+        let mut list = hashed(&[1, 2, 3, 4]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.insert_after(5);
+        cursor.insert_before(6);
+        cursor.move_next();
+        cursor.move_next();
+        cursor.remove_current();
+        cursor.move_next();
+        // ghost position
+        cursor.insert_before(7);
+        cursor.insert_after(8);
+        cursor.move_next();
+        cursor.remove_current();
+        drop(cursor);
+        assert_fresh(&list);
+        assert_eq!(list.to_vec(), [1, 6, 2, 5, 4, 7]);
+    }
+}
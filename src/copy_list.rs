@@ -0,0 +1,218 @@
+use alloc::rc::{Rc, Weak};
+use core::cell::Cell;
+
+use crate::ListError;
+
+type CopyLink<T> = Option<Rc<CopyNode<T>>>;
+
+struct CopyNode<T> {
+    value: Cell<T>,
+    next: Cell<CopyLink<T>>,
+}
+
+impl<T> CopyNode<T> {
+    // Cell only moves values in and out, so the link is taken and put back.
+    fn next(&self) -> CopyLink<T> {
+        let next = self.next.take();
+        self.next.set(next.clone());
+        next
+    }
+}
+
+// List for small Copy values. Values and links live in Cells instead of a
+// RefCell per node: reads and writes copy in and out, there are no borrow
+// flags to check and nothing to panic on.
+pub struct CopyList<T> {
+    head: CopyLink<T>,
+    // weak, so the chain has one owner and can be freed front to back
+    tail: Option<Weak<CopyNode<T>>>,
+    len: usize,
+}
+
+pub struct CopyListIter<T> {
+    current: CopyLink<T>,
+}
+
+impl<T: Copy> Iterator for CopyListIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = node.next();
+        Some(node.value.get())
+    }
+}
+
+impl<T: Copy> CopyList<T> {
+    pub fn new() -> CopyList<T> {
+        CopyList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let new = Rc::new(CopyNode {
+            value: Cell::new(value),
+            next: Cell::new(None),
+        });
+        match self
+            .tail
+            .replace(Rc::downgrade(&new))
+            .and_then(|tail| tail.upgrade())
+        {
+            None => self.head = Some(new),
+            Some(tail) => tail.next.set(Some(new)),
+        }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let new = Rc::new(CopyNode {
+            value: Cell::new(value),
+            next: Cell::new(self.head.take()),
+        });
+        if self.tail.is_none() {
+            self.tail = Some(Rc::downgrade(&new));
+        }
+        self.head = Some(new);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.next.take();
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        self.len -= 1;
+        Some(node.value.get())
+    }
+
+    pub fn iter(&self) -> CopyListIter<T> {
+        CopyListIter {
+            current: self.head.clone(),
+        }
+    }
+
+    fn nth_node(&self, n: usize) -> CopyLink<T> {
+        let mut current = self.head.clone();
+        for _ in 0..n {
+            current = current?.next();
+        }
+        current
+    }
+
+    fn out_of_bounds(&self, index: usize) -> ListError {
+        ListError::IndexOutOfBounds {
+            index,
+            len: self.len,
+        }
+    }
+
+    pub fn get_nth(&self, n: usize) -> Result<T, ListError> {
+        let node = self.nth_node(n).ok_or_else(|| self.out_of_bounds(n))?;
+        Ok(node.value.get())
+    }
+
+    // Only needs &self, the value is swapped in place through its Cell.
+    pub fn update_nth(&self, n: usize, value: T) -> Result<(), ListError> {
+        let node = self.nth_node(n).ok_or_else(|| self.out_of_bounds(n))?;
+        node.value.set(value);
+        Ok(())
+    }
+
+    pub fn map_in_place(&self, mut f: impl FnMut(T) -> T) {
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            node.value.set(f(node.value.get()));
+            current = node.next();
+        }
+    }
+}
+
+impl<T: Copy> Default for CopyList<T> {
+    fn default() -> Self {
+        CopyList::new()
+    }
+}
+
+// Frees front to back, no recursion through `next`. A node an iterator still
+// holds keeps the rest of the chain, as with `LinkedList`.
+impl<T> Drop for CopyList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.next.into_inner(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_push_pop() {
+        let mut list = CopyList::new();
+        list.push_back(2.5);
+        list.push_back(3.5);
+        list.push_front(1.5);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), [1.5, 2.5, 3.5]);
+        assert_eq!(list.pop_front(), Some(1.5));
+        assert_eq!(list.pop_front(), Some(2.5));
+        assert_eq!(list.pop_front(), Some(3.5));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+
+        list.push_back(4.5);
+        assert_eq!(list.iter().collect::<Vec<_>>(), [4.5]);
+    }
+
+    #[test]
+    fn test_update_while_iterating() {
+        let mut list = CopyList::default();
+        for value in 1..=4 {
+            list.push_back(value);
+        }
+
+        // no borrow is held by the iterator, writes in between are fine
+        for (i, value) in list.iter().enumerate() {
+            list.update_nth(i, value * 10).unwrap();
+        }
+        assert_eq!(list.iter().collect::<Vec<_>>(), [10, 20, 30, 40]);
+        list.map_in_place(|value| value + 1);
+        assert_eq!(list.get_nth(3), Ok(41));
+        assert_eq!(
+            list.get_nth(4),
+            Err(ListError::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert!(list.update_nth(4, 0).is_err());
+        assert_eq!(list.iter().sum::<i32>(), 104);
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut list = CopyList::new();
+        for value in 0..1_000_000 {
+            list.push_back(value);
+        }
+        list.push_front(-1);
+        assert_eq!(list.get_nth(1_000_000), Ok(999_999));
+        drop(list);
+    }
+}
@@ -0,0 +1,565 @@
+use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::content_hash::HashRun;
+use crate::{into_value, Link, LinkedList, ListError, Node};
+
+#[derive(Debug)]
+pub(crate) struct CursorState<T> {
+    // None once the cursor moved past the last node
+    node: Link<T>,
+    index: usize,
+    valid: bool,
+}
+
+pub(crate) type CursorRegistry<T> = RefCell<Vec<Weak<RefCell<CursorState<T>>>>>;
+
+// Position in a list that stays usable while the list changes. The list keeps
+// a registry of its live cursors and adjusts them on every structural change:
+// - an insert at or before the cursor leaves it on its node, the index moves up
+// - removing a node before the cursor moves the index down
+// - removing the node under the cursor moves it to the next node (or past the
+//   end), keeping the index
+// - relinking (rotate, swap, split, apply, ...) invalidates all cursors
+// A cursor past the end stays there, pushing at the back moves its index.
+pub struct Cursor<T> {
+    state: Rc<RefCell<CursorState<T>>>,
+}
+
+impl<T> Cursor<T> {
+    pub fn is_valid(&self) -> bool {
+        self.state.borrow().valid
+    }
+
+    pub fn index(&self) -> Result<usize, ListError> {
+        let state = self.state.borrow();
+        state
+            .valid
+            .then_some(state.index)
+            .ok_or(ListError::CursorInvalidated)
+    }
+
+    // Node under the cursor, None past the end of the list.
+    pub fn current(&self) -> Result<Link<T>, ListError> {
+        let state = self.state.borrow();
+        state
+            .valid
+            .then(|| state.node.clone())
+            .ok_or(ListError::CursorInvalidated)
+    }
+
+    pub fn move_next(&mut self) -> Result<(), ListError> {
+        let mut state = self.state.borrow_mut();
+        if !state.valid {
+            return Err(ListError::CursorInvalidated);
+        }
+        // past the end the index is the length of the list
+        let end = ListError::IndexOutOfBounds {
+            index: state.index,
+            len: state.index,
+        };
+        let next = state.node.as_ref().ok_or(end)?.borrow().next.clone();
+        state.node = next;
+        state.index += 1;
+        Ok(())
+    }
+
+    // Moves up to k nodes forward, stopping past the end; returns the steps taken.
+    pub fn seek_forward(&mut self, k: usize) -> Result<usize, ListError> {
+        let mut steps = 0;
+        while steps < k && self.current()?.is_some() {
+            self.move_next()?;
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    // Moves up to k nodes back, stopping at the head; returns the steps taken.
+    // Nodes don't link back, so this walks `list` from its head again.
+    pub fn seek_backward(&mut self, list: &LinkedList<T>, k: usize) -> Result<usize, ListError> {
+        let index = self.index()?;
+        let registered = list
+            .cursors
+            .borrow()
+            .iter()
+            .any(|cursor| cursor.as_ptr() == Rc::as_ptr(&self.state));
+        if !registered {
+            return Err(ListError::StaleHandle);
+        }
+        let steps = k.min(index);
+        let mut state = self.state.borrow_mut();
+        state.index -= steps;
+        state.node = list.iter().nth(state.index).flatten();
+        Ok(steps)
+    }
+}
+
+impl<T> LinkedList<T> {
+    // Several cursors may be open at the same time, they don't borrow the list.
+    pub fn cursor(&self) -> Cursor<T> {
+        self.register_cursor(self.head.clone(), 0)
+    }
+
+    pub fn cursor_at(&self, n: usize) -> Result<Cursor<T>, ListError> {
+        let node = self.get_nth(n)?;
+        Ok(self.register_cursor(node, n))
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            prev: None,
+            current: self.head.clone(),
+            index: 0,
+            prefix: HashRun::EMPTY,
+            list: self,
+        }
+    }
+
+    fn register_cursor(&self, node: Link<T>, index: usize) -> Cursor<T> {
+        let state = Rc::new(RefCell::new(CursorState {
+            node,
+            index,
+            valid: true,
+        }));
+        let mut cursors = self.cursors.borrow_mut();
+        cursors.retain(|cursor| cursor.strong_count() > 0);
+        cursors.push(Rc::downgrade(&state));
+        Cursor { state }
+    }
+
+    // A node was linked in at position `index`, cursors at or behind it keep
+    // their node and move up by one.
+    pub(crate) fn cursors_inserted(&self, index: usize) {
+        self.cursors_spliced(index, 1);
+    }
+
+    // `count` nodes were linked in starting at position `index`.
+    pub(crate) fn cursors_spliced(&self, index: usize, count: usize) {
+        for cursor in self.cursors.borrow().iter().filter_map(Weak::upgrade) {
+            let mut cursor = cursor.borrow_mut();
+            if cursor.index >= index {
+                cursor.index += count;
+            }
+        }
+    }
+
+    // The node at position `index` was unlinked, `next` is the node that followed it.
+    pub(crate) fn cursors_removed(&self, index: usize, next: &Link<T>) {
+        for cursor in self.cursors.borrow().iter().filter_map(Weak::upgrade) {
+            let mut cursor = cursor.borrow_mut();
+            if cursor.index > index {
+                cursor.index -= 1;
+            } else if cursor.index == index {
+                cursor.node = next.clone();
+            }
+        }
+    }
+
+    pub(crate) fn cursors_pushed_back(&self) {
+        self.cursors_appended(1);
+    }
+
+    // Only cursors past the end are behind nodes added at the back.
+    pub(crate) fn cursors_appended(&self, count: usize) {
+        for cursor in self.cursors.borrow().iter().filter_map(Weak::upgrade) {
+            let mut cursor = cursor.borrow_mut();
+            if cursor.valid && cursor.node.is_none() {
+                cursor.index += count;
+            }
+        }
+    }
+
+    pub(crate) fn invalidate_cursors(&self) {
+        for cursor in self.cursors.take().iter().filter_map(Weak::upgrade) {
+            let mut cursor = cursor.borrow_mut();
+            cursor.valid = false;
+            cursor.node = None;
+        }
+    }
+}
+
+// Editing cursor holding the list exclusively, so it can keep the node before
+// it and relink in O(1). With the content hash enabled it also carries the hash
+// of the values before it, which makes an edit O(log n). Past
+// the tail it's on a "ghost" position: moving on from there wraps to the head,
+// inserting after it pushes at the front and inserting before it at the back.
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    // the tail while on the ghost position
+    prev: Link<T>,
+    current: Link<T>,
+    index: usize,
+    // content hash of the values before the cursor
+    prefix: HashRun,
+}
+
+impl<T> CursorMut<'_, T> {
+    // None on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.current.as_ref().map(|_| self.index)
+    }
+
+    pub fn current(&self) -> Link<T> {
+        self.current.clone()
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            None => {
+                self.prev = None;
+                self.current = self.list.head.clone();
+                self.index = 0;
+                self.prefix = HashRun::EMPTY;
+            }
+            Some(node) => {
+                self.list
+                    .hash_extend(&mut self.prefix, &node.borrow().value);
+                self.current = node.borrow().next.clone();
+                self.prev = Some(node);
+                self.index += 1;
+            }
+        }
+    }
+
+    // The cursor stays on its node.
+    pub fn insert_after(&mut self, value: T) {
+        let Some(node) = &self.current else {
+            self.list.push_front(value);
+            self.prev = self.list.tail_node();
+            self.index = self.list.len;
+            self.prefix = self.list.hash_run_all();
+            return;
+        };
+        let mut prefix = self.prefix;
+        self.list.hash_extend(&mut prefix, &node.borrow().value);
+        let inserted = self.list.hash_one(&value);
+        let suffix_len = self.list.len - self.index - 1;
+        self.list
+            .hash_replaced(prefix, HashRun::EMPTY, inserted, suffix_len);
+        let new = Node::new(value, node.borrow_mut().next.take());
+        if new.borrow().next.is_none() {
+            self.list.set_tail(Some(&new));
+        }
+        node.borrow_mut().next = Some(new);
+        self.list.len += 1;
+        self.list.cursors_inserted(self.index + 1);
+    }
+
+    // The cursor stays on its node, which moves up by one.
+    pub fn insert_before(&mut self, value: T) {
+        let Some(node) = &self.current else {
+            self.list.push_back(value);
+            self.prev = self.list.tail_node();
+            self.index = self.list.len;
+            self.prefix = self.list.hash_run_all();
+            return;
+        };
+        let inserted = self.list.hash_one(&value);
+        let suffix_len = self.list.len - self.index;
+        self.list
+            .hash_replaced(self.prefix, HashRun::EMPTY, inserted, suffix_len);
+        self.list.hash_extend(&mut self.prefix, &value);
+        let new = Node::new(value, Some(node.clone()));
+        match &self.prev {
+            None => self.list.head = Some(new.clone()),
+            Some(prev) => prev.borrow_mut().next = Some(new.clone()),
+        }
+        self.prev = Some(new);
+        self.list.len += 1;
+        self.list.cursors_inserted(self.index);
+        self.index += 1;
+    }
+}
+
+impl<T: Clone> CursorMut<'_, T> {
+    // Unlinks the current node and moves on to the next one. The value is
+    // cloned only if the node is still referenced elsewhere.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        let removed = self.list.hash_one(&node.borrow().value);
+        let suffix_len = self.list.len - self.index - 1;
+        self.list
+            .hash_replaced(self.prefix, removed, HashRun::EMPTY, suffix_len);
+        let next = node.borrow_mut().next.take();
+        match &self.prev {
+            None => self.list.head = next.clone(),
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+        }
+        if next.is_none() {
+            self.list.set_tail(self.prev.as_ref());
+        }
+        self.current = next;
+        self.list.len -= 1;
+        self.list.cursors_removed(self.index, &self.current);
+        Some(into_value(node))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NodeRef;
+
+    fn values(values: &[i32]) -> LinkedList<i32> {
+        let mut list = LinkedList::new();
+        for value in values {
+            list.push_back(*value);
+        }
+        list
+    }
+
+    fn value_at(cursor: &Cursor<i32>) -> Option<i32> {
+        cursor.current().unwrap().map(|node| node.borrow().value)
+    }
+
+    #[test]
+    fn test_cursors_follow_pushes() {
+        let mut list = values(&[1, 2, 3]);
+        let mut first = list.cursor();
+        let mut second = list.cursor_at(1).unwrap();
+        let mut end = list.cursor_at(2).unwrap();
+        end.move_next().unwrap();
+        assert_eq!(
+            end.move_next(),
+            Err(ListError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert!(list.cursor_at(3).is_err());
+
+        list.push_front(0);
+        list.push_after_n(1, 9).unwrap();
+        list.push_back(4);
+
+        assert_eq!((first.index(), value_at(&first)), (Ok(1), Some(1)));
+        assert_eq!((second.index(), value_at(&second)), (Ok(3), Some(2)));
+        assert_eq!((end.index(), value_at(&end)), (Ok(6), None));
+        first.move_next().unwrap();
+        second.move_next().unwrap();
+        assert_eq!((first.index(), value_at(&first)), (Ok(2), Some(9)));
+        assert_eq!((second.index(), value_at(&second)), (Ok(4), Some(3)));
+    }
+
+    // Every cursor position against every insert and remove position, checked
+    // against the same edit on a Vec.
+    #[test]
+    fn test_cursor_position_matrix() {
+        let len = 5;
+        for at in 0..=len {
+            for cursor_pos in 0..=len {
+                let mut model: Vec<i32> = (0..len as i32).collect();
+                let mut list = values(&model);
+                let mut cursor = list.cursor_at(cursor_pos.min(len - 1)).unwrap();
+                if cursor_pos == len {
+                    cursor.move_next().unwrap();
+                }
+                model.insert(at, 100);
+                match at {
+                    0 => drop(list.push_front(100)),
+                    _ if at == len => drop(list.push_back(100)),
+                    _ => list.push_after_n(at - 1, 100).unwrap(),
+                }
+                let expected = cursor_pos + usize::from(at <= cursor_pos);
+                assert_eq!(
+                    cursor.index(),
+                    Ok(expected),
+                    "insert {at} cursor {cursor_pos}"
+                );
+                assert_eq!(value_at(&cursor), model.get(expected).copied());
+            }
+        }
+
+        for at in 0..len {
+            for cursor_pos in 0..=len {
+                let mut model: Vec<i32> = (0..len as i32).collect();
+                let mut list = values(&model);
+                let mut cursor = list.cursor_at(cursor_pos.min(len - 1)).unwrap();
+                if cursor_pos == len {
+                    cursor.move_next().unwrap();
+                }
+                model.remove(at);
+                list.mark_remove(&NodeRef::from(&list.get_nth(at).unwrap().unwrap()))
+                    .unwrap();
+                list.sweep();
+                let expected = cursor_pos - usize::from(at < cursor_pos);
+                assert_eq!(
+                    cursor.index(),
+                    Ok(expected),
+                    "remove {at} cursor {cursor_pos}"
+                );
+                assert_eq!(value_at(&cursor), model.get(expected).copied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_cursors_skip_runs_of_removed_nodes() {
+        let mut list = values(&[0, 1, 2, 3, 4]);
+        let cursors: Vec<_> = (0..5).map(|n| list.cursor_at(n).unwrap()).collect();
+        for n in [1, 2, 4] {
+            list.mark_remove(&NodeRef::from(&list.get_nth(n).unwrap().unwrap()))
+                .unwrap();
+        }
+        list.sweep();
+
+        let positions: Vec<_> = cursors
+            .iter()
+            .map(|cursor| (cursor.index().unwrap(), value_at(cursor)))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                (0, Some(0)),
+                (1, Some(3)),
+                (1, Some(3)),
+                (1, Some(3)),
+                (2, None)
+            ]
+        );
+
+        let mut any = LinkedList::<Box<dyn core::any::Any>>::new();
+        any.push_back(Box::new(1_u8));
+        any.push_back(Box::new("two"));
+        any.push_back(Box::new(3_u8));
+        let on_removed = any.cursor_at(1).unwrap();
+        let behind = any.cursor_at(2).unwrap();
+        assert!(any.remove_downcast::<&str>().is_some());
+        assert_eq!(on_removed.index(), Ok(1));
+        assert_eq!(behind.index(), Ok(1));
+        let node = on_removed.current().unwrap().unwrap();
+        assert_eq!(node.borrow().value.downcast_ref::<u8>(), Some(&3));
+    }
+
+    #[test]
+    fn test_seek() {
+        let list = values(&[0, 1, 2, 3]);
+        let mut cursor = list.cursor();
+
+        assert_eq!(cursor.seek_forward(2), Ok(2));
+        assert_eq!((cursor.index(), value_at(&cursor)), (Ok(2), Some(2)));
+        assert_eq!(cursor.seek_forward(5), Ok(2));
+        assert_eq!((cursor.index(), value_at(&cursor)), (Ok(4), None));
+        assert_eq!(cursor.seek_forward(1), Ok(0));
+
+        assert_eq!(cursor.seek_backward(&list, 1), Ok(1));
+        assert_eq!((cursor.index(), value_at(&cursor)), (Ok(3), Some(3)));
+        assert_eq!(cursor.seek_backward(&list, 9), Ok(3));
+        assert_eq!((cursor.index(), value_at(&cursor)), (Ok(0), Some(0)));
+        assert_eq!(cursor.seek_backward(&list, 1), Ok(0));
+
+        let other = values(&[0, 1, 2, 3]);
+        assert_eq!(cursor.seek_backward(&other, 1), Err(ListError::StaleHandle));
+        drop(list);
+        assert_eq!(cursor.seek_forward(1), Err(ListError::CursorInvalidated));
+    }
+
+    #[test]
+    fn test_structural_changes_invalidate_cursors() {
+        let mut list = values(&[1, 2, 3]);
+        let mut cursor = list.cursor_at(1).unwrap();
+        list.update_nth(1, 5).unwrap();
+        assert_eq!(value_at(&cursor), Some(5));
+
+        list.rotate_to(1).unwrap();
+        assert!(!cursor.is_valid());
+        assert_eq!(cursor.index(), Err(ListError::CursorInvalidated));
+        assert!(cursor.current().is_err());
+        assert_eq!(cursor.move_next(), Err(ListError::CursorInvalidated));
+
+        let cursor = list.cursor();
+        let rest = list.split_off(1).unwrap();
+        assert!(!cursor.is_valid());
+
+        let cursor = rest.cursor();
+        drop(rest);
+        assert!(!cursor.is_valid());
+    }
+
+    #[test]
+    fn test_dropped_cursors_are_unregistered() {
+        let list = values(&[1]);
+        for _ in 0..10 {
+            list.cursor();
+        }
+        let _cursor = list.cursor();
+        assert_eq!(list.cursors.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_cursor_mut_edits() {
+        let mut list = values(&[1, 2, 3]);
+        let tracked = list.cursor_at(2).unwrap();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        cursor.insert_after(20);
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.current().unwrap().borrow().value, 20);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+
+        // on the ghost position
+        cursor.insert_before(4);
+        cursor.insert_after(-1);
+        cursor.move_next();
+        assert_eq!(cursor.current().unwrap().borrow().value, -1);
+        assert_eq!(list, [-1, 0, 1, 20, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 4);
+        // it was on the removed 3, then the two pushes moved it
+        assert_eq!(tracked.index(), Ok(5));
+        assert_eq!(value_at(&tracked), None);
+
+        let mut empty = LinkedList::<i32>::new();
+        let mut cursor = empty.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), None);
+        cursor.insert_after(1);
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert!(empty.is_empty() && empty.head.is_none() && empty.tail.is_none());
+    }
+
+    #[test]
+    fn test_cursor_mut_matches_vec() {
+        let mut list = LinkedList::new();
+        let mut model: Vec<i32> = Vec::new();
+        let mut cursor = list.cursor_front_mut();
+        // index into the model, model.len() for the ghost position
+        let mut pos = 0;
+        for step in 0..2000 {
+            match step * 7 % 5 {
+                0 => {
+                    cursor.insert_before(step);
+                    model.insert(pos, step);
+                    pos += 1;
+                }
+                1 => {
+                    cursor.insert_after(step);
+                    if pos == model.len() {
+                        model.insert(0, step);
+                        pos += 1;
+                    } else {
+                        model.insert(pos + 1, step);
+                    }
+                }
+                2 if pos < model.len() => {
+                    assert_eq!(cursor.remove_current(), Some(model.remove(pos)));
+                }
+                _ => {
+                    cursor.move_next();
+                    pos = if pos == model.len() { 0 } else { pos + 1 };
+                }
+            }
+            assert_eq!(cursor.index(), (pos < model.len()).then_some(pos));
+            let value = cursor.current().map(|node| node.borrow().value);
+            assert_eq!(value, model.get(pos).copied());
+        }
+        assert_eq!(list, model);
+        assert_eq!(list.len(), model.len());
+        assert_eq!(
+            list.tail_node().as_ref().map(|tail| tail.borrow().value),
+            model.last().copied()
+        );
+    }
+}
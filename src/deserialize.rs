@@ -0,0 +1,110 @@
+use core::fmt::Formatter;
+use core::marker::PhantomData;
+
+use serde::de::{DeserializeSeed, Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+use crate::node_pool::{NodePool, PoolList};
+use crate::LinkedList;
+
+struct ListVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+    type Value = LinkedList<T>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "a sequence")
+    }
+
+    // Every element goes into its node as soon as it's parsed, nothing is
+    // buffered on the way.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = LinkedList::new();
+        while let Some(value) = seq.next_element()? {
+            list.push_back(value);
+        }
+        Ok(list)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ListVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+struct PoolListVisitor<'a, T, const N: usize> {
+    pool: &'a NodePool<T, N>,
+}
+
+impl<'de, 'a, T: Deserialize<'de>, const N: usize> Visitor<'de> for PoolListVisitor<'a, T, N> {
+    type Value = PoolList<'a, T, N>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "a sequence of at most {} elements",
+            self.pool.available()
+        )
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = PoolList::new(self.pool);
+        while let Some(value) = seq.next_element()? {
+            // on error the partial list is dropped and its nodes go back to the pool
+            list.push_back(value).map_err(A::Error::custom)?;
+        }
+        Ok(list)
+    }
+}
+
+// Deserializes straight into nodes taken from the pool:
+//
+//     let list = POOL.deserialize(&mut deserializer)?;
+impl<'de, 'a, T: Deserialize<'de>, const N: usize> DeserializeSeed<'de> for &'a NodePool<T, N> {
+    type Value = PoolList<'a, T, N>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(PoolListVisitor { pool: self })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use serde::de::value::{self, SeqDeserializer};
+
+    fn seq<T>(values: alloc::vec::Vec<T>) -> SeqDeserializer<vec::IntoIter<T>, value::Error> {
+        SeqDeserializer::new(values.into_iter())
+    }
+
+    #[test]
+    fn test_deserialize_list() {
+        let list = LinkedList::<String>::deserialize(seq(vec!["a", "b", "c"])).unwrap();
+        assert_eq!(list.to_string(), "[a, b, c]");
+        let empty = LinkedList::<u8>::deserialize(seq::<u8>(vec![])).unwrap();
+        assert!(empty.head.is_none() && empty.tail.is_none());
+
+        assert!(LinkedList::<u8>::deserialize(seq(vec![1, 300])).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_into_pool() {
+        let pool = NodePool::<u32, 3>::new();
+        let list = (&pool).deserialize(seq(vec![1, 2])).unwrap();
+        assert_eq!(list.to_string(), "[1, 2]");
+        assert_eq!(pool.available(), 1);
+
+        // too long for what's left, the nodes taken so far are given back
+        assert!((&pool).deserialize(seq(vec![3, 4])).is_err());
+        assert_eq!(pool.available(), 1);
+        drop(list);
+        assert_eq!(pool.available(), 3);
+    }
+}
@@ -0,0 +1,394 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::content_hash::HashRun;
+use crate::{LinkedList, ListError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOp<T> {
+    Keep(T),
+    Delete(T),
+    Insert(T),
+}
+
+impl<T: PartialEq + Clone> LinkedList<T> {
+    fn collect_values(&self) -> Vec<T> {
+        self.iter()
+            .flatten()
+            .map(|node| node.borrow().value.clone())
+            .collect()
+    }
+
+    // Minimal edit script (fewest inserts + deletes) turning self into other.
+    // Myers' O((n + m) * d) algorithm in its linear space form, d being the
+    // number of edits, so close lists are cheap to compare however long.
+    pub fn diff(&self, other: &LinkedList<T>) -> Vec<EditOp<T>> {
+        let old = self.collect_values();
+        let new = other.collect_values();
+        let mut edits = Vec::with_capacity(old.len().max(new.len()));
+        diff_slices(&old, &new, &mut edits);
+        edits
+    }
+
+    // Index and both values of the first position where the lists differ, None if
+    // they are equal or one is a prefix of the other.
+    pub fn first_divergence(&self, other: &LinkedList<T>) -> Option<(usize, T, T)> {
+        self.iter()
+            .flatten()
+            .zip(other.iter().flatten())
+            .enumerate()
+            .find(|(_, (a, b))| a.borrow().value != b.borrow().value)
+            .map(|(i, (a, b))| (i, a.borrow().value.clone(), b.borrow().value.clone()))
+    }
+
+    // Replays an edit script produced by `diff`. The whole script is checked
+    // against the current contents first, so on error the list is unchanged.
+    pub fn apply(&mut self, edits: &[EditOp<T>]) -> Result<(), ListError> {
+        let mut nodes = self.iter().flatten();
+        let mut index = 0;
+        for edit in edits {
+            match edit {
+                EditOp::Keep(value) | EditOp::Delete(value) => {
+                    let mismatch = ListError::EditScriptMismatch { index };
+                    let node = nodes.next().ok_or(mismatch)?;
+                    if node.borrow().value != *value {
+                        return Err(mismatch);
+                    }
+                    index += 1;
+                }
+                EditOp::Insert(_) => {}
+            }
+        }
+        if nodes.next().is_some() {
+            return Err(ListError::EditScriptMismatch { index });
+        }
+
+        let mut patched = LinkedList::new();
+        let mut run = HashRun::EMPTY;
+        for edit in edits {
+            match edit {
+                EditOp::Keep(value) => {
+                    self.hash_extend(&mut run, value);
+                    patched.push_back_node(self.pop_front_node().unwrap());
+                }
+                EditOp::Delete(_) => {
+                    self.pop_front_node();
+                }
+                EditOp::Insert(value) => {
+                    self.hash_extend(&mut run, value);
+                    patched.push_back(value.clone());
+                }
+            }
+        }
+        self.head = patched.head.take();
+        self.tail = patched.tail.take();
+        self.len = patched.len;
+        self.hash_reset(run);
+        self.invalidate_cursors();
+        Ok(())
+    }
+}
+
+fn diff_slices<T: PartialEq + Clone>(old: &[T], new: &[T], edits: &mut Vec<EditOp<T>>) {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    edits.extend(old[..prefix].iter().cloned().map(EditOp::Keep));
+    let split = if old_mid.is_empty() || new_mid.is_empty() {
+        None
+    } else {
+        middle_snake(old_mid, new_mid)
+    };
+    match split {
+        // both halves are smaller, the split is on a shortest edit path
+        Some((x, y)) => {
+            diff_slices(&old_mid[..x], &new_mid[..y], edits);
+            diff_slices(&old_mid[x..], &new_mid[y..], edits);
+        }
+        None => {
+            edits.extend(old_mid.iter().cloned().map(EditOp::Delete));
+            edits.extend(new_mid.iter().cloned().map(EditOp::Insert));
+        }
+    }
+    edits.extend(old[old.len() - suffix..].iter().cloned().map(EditOp::Keep));
+}
+
+// Where a shortest edit path crosses the middle, found by searching forward
+// from the start and backward from the end until the two searches meet. The
+// slices are not empty and differ in their first and last values. None when
+// they have nothing in common.
+fn middle_snake<T: PartialEq>(old: &[T], new: &[T]) -> Option<(usize, usize)> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_d = (n + m + 1) / 2;
+    let offset = max_d;
+    let width = 2 * max_d + 2;
+    // furthest x reached on each diagonal k = x - y, from the front and the back
+    let mut forward = vec![-1isize; width as usize];
+    let mut backward = vec![-1isize; width as usize];
+    forward[offset as usize + 1] = 0;
+    backward[offset as usize + 1] = 0;
+    let delta = n - m;
+    // with odd delta the forward search is the one to meet the backward one
+    let front = delta % 2 != 0;
+    // diagonals past the edges of the grid, skipped from then on
+    let (mut k1_start, mut k1_end, mut k2_start, mut k2_end) = (0, 0, 0, 0);
+
+    for d in 0..max_d {
+        for k1 in (-d + k1_start..=d - k1_end).step_by(2) {
+            let k1_offset = (offset + k1) as usize;
+            let mut x1 = if k1 == -d || (k1 != d && forward[k1_offset - 1] < forward[k1_offset + 1])
+            {
+                forward[k1_offset + 1]
+            } else {
+                forward[k1_offset - 1] + 1
+            };
+            let mut y1 = x1 - k1;
+            while x1 < n && y1 < m && old[x1 as usize] == new[y1 as usize] {
+                x1 += 1;
+                y1 += 1;
+            }
+            forward[k1_offset] = x1;
+            if x1 > n {
+                k1_end += 2;
+            } else if y1 > m {
+                k1_start += 2;
+            } else if front {
+                let k2_offset = offset + delta - k1;
+                if (0..width).contains(&k2_offset) && backward[k2_offset as usize] != -1 {
+                    let x2 = n - backward[k2_offset as usize];
+                    if x1 >= x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+        }
+
+        for k2 in (-d + k2_start..=d - k2_end).step_by(2) {
+            let k2_offset = (offset + k2) as usize;
+            let mut x2 =
+                if k2 == -d || (k2 != d && backward[k2_offset - 1] < backward[k2_offset + 1]) {
+                    backward[k2_offset + 1]
+                } else {
+                    backward[k2_offset - 1] + 1
+                };
+            let mut y2 = x2 - k2;
+            while x2 < n && y2 < m && old[(n - x2 - 1) as usize] == new[(m - y2 - 1) as usize] {
+                x2 += 1;
+                y2 += 1;
+            }
+            backward[k2_offset] = x2;
+            if x2 > n {
+                k2_end += 2;
+            } else if y2 > m {
+                k2_start += 2;
+            } else if !front {
+                let k1_offset = offset + delta - k2;
+                if (0..width).contains(&k1_offset) && forward[k1_offset as usize] != -1 {
+                    let x1 = forward[k1_offset as usize];
+                    let y1 = offset + x1 - k1_offset;
+                    if x1 >= n - x2 {
+                        return Some((x1 as usize, y1 as usize));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+
+    fn list_of(values: &[char]) -> LinkedList<char> {
+        let mut list = LinkedList::new();
+        for value in values {
+            list.push_back(*value);
+        }
+        list
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = list_of(&['a', 'b', 'c', 'a', 'b', 'b', 'a']);
+        let new = list_of(&['c', 'b', 'a', 'b', 'a', 'c']);
+
+        let edits = old.diff(&new);
+
+        let changes = edits
+            .iter()
+            .filter(|edit| !matches!(edit, EditOp::Keep(_)))
+            .count();
+        assert_eq!(changes, 5);
+        let kept: Vec<char> = edits
+            .iter()
+            .filter_map(|edit| match edit {
+                EditOp::Keep(value) | EditOp::Delete(value) => Some(*value),
+                EditOp::Insert(_) => None,
+            })
+            .collect();
+        assert_eq!(kept, old.collect_values());
+        let produced: Vec<char> = edits
+            .iter()
+            .filter_map(|edit| match edit {
+                EditOp::Keep(value) | EditOp::Insert(value) => Some(*value),
+                EditOp::Delete(_) => None,
+            })
+            .collect();
+        assert_eq!(produced, new.collect_values());
+    }
+
+    // Reference edit count from a plain LCS table, used as an oracle for `diff`.
+    fn lcs_edits(old: &[u8], new: &[u8]) -> usize {
+        let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+        old.len() + new.len() - 2 * lcs[0][0]
+    }
+
+    #[test]
+    fn test_diff_is_minimal() {
+        let mut seed = 5u32;
+        let mut random_list = |len: u32| {
+            let mut list = LinkedList::new();
+            for _ in 0..len {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                list.push_back((seed >> 16) as u8 % 4);
+            }
+            list
+        };
+        for round in 0..300 {
+            let old = random_list(round % 23);
+            let new = random_list(round % 17);
+            let edits = old.diff(&new);
+            let changes = edits
+                .iter()
+                .filter(|edit| !matches!(edit, EditOp::Keep(_)))
+                .count();
+            assert_eq!(
+                changes,
+                lcs_edits(&old.collect_values(), &new.collect_values())
+            );
+            let mut patched = old.clone();
+            patched.apply(&edits).unwrap();
+            assert_eq!(patched.collect_values(), new.collect_values());
+        }
+    }
+
+    #[test]
+    fn test_diff_long_lists() {
+        // an n * m table would need 10^10 entries
+        let mut old = LinkedList::new();
+        let mut new = LinkedList::new();
+        for i in 0..100_000 {
+            old.push_back(i);
+            if i % 10_000 != 0 {
+                new.push_back(i);
+            }
+        }
+        new.push_front(-1);
+        let edits = old.diff(&new);
+        let changes = edits
+            .iter()
+            .filter(|edit| !matches!(edit, EditOp::Keep(_)))
+            .count();
+        assert_eq!(changes, 11);
+        old.apply(&edits).unwrap();
+        assert_eq!(old.collect_values(), new.collect_values());
+    }
+
+    #[test]
+    fn test_diff_edges() {
+        let same = list_of(&['a', 'b']);
+        assert_eq!(same.diff(&same), [EditOp::Keep('a'), EditOp::Keep('b')]);
+        assert_eq!(
+            list_of(&[]).diff(&same),
+            [EditOp::Insert('a'), EditOp::Insert('b')]
+        );
+        assert_eq!(
+            same.diff(&list_of(&[])),
+            [EditOp::Delete('a'), EditOp::Delete('b')]
+        );
+        assert_eq!(
+            list_of(&['a', 'x', 'b']).diff(&list_of(&['a', 'y', 'b'])),
+            [
+                EditOp::Keep('a'),
+                EditOp::Delete('x'),
+                EditOp::Insert('y'),
+                EditOp::Keep('b')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut old = list_of(&['a', 'b', 'c', 'a', 'b', 'b', 'a']);
+        let new = list_of(&['c', 'b', 'a', 'b', 'a', 'c']);
+        let edits = old.diff(&new);
+        // the node under the first kept value
+        let first_keep = edits
+            .iter()
+            .filter(|edit| !matches!(edit, EditOp::Insert(_)))
+            .position(|edit| matches!(edit, EditOp::Keep(_)))
+            .unwrap();
+        let kept = old.get_nth(first_keep).unwrap().unwrap();
+
+        old.apply(&edits).unwrap();
+
+        assert_eq!(old.collect_values(), new.collect_values());
+        assert!(old.iter().flatten().any(|node| Rc::ptr_eq(&node, &kept)));
+        assert_eq!(old.tail_node().unwrap().borrow().value, 'c');
+
+        let mut empty = list_of(&[]);
+        empty.apply(&empty.diff(&new)).unwrap();
+        assert_eq!(empty.collect_values(), new.collect_values());
+        old.apply(&old.diff(&list_of(&[]))).unwrap();
+        assert!(old.head.is_none());
+        assert!(old.tail.is_none());
+    }
+
+    #[test]
+    fn test_apply_validates_script() {
+        let mut list = list_of(&['a', 'b']);
+
+        let wrong_value = [EditOp::Keep('a'), EditOp::Delete('c')];
+        let mismatch = |index| Err(ListError::EditScriptMismatch { index });
+        assert_eq!(list.apply(&wrong_value), mismatch(1));
+        let too_long = [EditOp::Keep('a'), EditOp::Keep('b'), EditOp::Delete('c')];
+        assert_eq!(list.apply(&too_long), mismatch(2));
+        let too_short = [EditOp::Insert('x'), EditOp::Delete('a')];
+        assert_eq!(list.apply(&too_short), mismatch(1));
+
+        assert_eq!(list.collect_values(), ['a', 'b']);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let list = list_of(&['a', 'b', 'c']);
+
+        assert_eq!(
+            list.first_divergence(&list_of(&['a', 'x', 'y'])),
+            Some((1, 'b', 'x'))
+        );
+        assert_eq!(list.first_divergence(&list_of(&['z'])), Some((0, 'a', 'z')));
+        assert_eq!(list.first_divergence(&list_of(&['a', 'b', 'c'])), None);
+        assert_eq!(list.first_divergence(&list_of(&['a', 'b'])), None);
+        assert_eq!(list.first_divergence(&list_of(&['a', 'b', 'c', 'd'])), None);
+        assert_eq!(list.first_divergence(&list_of(&[])), None);
+    }
+}
@@ -0,0 +1,466 @@
+use alloc::rc::{Rc, Weak};
+use core::cell::RefCell;
+
+use crate::ListError;
+
+pub type DoublyNodeHandle<T> = Rc<RefCell<DoublyNode<T>>>;
+type DoublyLink<T> = Option<DoublyNodeHandle<T>>;
+
+#[derive(Debug)]
+pub struct DoublyNode<T> {
+    pub value: T,
+    next: DoublyLink<T>,
+    // Weak, so neighbours don't keep each other alive in a cycle
+    prev: Option<Weak<RefCell<DoublyNode<T>>>>,
+    // header of the list the node is linked in, checked by the handle methods;
+    // a node that isn't linked holds `Weak::new()`, which matches no list
+    list: Weak<()>,
+}
+
+impl<T> DoublyNode<T> {
+    fn prev(&self) -> DoublyLink<T> {
+        self.prev.as_ref().and_then(Weak::upgrade)
+    }
+}
+
+// List with a back link in every node: pop_back, iterating from the back and
+// moving a cursor backwards are O(1) per step. Forward links own the nodes,
+// back links are Weak, so dropping the list frees everything.
+pub struct DoublyLinkedList<T> {
+    head: DoublyLink<T>,
+    tail: DoublyLink<T>,
+    len: usize,
+    // identifies the list by address, nodes keep it from being reused
+    header: Rc<()>,
+}
+
+// Yields node handles from either end until the two ends meet.
+pub struct DoublyIter<T> {
+    front: DoublyLink<T>,
+    back: DoublyLink<T>,
+    len: usize,
+}
+
+impl<T> Iterator for DoublyIter<T> {
+    type Item = DoublyNodeHandle<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.front.take()?;
+        self.front = node.borrow().next.clone();
+        self.len -= 1;
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for DoublyIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.back.take()?;
+        self.back = node.borrow().prev();
+        self.len -= 1;
+        Some(node)
+    }
+}
+
+impl<T> ExactSizeIterator for DoublyIter<T> {}
+
+impl<T> DoublyLinkedList<T> {
+    pub fn new() -> DoublyLinkedList<T> {
+        DoublyLinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            header: Rc::new(()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        let new = Rc::new(RefCell::new(DoublyNode {
+            value,
+            next: None,
+            prev: self.tail.as_ref().map(Rc::downgrade),
+            list: Rc::downgrade(&self.header),
+        }));
+        match self.tail.replace(new.clone()) {
+            None => self.head = Some(new),
+            Some(tail) => tail.borrow_mut().next = Some(new),
+        }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.link_front(Rc::new(RefCell::new(DoublyNode {
+            value,
+            next: None,
+            prev: None,
+            list: Weak::new(),
+        })));
+    }
+
+    // Links a detached node in as the new head.
+    fn link_front(&mut self, new: DoublyNodeHandle<T>) {
+        new.borrow_mut().list = Rc::downgrade(&self.header);
+        new.borrow_mut().next = self.head.take();
+        match &new.borrow().next {
+            None => self.tail = Some(new.clone()),
+            Some(next) => next.borrow_mut().prev = Some(Rc::downgrade(&new)),
+        }
+        self.head = Some(new);
+        self.len += 1;
+    }
+
+    pub fn front_node(&self) -> DoublyLink<T> {
+        self.head.clone()
+    }
+
+    pub fn back_node(&self) -> DoublyLink<T> {
+        self.tail.clone()
+    }
+
+    fn check_linked(&self, node: &DoublyNodeHandle<T>) -> Result<(), ListError> {
+        match Weak::as_ptr(&node.borrow().list) == Rc::as_ptr(&self.header) {
+            true => Ok(()),
+            false => Err(ListError::StaleHandle),
+        }
+    }
+
+    // Takes the node out of the chain in O(1) through its back link, the
+    // handle keeps the value. StaleHandle if the node isn't linked in this
+    // list, e.g. it was unlinked already.
+    pub fn unlink(&mut self, node: &DoublyNodeHandle<T>) -> Result<(), ListError> {
+        self.check_linked(node)?;
+        node.borrow_mut().list = Weak::new();
+        let prev = node
+            .borrow_mut()
+            .prev
+            .take()
+            .and_then(|prev| prev.upgrade());
+        let next = node.borrow_mut().next.take();
+        match &next {
+            None => self.tail = prev.clone(),
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+        }
+        match prev {
+            None => self.head = next,
+            Some(prev) => prev.borrow_mut().next = next,
+        }
+        self.len -= 1;
+        Ok(())
+    }
+
+    // O(1), StaleHandle as for `unlink`.
+    pub fn move_to_front(&mut self, node: &DoublyNodeHandle<T>) -> Result<(), ListError> {
+        self.check_linked(node)?;
+        if self
+            .head
+            .as_ref()
+            .is_some_and(|head| Rc::ptr_eq(head, node))
+        {
+            return Ok(());
+        }
+        self.unlink(node)?;
+        self.link_front(node.clone());
+        Ok(())
+    }
+
+    // Detached node of a pop, its value is moved out unless the node is still
+    // referenced elsewhere.
+    fn into_value(node: DoublyNodeHandle<T>) -> T
+    where
+        T: Clone,
+    {
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().value,
+            Err(node) => node.borrow().value.clone(),
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let node = self.head.take()?;
+        node.borrow_mut().list = Weak::new();
+        self.head = node.borrow_mut().next.take();
+        match &self.head {
+            None => self.tail = None,
+            Some(head) => head.borrow_mut().prev = None,
+        }
+        self.len -= 1;
+        Some(Self::into_value(node))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let node = self.tail.take()?;
+        node.borrow_mut().list = Weak::new();
+        self.tail = node
+            .borrow_mut()
+            .prev
+            .take()
+            .and_then(|prev| prev.upgrade());
+        match &self.tail {
+            None => self.head = None,
+            Some(tail) => tail.borrow_mut().next = None,
+        }
+        self.len -= 1;
+        Some(Self::into_value(node))
+    }
+
+    pub fn iter(&self) -> DoublyIter<T> {
+        DoublyIter {
+            front: self.head.clone(),
+            back: self.tail.clone(),
+            len: self.len,
+        }
+    }
+
+    pub fn cursor_front(&self) -> DoublyCursor<'_, T> {
+        DoublyCursor {
+            list: self,
+            current: self.head.clone(),
+        }
+    }
+
+    pub fn cursor_back(&self) -> DoublyCursor<'_, T> {
+        DoublyCursor {
+            list: self,
+            current: self.tail.clone(),
+        }
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        DoublyLinkedList::new()
+    }
+}
+
+// Same as for LinkedList, unlinks front to back instead of recursing.
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_inner().next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+// Read cursor moving both ways in O(1). Past either end it's on a ghost
+// position, moving on from there wraps around to the other end.
+pub struct DoublyCursor<'a, T> {
+    list: &'a DoublyLinkedList<T>,
+    current: DoublyLink<T>,
+}
+
+impl<T> DoublyCursor<'_, T> {
+    pub fn current(&self) -> DoublyLink<T> {
+        self.current.clone()
+    }
+
+    pub fn move_next(&mut self) {
+        self.current = match self.current.take() {
+            None => self.list.head.clone(),
+            Some(node) => node.borrow().next.clone(),
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.current = match self.current.take() {
+            None => self.list.tail.clone(),
+            Some(node) => node.borrow().prev(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use std::collections::VecDeque;
+
+    fn values<T: Clone>(list: &DoublyLinkedList<T>) -> Vec<T> {
+        list.iter()
+            .map(|node| node.borrow().value.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        let mut model = VecDeque::new();
+        for i in 0..300 {
+            match i % 5 {
+                0 | 3 => {
+                    list.push_back(i);
+                    model.push_back(i);
+                }
+                1 => {
+                    list.push_front(i);
+                    model.push_front(i);
+                }
+                2 => assert_eq!(list.pop_back(), model.pop_back()),
+                _ => assert_eq!(list.pop_front(), model.pop_front()),
+            }
+            assert_eq!(values(&list), model.iter().copied().collect::<Vec<_>>());
+            assert_eq!(list.len(), model.len());
+        }
+        while let Some(value) = model.pop_back() {
+            assert_eq!(list.pop_back(), Some(value));
+        }
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+        assert_eq!(list.pop_front(), None);
+    }
+
+    #[test]
+    fn test_reverse_iteration() {
+        let mut list = DoublyLinkedList::default();
+        for i in 0..5 {
+            list.push_back(i);
+        }
+        let reversed: Vec<_> = list.iter().rev().map(|node| node.borrow().value).collect();
+        assert_eq!(reversed, [4, 3, 2, 1, 0]);
+
+        // both ends of one iterator meet in the middle
+        let mut iter = list.iter();
+        assert_eq!(iter.next().unwrap().borrow().value, 0);
+        assert_eq!(iter.next_back().unwrap().borrow().value, 4);
+        assert_eq!(iter.len(), 3);
+        let rest: Vec<_> = iter.map(|node| node.borrow().value).collect();
+        assert_eq!(rest, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_cursor_moves_both_ways() {
+        let mut list = DoublyLinkedList::new();
+        for value in ['a', 'b', 'c'] {
+            list.push_back(value);
+        }
+        let value = |cursor: &DoublyCursor<char>| cursor.current().map(|node| node.borrow().value);
+        let mut cursor = list.cursor_back();
+        cursor.move_prev();
+        assert_eq!(value(&cursor), Some('b'));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(value(&cursor), None);
+        cursor.move_prev();
+        assert_eq!(value(&cursor), Some('c'));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(value(&cursor), Some('a'));
+        assert_eq!(value(&list.cursor_front()), Some('a'));
+    }
+
+    #[test]
+    fn test_unlink_and_move_to_front() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..5 {
+            list.push_back(i);
+        }
+        let nodes: Vec<_> = list.iter().collect();
+        list.unlink(&nodes[2]).unwrap();
+        list.unlink(&nodes[0]).unwrap();
+        list.unlink(&nodes[4]).unwrap();
+        assert_eq!(values(&list), [1, 3]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(nodes[2].borrow().value, 2);
+
+        list.move_to_front(&nodes[3]).unwrap();
+        list.move_to_front(&nodes[3]).unwrap();
+        assert_eq!(values(&list), [3, 1]);
+        let reversed: Vec<_> = list.iter().rev().map(|node| node.borrow().value).collect();
+        assert_eq!(reversed, [1, 3]);
+        assert!(Rc::ptr_eq(&list.back_node().unwrap(), &nodes[1]));
+
+        list.unlink(&nodes[1]).unwrap();
+        list.unlink(&nodes[3]).unwrap();
+        assert!(list.is_empty() && list.front_node().is_none() && list.back_node().is_none());
+        list.push_back(5);
+        assert_eq!(values(&list), [5]);
+    }
+
+    #[test]
+    fn test_unlink_checks_membership() {
+        let mut list = DoublyLinkedList::new();
+        let mut other = DoublyLinkedList::new();
+        for i in 0..5 {
+            list.push_back(i);
+            other.push_back(i);
+        }
+        let nodes: Vec<_> = list.iter().collect();
+        let foreign = other.front_node().unwrap();
+
+        list.unlink(&nodes[1]).unwrap();
+        assert_eq!(list.unlink(&nodes[1]), Err(ListError::StaleHandle));
+        assert_eq!(list.move_to_front(&nodes[1]), Err(ListError::StaleHandle));
+        assert_eq!(list.unlink(&foreign), Err(ListError::StaleHandle));
+        assert_eq!(list.move_to_front(&foreign), Err(ListError::StaleHandle));
+        assert_eq!((list.len(), other.len()), (4, 5));
+        assert_eq!(values(&list), [0, 2, 3, 4]);
+        assert_eq!(values(&other), [0, 1, 2, 3, 4]);
+
+        // a popped node is no longer linked either
+        list.pop_front();
+        assert_eq!(list.unlink(&nodes[0]), Err(ListError::StaleHandle));
+        list.move_to_front(&nodes[4]).unwrap();
+        assert_eq!(values(&list), [4, 2, 3]);
+        assert!(Rc::ptr_eq(&list.front_node().unwrap(), &nodes[4]));
+    }
+
+    #[test]
+    fn test_handle_outlives_its_list() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(1);
+        let node = list.front_node().unwrap();
+        drop(list);
+        // the node keeps the old header alive, so no new list can match it
+        for _ in 0..8 {
+            let mut list = DoublyLinkedList::new();
+            list.push_back(2);
+            assert_eq!(list.unlink(&node), Err(ListError::StaleHandle));
+        }
+    }
+
+    #[test]
+    fn test_nodes_are_freed() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..3 {
+            list.push_back(Rc::new(i));
+        }
+        let middle = list.iter().nth(1).unwrap();
+        let weak = Rc::downgrade(&middle);
+        drop(middle);
+        drop(list);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut list = DoublyLinkedList::new();
+        for i in 0..1_000_000 {
+            list.push_back(i);
+        }
+        drop(list);
+    }
+}
@@ -0,0 +1,77 @@
+use core::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListError {
+    IndexOutOfBounds { index: usize, len: usize },
+    EmptyList,
+    // a handle whose element was removed, or that belongs to another collection
+    StaleHandle,
+    // `decrease_key` with a value greater than the current one
+    KeyIncreased,
+    // an edit script that stops matching the list at `index`
+    EditScriptMismatch { index: usize },
+    // stored bytes that don't decode to an element
+    CorruptElement { index: usize },
+    // a fixed-capacity list or pool with no free slot left
+    CapacityExceeded { capacity: usize },
+    // a cursor used after a relinking change invalidated it
+    CursorInvalidated,
+    // an element whose key is already indexed
+    DuplicateKey,
+}
+
+impl Display for ListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {index} out of bounds for list of length {len}")
+            }
+            ListError::EmptyList => write!(f, "list is empty"),
+            ListError::StaleHandle => write!(f, "handle refers to a removed element"),
+            ListError::KeyIncreased => write!(f, "new key is greater than the current one"),
+            ListError::EditScriptMismatch { index } => {
+                write!(f, "edit script does not match the list at index {index}")
+            }
+            ListError::CorruptElement { index } => {
+                write!(f, "element at index {index} is corrupt")
+            }
+            ListError::CapacityExceeded { capacity } => {
+                write!(f, "capacity of {capacity} elements exceeded")
+            }
+            ListError::CursorInvalidated => write!(f, "cursor was invalidated"),
+            ListError::DuplicateKey => write!(f, "key is already present"),
+        }
+    }
+}
+
+impl core::error::Error for ListError {}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ListError {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                defmt::write!(
+                    f,
+                    "index {} out of bounds for list of length {}",
+                    index,
+                    len
+                )
+            }
+            ListError::EmptyList => defmt::write!(f, "list is empty"),
+            ListError::StaleHandle => defmt::write!(f, "handle refers to a removed element"),
+            ListError::KeyIncreased => defmt::write!(f, "new key is greater than the current one"),
+            ListError::EditScriptMismatch { index } => {
+                defmt::write!(f, "edit script does not match the list at index {}", index)
+            }
+            ListError::CorruptElement { index } => {
+                defmt::write!(f, "element at index {} is corrupt", index)
+            }
+            ListError::CapacityExceeded { capacity } => {
+                defmt::write!(f, "capacity of {} elements exceeded", capacity)
+            }
+            ListError::CursorInvalidated => defmt::write!(f, "cursor was invalidated"),
+            ListError::DuplicateKey => defmt::write!(f, "key is already present"),
+        }
+    }
+}
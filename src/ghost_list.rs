@@ -0,0 +1,264 @@
+use alloc::rc::Rc;
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+
+use crate::ListError;
+
+// Invariant in 'id, so one brand can't be coerced into another.
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+// The only key to the cells of brand 'id. `GhostToken::scope` creates a fresh
+// brand for every call and the token can't be cloned, so at any time there is
+// either one `&mut GhostToken<'id>` or any number of `&GhostToken<'id>`: the
+// borrow checker enforces on the token what RefCell checks at runtime per node.
+pub struct GhostToken<'id> {
+    _brand: Brand<'id>,
+}
+
+impl GhostToken<'_> {
+    pub fn scope<R>(f: impl for<'new> FnOnce(GhostToken<'new>) -> R) -> R {
+        f(GhostToken {
+            _brand: PhantomData,
+        })
+    }
+}
+
+// The two unsafe blocks below are the whole trusted core, the same reasoning
+// as the GhostCell paper (Yanovski et al., ICFP 2021) and the `ghost-cell`
+// crate: a borrow of a cell is tied to a borrow of the token. Everything built
+// on top, the list included, is safe code.
+pub struct GhostCell<'id, T> {
+    _brand: Brand<'id>,
+    value: UnsafeCell<T>,
+}
+
+impl<'id, T> GhostCell<'id, T> {
+    pub fn new(value: T) -> GhostCell<'id, T> {
+        GhostCell {
+            _brand: PhantomData,
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    pub fn borrow<'a>(&'a self, _token: &'a GhostToken<'id>) -> &'a T {
+        // a shared token borrow rules out a `borrow_mut` of any cell of the brand
+        unsafe { &*self.value.get() }
+    }
+
+    pub fn borrow_mut<'a>(&'a self, _token: &'a mut GhostToken<'id>) -> &'a mut T {
+        // the token is borrowed exclusively, no other reference into a cell of
+        // the brand can be alive
+        unsafe { &mut *self.value.get() }
+    }
+}
+
+type GhostLink<'id, T> = Option<Rc<GhostCell<'id, GhostNode<'id, T>>>>;
+
+struct GhostNode<'id, T> {
+    value: T,
+    next: GhostLink<'id, T>,
+}
+
+// List core without per-node borrow flags: nodes are only reachable through the
+// token of the list's brand, so reads hand out plain references and nothing can
+// panic with "already borrowed".
+pub struct GhostList<'id, T> {
+    head: GhostLink<'id, T>,
+    tail: GhostLink<'id, T>,
+    len: usize,
+}
+
+pub struct GhostListIter<'a, 'id, T> {
+    current: Option<&'a GhostCell<'id, GhostNode<'id, T>>>,
+    token: &'a GhostToken<'id>,
+}
+
+impl<'a, 'id, T> Iterator for GhostListIter<'a, 'id, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?.borrow(self.token);
+        self.current = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+impl<'id, T> GhostList<'id, T> {
+    pub fn new() -> GhostList<'id, T> {
+        GhostList {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_back(&mut self, value: T, token: &mut GhostToken<'id>) {
+        let new = Rc::new(GhostCell::new(GhostNode { value, next: None }));
+        match self.tail.replace(new.clone()) {
+            None => self.head = Some(new),
+            Some(tail) => tail.borrow_mut(token).next = Some(new),
+        }
+        self.len += 1;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let new = Rc::new(GhostCell::new(GhostNode {
+            value,
+            next: self.head.take(),
+        }));
+        if self.tail.is_none() {
+            self.tail = Some(new.clone());
+        }
+        self.head = Some(new);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self, token: &mut GhostToken<'id>) -> Option<T> {
+        let node = self.head.take()?;
+        self.head = node.borrow_mut(token).next.take();
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        self.len -= 1;
+        // unlinked and no longer the tail, so this is the only handle
+        Rc::try_unwrap(node)
+            .ok()
+            .map(|node| node.into_inner().value)
+    }
+
+    pub fn iter<'a>(&'a self, token: &'a GhostToken<'id>) -> GhostListIter<'a, 'id, T> {
+        GhostListIter {
+            current: self.head.as_deref(),
+            token,
+        }
+    }
+
+    pub fn get_nth<'a>(&'a self, n: usize, token: &'a GhostToken<'id>) -> Option<&'a T> {
+        self.iter(token).nth(n)
+    }
+
+    pub fn update_nth(
+        &self,
+        n: usize,
+        value: T,
+        token: &mut GhostToken<'id>,
+    ) -> Result<(), ListError> {
+        let mut current = self.head.as_ref();
+        for _ in 0..n {
+            current = current.and_then(|node| node.borrow(token).next.as_ref());
+        }
+        // only the handle of the nth node is cloned, to let go of the shared token
+        let node = current.cloned().ok_or(ListError::IndexOutOfBounds {
+            index: n,
+            len: self.len,
+        })?;
+        node.borrow_mut(token).value = value;
+        Ok(())
+    }
+
+    pub fn for_each_mut(&self, token: &mut GhostToken<'id>, mut f: impl FnMut(&mut T)) {
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let node = node.borrow_mut(token);
+            f(&mut node.value);
+            current = node.next.clone();
+        }
+    }
+}
+
+// Frees front to back without a token: a node held only by its predecessor
+// is owned here outright.
+impl<T> Drop for GhostList<'_, T> {
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_inner().next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<T> Default for GhostList<'_, T> {
+    fn default() -> Self {
+        GhostList::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_push_and_read() {
+        GhostToken::scope(|mut token| {
+            let mut list = GhostList::new();
+            list.push_back(2, &mut token);
+            list.push_back(3, &mut token);
+            list.push_front(1);
+
+            // plain references to several nodes at once, no guards
+            let first = list.get_nth(0, &token).unwrap();
+            let last = list.get_nth(2, &token).unwrap();
+            assert_eq!((*first, *last), (1, 3));
+            assert!(list.get_nth(3, &token).is_none());
+            assert_eq!(list.iter(&token).copied().collect::<Vec<_>>(), [1, 2, 3]);
+            assert_eq!(list.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_mutate() {
+        GhostToken::scope(|mut token| {
+            let mut list = GhostList::default();
+            for value in 0..4 {
+                list.push_back(value, &mut token);
+            }
+
+            list.update_nth(1, 10, &mut token).unwrap();
+            assert_eq!(
+                list.update_nth(4, 10, &mut token),
+                Err(ListError::IndexOutOfBounds { index: 4, len: 4 })
+            );
+            list.for_each_mut(&mut token, |value| *value *= 2);
+            assert_eq!(
+                list.iter(&token).copied().collect::<Vec<_>>(),
+                [0, 20, 4, 6]
+            );
+
+            assert_eq!(list.pop_front(&mut token), Some(0));
+            assert_eq!(list.pop_front(&mut token), Some(20));
+            assert_eq!(list.pop_front(&mut token), Some(4));
+            assert_eq!(list.pop_front(&mut token), Some(6));
+            assert_eq!(list.pop_front(&mut token), None);
+            assert!(list.is_empty());
+            list.push_back(7, &mut token);
+            assert_eq!(list.iter(&token).copied().collect::<Vec<_>>(), [7]);
+        });
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        GhostToken::scope(|mut token| {
+            let mut list = GhostList::new();
+            for value in 0..1_000_000 {
+                list.push_back(value, &mut token);
+            }
+            drop(list);
+        });
+    }
+}
@@ -0,0 +1,114 @@
+use core::hash::Hash;
+
+use crate::content_hash::hash_value;
+use crate::LinkedList;
+
+const GENESIS: u64 = 0;
+
+#[derive(Debug)]
+pub struct Chained<T> {
+    pub value: T,
+    // hash of (hash of the previous node, value)
+    pub hash: u64,
+}
+
+// Append-only list where every node commits to its value and to all the nodes
+// before it, like an audit log. Values edited through node handles afterwards
+// are found by `verify_chain`. The hash is FNV-1a: it catches accidental or
+// out-of-band edits, but it's no protection against someone who recomputes it.
+pub struct HashChainList<T> {
+    list: LinkedList<Chained<T>>,
+    last_hash: u64,
+}
+
+impl<T: Hash> HashChainList<T> {
+    pub fn new() -> HashChainList<T> {
+        HashChainList {
+            list: LinkedList::new(),
+            last_hash: GENESIS,
+        }
+    }
+
+    pub fn list(&self) -> &LinkedList<Chained<T>> {
+        &self.list
+    }
+
+    // Hash of the newest node, enough to pin down the whole chain.
+    pub fn last_hash(&self) -> u64 {
+        self.last_hash
+    }
+
+    pub fn push(&mut self, value: T) {
+        let hash = hash_value(&(self.last_hash, &value));
+        self.list.push_back(Chained { value, hash });
+        self.last_hash = hash;
+    }
+
+    // Err with the index of the first node whose value or hash doesn't match.
+    pub fn verify_chain(&self) -> Result<(), usize> {
+        let mut prev = GENESIS;
+        for (i, node) in self.list.iter().flatten().enumerate() {
+            let chained = &node.borrow().value;
+            if chained.hash != hash_value(&(prev, &chained.value)) {
+                return Err(i);
+            }
+            prev = chained.hash;
+        }
+        if prev != self.last_hash {
+            return Err(self.list.iter().count());
+        }
+        Ok(())
+    }
+}
+
+impl<T: Hash> Default for HashChainList<T> {
+    fn default() -> Self {
+        HashChainList::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::{String, ToString};
+
+    fn log() -> HashChainList<String> {
+        let mut log = HashChainList::new();
+        for entry in ["login alice", "grant admin", "logout alice"] {
+            log.push(entry.to_string());
+        }
+        log
+    }
+
+    #[test]
+    fn test_untouched_chain_verifies() {
+        let log = log();
+        assert_eq!(log.verify_chain(), Ok(()));
+        assert_eq!(HashChainList::<u8>::new().verify_chain(), Ok(()));
+        assert_ne!(log.last_hash(), GENESIS);
+        assert_eq!(log.list().iter().count(), 3);
+    }
+
+    #[test]
+    fn test_detects_edits() {
+        let log = log();
+        let node = log.list().get_nth(1).unwrap().unwrap();
+        node.borrow_mut().value.value = "grant guest".to_string();
+        assert_eq!(log.verify_chain(), Err(1));
+
+        // recomputing the edited node's hash breaks the link to the next one
+        let log = self::log();
+        let node = log.list().get_nth(1).unwrap().unwrap();
+        let prev = log.list().get_nth(0).unwrap().unwrap().borrow().value.hash;
+        node.borrow_mut().value.value = "grant guest".to_string();
+        let forged = hash_value(&(prev, &node.borrow().value.value));
+        node.borrow_mut().value.hash = forged;
+        assert_eq!(log.verify_chain(), Err(2));
+
+        // and rewriting the rest of the chain no longer matches the last hash
+        let last = log.list().get_nth(2).unwrap().unwrap();
+        let forged = hash_value(&(forged, &last.borrow().value.value));
+        last.borrow_mut().value.hash = forged;
+        assert_eq!(log.verify_chain(), Err(3));
+    }
+}
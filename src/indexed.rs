@@ -0,0 +1,238 @@
+use std::cell::Ref;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::mem;
+use std::rc::Rc;
+
+use crate::{LinkedList, LinkedListNodeIter, ListError, NodeHandle, ValueRef};
+
+// List with a key -> node map kept in sync, keys are extracted from the values
+// by a closure and must be unique.
+//
+// A singly linked node can be unlinked in O(1) by moving its successor's value
+// into it and unlinking the successor instead, re-pointing that value's key at
+// the surviving node. Only the last node has no successor, so `remove` and
+// `move_to_front` of the tail element still need to walk to its predecessor.
+pub struct IndexedList<K, T> {
+    list: LinkedList<T>,
+    index: HashMap<K, NodeHandle<T>>,
+    key: Box<dyn Fn(&T) -> K>,
+}
+
+// Read-only view of the values in list order. Only guards are handed out, no
+// node handles: a handle could change a key behind the index's back, or keep
+// a node alive that `remove` needs to take the value out of.
+pub struct IndexedIter<'a, T> {
+    nodes: LinkedListNodeIter<T>,
+    _list: PhantomData<&'a LinkedList<T>>,
+}
+
+impl<'a, T> Iterator for IndexedIter<'a, T> {
+    type Item = ValueRef<'a, T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(ValueRef::new(self.nodes.next()??))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IndexedIter<'_, T> {}
+
+impl<K: Eq + Hash, T> IndexedList<K, T> {
+    pub fn new(key: impl Fn(&T) -> K + 'static) -> IndexedList<K, T> {
+        IndexedList {
+            list: LinkedList::new(),
+            index: HashMap::new(),
+            key: Box::new(key),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn iter(&self) -> IndexedIter<'_, T> {
+        IndexedIter {
+            nodes: self.list.iter(),
+            _list: PhantomData,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.index.contains_key(key)
+    }
+
+    pub fn get(&self, key: &K) -> Option<Ref<'_, T>> {
+        let node = self.index.get(key)?;
+        Some(Ref::map(node.borrow(), |node| &node.value))
+    }
+
+    pub fn push_back(&mut self, value: T) -> Result<(), ListError> {
+        let key = (self.key)(&value);
+        if self.index.contains_key(&key) {
+            return Err(ListError::DuplicateKey);
+        }
+        self.list.push_back(value);
+        self.index.insert(key, self.list.tail_node().unwrap());
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, value: T) -> Result<(), ListError> {
+        let key = (self.key)(&value);
+        if self.index.contains_key(&key) {
+            return Err(ListError::DuplicateKey);
+        }
+        self.list.push_front(value);
+        self.index.insert(key, self.list.head.clone().unwrap());
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        let node = self.index.remove(key)?;
+        let next = node.borrow_mut().next.take();
+        let unlinked = match next {
+            Some(next) => {
+                mem::swap(&mut node.borrow_mut().value, &mut next.borrow_mut().value);
+                node.borrow_mut().next = next.borrow_mut().next.take();
+                if node.borrow().next.is_none() {
+                    self.list.set_tail(Some(&node));
+                }
+                let moved_key = (self.key)(&node.borrow().value);
+                self.index.insert(moved_key, node);
+                next
+            }
+            None => {
+                let prev = self.list.iter().flatten().find(|prev| {
+                    prev.borrow()
+                        .next
+                        .as_ref()
+                        .is_some_and(|next| Rc::ptr_eq(next, &node))
+                });
+                match &prev {
+                    None => self.list.head = None,
+                    Some(prev) => prev.borrow_mut().next = None,
+                }
+                self.list.set_tail(prev.as_ref());
+                node
+            }
+        };
+        self.list.len -= 1;
+        // the map and the list held the only handles to the unlinked node,
+        // none are handed out
+        let unlinked = Rc::try_unwrap(unlinked).ok().expect("node handle escaped");
+        Some(unlinked.into_inner().value)
+    }
+
+    pub fn move_to_front(&mut self, key: &K) -> bool {
+        match self.index.get(key) {
+            None => false,
+            Some(node) if Rc::ptr_eq(node, self.list.head.as_ref().unwrap()) => true,
+            Some(_) => {
+                let value = self.remove(key).unwrap();
+                self.list.push_front(value);
+                let key = (self.key)(&self.list.head.as_ref().unwrap().borrow().value);
+                self.index.insert(key, self.list.head.clone().unwrap());
+                true
+            }
+        }
+    }
+}
+
+impl<K, T: Debug> Debug for IndexedList<K, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.list, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn users() -> IndexedList<u32, (u32, &'static str)> {
+        let mut list = IndexedList::new(|user: &(u32, &str)| user.0);
+        list.push_back((1, "ann")).unwrap();
+        list.push_back((2, "bob")).unwrap();
+        list.push_back((3, "cid")).unwrap();
+        list.push_front((0, "dan")).unwrap();
+        list
+    }
+
+    fn ids(list: &IndexedList<u32, (u32, &'static str)>) -> Vec<u32> {
+        list.iter().map(|user| user.0).collect()
+    }
+
+    #[test]
+    fn test_get_and_duplicates() {
+        let mut list = users();
+
+        assert_eq!(list.get(&2).unwrap().1, "bob");
+        assert!(list.get(&7).is_none());
+        assert_eq!(
+            list.push_back((2, "other bob")),
+            Err(ListError::DuplicateKey)
+        );
+        assert!(list.push_front((0, "other dan")).is_err());
+        assert_eq!(list.len(), 4);
+        assert_eq!(ids(&list), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list = users();
+
+        assert_eq!(list.remove(&1), Some((1, "ann")));
+        assert_eq!(list.get(&2).unwrap().1, "bob");
+        assert_eq!(list.remove(&3), Some((3, "cid")));
+        assert_eq!(list.remove(&3), None);
+        assert_eq!(ids(&list), [0, 2]);
+        assert_eq!(list.list.tail_node().unwrap().borrow().value.0, 2);
+        assert_eq!(list.list.len(), 2);
+
+        // the successor of the head is the tail
+        assert_eq!(list.remove(&0), Some((0, "dan")));
+        assert_eq!(list.list.tail_node().unwrap().borrow().value.0, 2);
+        assert_eq!(list.remove(&2), Some((2, "bob")));
+        assert!(list.is_empty());
+        assert!(list.list.head.is_none());
+        assert!(list.list.tail.is_none());
+        assert!(list.list.is_empty());
+        list.push_back((5, "eve")).unwrap();
+        assert_eq!(ids(&list), [5]);
+    }
+
+    #[test]
+    fn test_move_to_front() {
+        let mut list = users();
+
+        assert!(list.move_to_front(&2));
+        assert!(list.move_to_front(&3));
+        assert!(list.move_to_front(&3));
+        assert!(!list.move_to_front(&9));
+
+        assert_eq!(ids(&list), [3, 2, 0, 1]);
+        assert_eq!(list.get(&1).unwrap().1, "ann");
+        assert_eq!(list.list.tail_node().unwrap().borrow().value.0, 1);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn test_remove_while_viewed() {
+        let mut list = users();
+        let names: Vec<_> = list.iter().map(|user| user.1).collect();
+        assert_eq!(names, ["dan", "ann", "bob", "cid"]);
+        assert_eq!(list.iter().len(), 4);
+        // every value can be moved out, no handle keeps a node alive
+        for id in [3, 1, 0, 2] {
+            assert_eq!(list.remove(&id).map(|user| user.0), Some(id));
+        }
+        assert_eq!(format!("{list:?}"), "[]");
+    }
+}
@@ -0,0 +1,341 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::str::FromStr;
+
+use crate::LinkedList;
+
+type SyncHook<W> = Box<dyn FnMut(&mut W) -> io::Result<()>>;
+
+// Every mutation is appended to the journal (and synced, if a hook is set)
+// before it is applied, so the list can be rebuilt with `replay` after a crash.
+// Records look like `push_after_n 2 5:value\n`: values are written with Display,
+// read back with FromStr and length-prefixed, so they may contain newlines.
+pub struct JournaledList<T, W: Write> {
+    list: LinkedList<T>,
+    journal: W,
+    sync: Option<SyncHook<W>>,
+    // bytes of complete records `replay` read
+    replayed_len: u64,
+}
+
+// Counts the bytes taken from the reader, to know where the last complete
+// record ends.
+struct Counted<R> {
+    inner: R,
+    read: u64,
+}
+
+impl<R: Read> Read for Counted<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Counted<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.read += amt as u64;
+        self.inner.consume(amt);
+    }
+}
+
+fn invalid(msg: impl Display) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, msg.to_string())
+}
+
+// None if the journal ends before the delimiter, i.e. at a torn record.
+fn read_token<R: BufRead>(reader: &mut R, delim: u8) -> io::Result<Option<String>> {
+    let mut buf = Vec::new();
+    reader.read_until(delim, &mut buf)?;
+    if buf.pop() != Some(delim) {
+        return Ok(None);
+    }
+    String::from_utf8(buf)
+        .map(Some)
+        .map_err(|_| invalid("journal is not valid UTF-8"))
+}
+
+fn read_index<R: BufRead>(reader: &mut R, delim: u8) -> io::Result<Option<usize>> {
+    match read_token(reader, delim)? {
+        None => Ok(None),
+        Some(n) => n
+            .parse()
+            .map(Some)
+            .map_err(|_| invalid("bad index in journal")),
+    }
+}
+
+fn read_value<T: FromStr, R: BufRead>(reader: &mut R) -> io::Result<Option<T>> {
+    let Some(len) = read_index(reader, b':')? else {
+        return Ok(None);
+    };
+    let mut bytes = vec![0; len + 1];
+    match reader.read_exact(&mut bytes) {
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        result => result?,
+    }
+    if bytes.pop() != Some(b'\n') {
+        return Err(invalid("journal record is not terminated"));
+    }
+    let text = String::from_utf8(bytes).map_err(|_| invalid("journal is not valid UTF-8"))?;
+    text.parse()
+        .map(Some)
+        .map_err(|_| invalid("cannot parse journaled value"))
+}
+
+impl<T: Display + FromStr, W: Write> JournaledList<T, W> {
+    pub fn new(journal: W) -> JournaledList<T, W> {
+        JournaledList {
+            list: LinkedList::new(),
+            journal,
+            sync: None,
+            replayed_len: 0,
+        }
+    }
+
+    // Called after each record is written, e.g. `|file: &mut File| file.sync_data()`.
+    pub fn with_sync(mut self, sync: impl FnMut(&mut W) -> io::Result<()> + 'static) -> Self {
+        self.sync = Some(Box::new(sync));
+        self
+    }
+
+    // Rebuilds the list from a journal and keeps appending to `journal`. A torn
+    // record at the end, left by a crash during its write, was never applied and
+    // is ignored, but its bytes are still there: cut the journal back to
+    // `replayed_len` before appending to it again, or the next record lands
+    // behind them and every later replay fails. `replay_file` does that.
+    pub fn replay<R: BufRead>(reader: R, journal: W) -> io::Result<JournaledList<T, W>> {
+        let mut reader = Counted {
+            inner: reader,
+            read: 0,
+        };
+        let mut replayed_len = 0;
+        let mut list = LinkedList::new();
+        while let Some(op) = read_token(&mut reader, b' ')? {
+            let applied = match op.as_str() {
+                "push_back" | "push_front" => {
+                    let Some(value) = read_value(&mut reader)? else {
+                        break;
+                    };
+                    if op == "push_back" {
+                        list.push_back(value);
+                    } else {
+                        list.push_front(value);
+                    }
+                    Ok(())
+                }
+                "push_after_n" | "update_nth" => {
+                    let Some(n) = read_index(&mut reader, b' ')? else {
+                        break;
+                    };
+                    let Some(value) = read_value(&mut reader)? else {
+                        break;
+                    };
+                    if op == "push_after_n" {
+                        list.push_after_n(n, value).map_err(invalid)
+                    } else {
+                        list.update_nth(n, value).map_err(invalid)
+                    }
+                }
+                "rotate_to" => {
+                    let Some(n) = read_index(&mut reader, b'\n')? else {
+                        break;
+                    };
+                    list.rotate_to(n).map_err(invalid)
+                }
+                _ => Err(invalid("unknown journal operation")),
+            };
+            applied?;
+            replayed_len = reader.read;
+        }
+        Ok(JournaledList {
+            list,
+            journal,
+            sync: None,
+            replayed_len,
+        })
+    }
+
+    // Length of the journal up to the end of its last complete record.
+    pub fn replayed_len(&self) -> u64 {
+        self.replayed_len
+    }
+
+    pub fn list(&self) -> &LinkedList<T> {
+        &self.list
+    }
+
+    pub fn into_parts(self) -> (LinkedList<T>, W) {
+        (self.list, self.journal)
+    }
+
+    fn append(&mut self, op: &str, index: Option<usize>, value: Option<&T>) -> io::Result<()> {
+        let mut record = String::from(op);
+        if let Some(index) = index {
+            record.push_str(&format!(" {index}"));
+        }
+        match value {
+            None => record.push('\n'),
+            Some(value) => {
+                let value = value.to_string();
+                record.push_str(&format!(" {}:{value}\n", value.len()));
+            }
+        }
+        self.journal.write_all(record.as_bytes())?;
+        self.journal.flush()?;
+        if let Some(sync) = &mut self.sync {
+            sync(&mut self.journal)?;
+        }
+        Ok(())
+    }
+
+    fn check_index(&self, n: usize) -> io::Result<()> {
+        self.list
+            .get_nth(n)
+            .map(|_| ())
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, err.to_string()))
+    }
+
+    pub fn push_back(&mut self, value: T) -> io::Result<()> {
+        self.append("push_back", None, Some(&value))?;
+        self.list.push_back(value);
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, value: T) -> io::Result<()> {
+        self.append("push_front", None, Some(&value))?;
+        self.list.push_front(value);
+        Ok(())
+    }
+
+    pub fn push_after_n(&mut self, n: usize, value: T) -> io::Result<()> {
+        self.check_index(n)?;
+        self.append("push_after_n", Some(n), Some(&value))?;
+        self.list.push_after_n(n, value).map_err(invalid)
+    }
+
+    pub fn update_nth(&mut self, n: usize, value: T) -> io::Result<()> {
+        self.check_index(n)?;
+        self.append("update_nth", Some(n), Some(&value))?;
+        self.list.update_nth(n, value).map_err(invalid)
+    }
+
+    pub fn rotate_to(&mut self, n: usize) -> io::Result<()> {
+        self.check_index(n)?;
+        self.append("rotate_to", Some(n), None)?;
+        self.list.rotate_to(n).map_err(invalid)
+    }
+}
+
+impl<T: Display + FromStr> JournaledList<T, File> {
+    // Replays a journal file and keeps appending to it, after cutting off a torn
+    // record at its end. The file must be open for reading and writing.
+    pub fn replay_file(mut file: File) -> io::Result<JournaledList<T, File>> {
+        file.rewind()?;
+        let mut replayed = Self::replay(BufReader::new(file.try_clone()?), file)?;
+        replayed.journal.set_len(replayed.replayed_len)?;
+        replayed.journal.seek(SeekFrom::End(0))?;
+        Ok(replayed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_replay_rebuilds_list() {
+        let syncs = Rc::new(Cell::new(0));
+        let counter = syncs.clone();
+        let mut list = JournaledList::new(Vec::new()).with_sync(move |_| {
+            counter.set(counter.get() + 1);
+            Ok(())
+        });
+        list.push_back("b".to_string()).unwrap();
+        list.push_front("a".to_string()).unwrap();
+        list.push_back("line\nbreak".to_string()).unwrap();
+        list.push_after_n(0, "x y".to_string()).unwrap();
+        list.update_nth(1, "".to_string()).unwrap();
+        list.rotate_to(1).unwrap();
+        assert!(list.push_after_n(9, "z".to_string()).is_err());
+        assert_eq!(syncs.get(), 6);
+
+        let (list, journal) = list.into_parts();
+        let replayed = JournaledList::<String, _>::replay(&journal[..], Vec::new()).unwrap();
+        assert_eq!(replayed.list().to_string(), list.to_string());
+        assert_eq!(
+            format!("{:?}", replayed.list()),
+            r#"["", "b", "line\nbreak", "a"]"#
+        );
+    }
+
+    #[test]
+    fn test_replay_ignores_torn_record() {
+        let mut list = JournaledList::new(Vec::new());
+        list.push_back(1).unwrap();
+        list.push_back(22).unwrap();
+        let (_, mut journal) = list.into_parts();
+        journal.truncate(journal.len() - 2);
+
+        let replayed = JournaledList::<i32, _>::replay(&journal[..], Vec::new()).unwrap();
+        assert_eq!(replayed.list().to_string(), "[1]");
+        assert_eq!(replayed.replayed_len(), b"push_back 1:1\n".len() as u64);
+    }
+
+    #[test]
+    fn test_append_after_torn_replay() {
+        let mut list = JournaledList::new(Vec::new());
+        list.push_back(1).unwrap();
+        list.push_back(22).unwrap();
+        let (_, mut journal) = list.into_parts();
+        journal.truncate(journal.len() - 2);
+
+        // the documented usage: keep appending to the same journal
+        let replayed = JournaledList::<i32, _>::replay(&journal[..], Vec::new()).unwrap();
+        journal.truncate(replayed.replayed_len() as usize);
+        let mut resumed = JournaledList::<i32, _>::replay(&journal[..], journal.clone()).unwrap();
+        resumed.push_back(3).unwrap();
+        let (_, journal) = resumed.into_parts();
+        let again = JournaledList::<i32, _>::replay(&journal[..], Vec::new()).unwrap();
+        assert_eq!(again.list().to_string(), "[1, 3]");
+        assert_eq!(again.replayed_len(), journal.len() as u64);
+    }
+
+    #[test]
+    fn test_replay_file_cuts_torn_record() {
+        let path = std::env::temp_dir().join(format!("rll-journal-{}", std::process::id()));
+        let open = || {
+            std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .unwrap()
+        };
+        std::fs::write(&path, b"push_back 1:1\npush_back 2:2").unwrap();
+
+        let mut resumed = JournaledList::<i32, _>::replay_file(open()).unwrap();
+        resumed.push_back(3).unwrap();
+        drop(resumed);
+        let again = JournaledList::<i32, _>::replay_file(open()).unwrap();
+        assert_eq!(again.list().to_string(), "[1, 3]");
+        drop(again);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_replay_rejects_garbage() {
+        assert!(JournaledList::<i32, _>::replay(&b"pop 1:1\n"[..], Vec::new()).is_err());
+        assert!(JournaledList::<i32, _>::replay(&b"push_back 1:x\n"[..], Vec::new()).is_err());
+        assert!(JournaledList::<i32, _>::replay(&b"rotate_to 3\n"[..], Vec::new()).is_err());
+    }
+}
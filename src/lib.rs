@@ -0,0 +1,4005 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod adapters;
+#[cfg(feature = "std")]
+mod arc_list;
+#[cfg(feature = "mmap")]
+mod archive;
+mod arena_list;
+#[cfg(feature = "bignum")]
+mod bignum;
+#[cfg(feature = "std")]
+mod blocking_queue;
+pub mod boxed;
+mod circular;
+#[cfg(target_has_atomic = "ptr")]
+pub mod concurrent;
+mod content_hash;
+mod copy_list;
+mod cursor;
+#[cfg(feature = "serde")]
+mod deserialize;
+mod diff;
+mod doubly;
+mod error;
+mod ghost_list;
+mod hash_chain;
+#[cfg(feature = "std")]
+mod indexed;
+#[cfg(feature = "std")]
+mod journal;
+#[cfg(feature = "examples-api")]
+mod line_buffer;
+#[cfg(feature = "std")]
+mod lru;
+mod node_alloc;
+mod node_pool;
+mod pairing_heap;
+mod persistent;
+mod priority_list;
+#[cfg(feature = "serde")]
+mod serialize;
+mod skip_list;
+mod small_list;
+mod sorted;
+#[cfg(feature = "spill")]
+mod spill;
+mod static_list;
+mod stats;
+mod unrolled;
+mod weak_list;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::rc::{Rc, Weak};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use content_hash::{ContentHash, HashRun};
+use core::any::Any;
+use core::cell::{Cell, Ref, RefCell, RefMut};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use cursor::CursorRegistry;
+
+#[cfg(feature = "std")]
+pub use arc_list::{ArcLinkedList, ArcListIter, ArcNode, ArcNodeHandle};
+#[cfg(feature = "mmap")]
+pub use archive::{Archivable, ArchivedList, ArchivedListIter};
+pub use arena_list::{ArenaKey, ArenaList, ArenaListIter};
+#[cfg(feature = "bignum")]
+pub use bignum::{BigNum, ParseBigNumError};
+#[cfg(feature = "std")]
+pub use blocking_queue::BoundedBlockingQueue;
+pub use circular::{CircularCycleIter, CircularIter, CircularList};
+pub use copy_list::{CopyList, CopyListIter};
+pub use cursor::{Cursor, CursorMut};
+pub use diff::EditOp;
+pub use doubly::{DoublyCursor, DoublyIter, DoublyLinkedList, DoublyNode, DoublyNodeHandle};
+pub use error::ListError;
+pub use ghost_list::{GhostCell, GhostList, GhostListIter, GhostToken};
+pub use hash_chain::{Chained, HashChainList};
+#[cfg(feature = "std")]
+pub use indexed::{IndexedIter, IndexedList};
+#[cfg(feature = "std")]
+pub use journal::JournaledList;
+#[cfg(feature = "examples-api")]
+pub use line_buffer::LineBuffer;
+#[cfg(feature = "std")]
+pub use lru::LruCache;
+pub use node_alloc::{BumpAlloc, Global, NodeAlloc};
+#[cfg(target_has_atomic = "ptr")]
+pub use node_pool::SpinLock;
+pub use node_pool::{DefaultPoolLock, LocalLock, NodePool, PoolList, PoolLock};
+pub use pairing_heap::{HeapEntry, PairingHeap};
+pub use persistent::{PersistentList, PersistentListIter};
+pub use priority_list::PriorityList;
+pub use skip_list::{SkipList, SkipListIter};
+pub use small_list::{SmallList, SmallListIter};
+pub use sorted::SortedLinkedList;
+#[cfg(feature = "spill")]
+pub use spill::{SpillingList, SpillingListIter};
+pub use static_list::{StaticList, StaticListIter};
+pub use stats::{Numeric, Stats};
+pub use unrolled::{UnrolledList, UnrolledListIter};
+pub use weak_list::{WeakList, WeakListIter};
+
+// Builds a list like `vec!`: `linked_list![1, 2, 3]`, or `linked_list![x; n]`
+// with n clones of x.
+#[macro_export]
+macro_rules! linked_list {
+    () => {
+        $crate::LinkedList::new()
+    };
+    ($value:expr; $n:expr) => {{
+        let value = $value;
+        $crate::LinkedList::from_fn($n, |_| ::core::clone::Clone::clone(&value))
+    }};
+    ($($value:expr),+ $(,)?) => {{
+        let mut list = $crate::LinkedList::new();
+        $(list.push_back($value);)+
+        list
+    }};
+}
+
+pub type NodeHandle<T> = Rc<RefCell<Node<T>>>;
+type Link<T> = Option<NodeHandle<T>>;
+type WeakLink<T> = Option<Weak<RefCell<Node<T>>>>;
+#[derive(Debug)]
+pub struct Node<T> {
+    pub value: T,
+    next: Link<T>,
+    // logically deleted, unlinked by the next `sweep`
+    removed: bool,
+}
+
+impl<T> Node<T> {
+    fn new(value: T, next: Link<T>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Node {
+            value,
+            next,
+            removed: false,
+        }))
+    }
+}
+
+// Every node is owned by exactly one strong link, head or the next of its
+// predecessor; tail only points back weakly, so unlinking a node always frees it.
+pub struct LinkedList<T> {
+    head: Link<T>,
+    tail: WeakLink<T>,
+    len: usize,
+    // Some once `enable_content_hash` is called; every mutation has to keep it in
+    // sync, the relinking helpers leave that to their callers
+    content_hash: Cell<Option<ContentHash<T>>>,
+    // open cursors, mutations shift or invalidate them
+    cursors: CursorRegistry<T>,
+    // Display and Debug print at most this many elements
+    display_limit: usize,
+}
+
+const DEFAULT_DISPLAY_LIMIT: usize = 100;
+
+// Walks the next links from the front and yields at most the length the list
+// had when it was created. Nodes only link forward, so the first `next_back`
+// collects the handles still ahead into `path`, both ends take from it after that.
+#[derive(Debug)]
+pub struct LinkedListNodeIter<T> {
+    current: Link<T>,
+    len: usize,
+    path: Option<VecDeque<NodeHandle<T>>>,
+}
+
+impl<T> Iterator for LinkedListNodeIter<T> {
+    type Item = Link<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = match &mut self.path {
+            Some(path) => path.pop_front()?,
+            None => {
+                let node = self.current.take()?;
+                self.current = node.borrow().next.clone();
+                node
+            }
+        };
+        self.len -= 1;
+        Some(Some(node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for LinkedListNodeIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.path.is_none() {
+            let mut path = VecDeque::with_capacity(self.len);
+            let mut current = self.current.take();
+            while let Some(node) = current {
+                if path.len() == self.len {
+                    break;
+                }
+                current = node.borrow().next.clone();
+                path.push_back(node);
+            }
+            self.path = Some(path);
+        }
+        let node = self.path.as_mut()?.pop_back()?;
+        self.len -= 1;
+        Some(Some(node))
+    }
+}
+
+impl<T> ExactSizeIterator for LinkedListNodeIter<T> {}
+
+impl<T> FusedIterator for LinkedListNodeIter<T> {}
+
+impl<T> LinkedListNodeIter<T> {
+    // The node the next call to `next` yields, without advancing.
+    pub fn peek(&self) -> Option<&NodeHandle<T>> {
+        if self.len == 0 {
+            return None;
+        }
+        match &self.path {
+            Some(path) => path.front(),
+            None => self.current.as_ref(),
+        }
+    }
+
+    // Looks n nodes further ahead than `peek`, following the next links.
+    pub fn peek_nth(&self, n: usize) -> Link<T> {
+        if n >= self.len {
+            return None;
+        }
+        follow(self.peek()?, n)
+    }
+}
+
+// Yields clones of the values, for when the node handles aren't needed.
+#[derive(Debug)]
+pub struct LinkedListValues<T> {
+    nodes: LinkedListNodeIter<T>,
+}
+
+impl<T: Clone> Iterator for LinkedListValues<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next()??;
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T: Clone> DoubleEndedIterator for LinkedListValues<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next_back()??;
+        let value = node.borrow().value.clone();
+        Some(value)
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for LinkedListValues<T> {}
+
+impl<T: Clone> FusedIterator for LinkedListValues<T> {}
+
+// Borrows each boxed trait object in turn, see `iter_dyn`.
+pub struct LinkedListDynIter<'a, T: ?Sized> {
+    nodes: LinkedListNodeIter<Box<T>>,
+    _list: PhantomData<&'a LinkedList<Box<T>>>,
+}
+
+impl<'a, T: ?Sized> Iterator for LinkedListDynIter<'a, T> {
+    type Item = ValueRef<'a, T, Box<T>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next()??;
+        Some(ValueRef::unboxed(node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<T: ?Sized> DoubleEndedIterator for LinkedListDynIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next_back()??;
+        Some(ValueRef::unboxed(node))
+    }
+}
+
+impl<T: ?Sized> ExactSizeIterator for LinkedListDynIter<'_, T> {}
+
+impl<T: ?Sized> FusedIterator for LinkedListDynIter<'_, T> {}
+
+// Consumes the list from the front, see `pop_front` for when values are cloned.
+#[derive(Debug)]
+pub struct LinkedListIntoIter<T> {
+    list: LinkedList<T>,
+}
+
+impl<T: Clone> Iterator for LinkedListIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for LinkedListIntoIter<T> {}
+
+// Pops values off the front as it goes. Whatever wasn't yielded is removed
+// when it's dropped, like `Vec::drain`.
+pub struct LinkedListDrain<'a, T> {
+    list: &'a mut LinkedList<T>,
+}
+
+impl<T: Clone> Iterator for LinkedListDrain<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T: Clone> ExactSizeIterator for LinkedListDrain<'_, T> {}
+
+impl<T> Drop for LinkedListDrain<'_, T> {
+    fn drop(&mut self) {
+        self.list.clear();
+    }
+}
+
+// Unlinks and yields the values matching `pred`, one node at a time. Nodes not
+// reached before it's dropped stay in the list.
+pub struct LinkedListExtractIf<'a, T, F> {
+    list: &'a mut LinkedList<T>,
+    pred: F,
+    prev: Link<T>,
+    current: Link<T>,
+    index: usize,
+    // hash of the values kept so far
+    prefix: HashRun,
+}
+
+impl<T: Clone, F: FnMut(&T) -> bool> Iterator for LinkedListExtractIf<'_, T, F> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.current.take() {
+            let next = node.borrow().next.clone();
+            self.current = next.clone();
+            if !(self.pred)(&node.borrow().value) {
+                self.list
+                    .hash_extend(&mut self.prefix, &node.borrow().value);
+                self.prev = Some(node);
+                self.index += 1;
+                continue;
+            }
+            node.borrow_mut().next = None;
+            match &self.prev {
+                None => self.list.head = next.clone(),
+                Some(prev) => prev.borrow_mut().next = next.clone(),
+            }
+            if next.is_none() {
+                self.list.set_tail(self.prev.as_ref());
+            }
+            let removed = self.list.hash_one(&node.borrow().value);
+            let suffix_len = self.list.len - self.index - 1;
+            self.list
+                .hash_replaced(self.prefix, removed, HashRun::EMPTY, suffix_len);
+            self.list.len -= 1;
+            self.list.cursors_removed(self.index, &next);
+            return Some(into_value(node));
+        }
+        None
+    }
+}
+
+// Yields every step-th node. Skipped nodes are only borrowed on the way, the
+// handle of a node is cloned just for the ones yielded.
+#[derive(Debug)]
+pub struct LinkedListStepIter<T> {
+    current: Link<T>,
+    step: usize,
+}
+
+impl<T> Iterator for LinkedListStepIter<T> {
+    type Item = Link<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = follow(&node, self.step);
+        Some(Some(node))
+    }
+}
+
+// Yields the nodes in consecutive groups of N. A shorter group left at the end
+// isn't yielded, it's kept for `remainder` once the iterator is exhausted.
+#[derive(Debug)]
+pub struct LinkedListTuples<T, const N: usize> {
+    nodes: LinkedListNodeIter<T>,
+    remainder: Vec<NodeHandle<T>>,
+}
+
+impl<T, const N: usize> LinkedListTuples<T, N> {
+    pub fn remainder(&self) -> &[NodeHandle<T>] {
+        &self.remainder
+    }
+}
+
+impl<T, const N: usize> Iterator for LinkedListTuples<T, N> {
+    type Item = [NodeHandle<T>; N];
+    fn next(&mut self) -> Option<Self::Item> {
+        let group: Vec<_> = self.nodes.by_ref().flatten().take(N).collect();
+        match group.try_into() {
+            Ok(group) => Some(group),
+            Err(rest) => {
+                if !rest.is_empty() {
+                    self.remainder = rest;
+                }
+                None
+            }
+        }
+    }
+}
+
+fn into_value<T: Clone>(node: NodeHandle<T>) -> T {
+    match Rc::try_unwrap(node) {
+        Ok(node) => node.into_inner().value,
+        Err(node) => node.borrow().value.clone(),
+    }
+}
+
+// Node k links after `node`, walked in a loop so long strides can't exhaust
+// the stack. Only the handle of the node reached is cloned.
+fn follow<T>(node: &NodeHandle<T>, k: usize) -> Link<T> {
+    let mut current = Rc::as_ptr(node);
+    for _ in 0..k {
+        // `node` keeps the first node alive and each node keeps its successor,
+        // nothing runs that could unlink them during the walk
+        let next = unsafe { &*current }
+            .borrow()
+            .next
+            .as_ref()
+            .map(Rc::as_ptr)?;
+        current = next;
+    }
+    unsafe {
+        Rc::increment_strong_count(current);
+        Some(Rc::from_raw(current))
+    }
+}
+
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            content_hash: Cell::new(None),
+            cursors: RefCell::new(Vec::new()),
+            display_limit: DEFAULT_DISPLAY_LIMIT,
+        }
+    }
+
+    pub fn from_fn(n: usize, mut f: impl FnMut(usize) -> T) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        for i in 0..n {
+            list.push_back(f(i));
+        }
+        list
+    }
+
+    // Starts with `first` (if any) and appends `succ` of the last value until it
+    // returns None.
+    pub fn successors(first: Option<T>, mut succ: impl FnMut(&T) -> Option<T>) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        let mut next = first;
+        while let Some(value) = next {
+            next = succ(&value);
+            list.push_back(value);
+        }
+        list
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Floyd's tortoise and hare over the next links themselves, not bounded by
+    // len like `iter`. The list's own methods never close a cycle, this is a
+    // check for relinking bugs and handles passed to the wrong list.
+    pub fn is_cyclic(&self) -> bool {
+        let next = |node: NodeHandle<T>| node.borrow().next.clone();
+        let mut slow = self.head.clone();
+        let mut fast = self.head.clone();
+        while let Some(hare) = fast.and_then(next).and_then(next) {
+            // the tortoise is behind the hare, so it has a successor
+            let tortoise = slow.and_then(next).unwrap();
+            if Rc::ptr_eq(&tortoise, &hare) {
+                return true;
+            }
+            slow = Some(tortoise);
+            fast = Some(hare);
+        }
+        false
+    }
+
+    pub(crate) fn tail_node(&self) -> Link<T> {
+        self.tail.as_ref().and_then(Weak::upgrade)
+    }
+
+    pub(crate) fn set_tail(&mut self, tail: Option<&NodeHandle<T>>) {
+        self.tail = tail.map(Rc::downgrade);
+    }
+
+    fn out_of_bounds(&self, index: usize) -> ListError {
+        ListError::IndexOutOfBounds {
+            index,
+            len: self.len,
+        }
+    }
+
+    pub fn push_back(&mut self, value: T) -> NodeRef<T> {
+        self.hash_pushed_back(&value);
+        self.cursors_pushed_back();
+        self.len += 1;
+        let new = Node::new(value, None);
+        match self.tail_node() {
+            None => self.head = Some(new.clone()),
+            Some(node) => node.borrow_mut().next = Some(new.clone()),
+        }
+        self.set_tail(Some(&new));
+        NodeRef::new(&new)
+    }
+
+    pub fn push_front(&mut self, value: T) -> NodeRef<T> {
+        self.hash_pushed_front(&value);
+        self.cursors_inserted(0);
+        self.len += 1;
+        let new = Node::new(value, None);
+        match self.head.take() {
+            None => {
+                self.set_tail(Some(&new));
+                self.head = Some(new.clone());
+            }
+            Some(node) => {
+                new.borrow_mut().next = Some(node.clone());
+                self.head = Some(new.clone());
+            }
+        }
+        NodeRef::new(&new)
+    }
+
+    // Like `Vec::insert`: the value ends up at `index`, 0 makes it the head and
+    // len appends it.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<NodeRef<T>, ListError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        if index == 0 {
+            return Ok(self.push_front(value));
+        }
+        if index == self.len {
+            return Ok(self.push_back(value));
+        }
+        let prefix = self.hash_run(self.iter().flatten().take(index));
+        let inserted = self.hash_one(&value);
+        self.hash_replaced(prefix, HashRun::EMPTY, inserted, self.len - index);
+        let prev = self.iter().nth(index - 1).unwrap().unwrap();
+        let next = prev.borrow_mut().next.take();
+        let new = Node::new(value, next);
+        prev.borrow_mut().next = Some(new.clone());
+        self.len += 1;
+        self.cursors_inserted(index);
+        Ok(NodeRef::new(&new))
+    }
+
+    // O(1) through the handle, None once its node is gone.
+    pub fn value(&self, node: &NodeRef<T>) -> Option<ValueRef<'_, T>> {
+        Some(ValueRef::new(node.0.upgrade()?))
+    }
+
+    // Position of a node linked in this list. Nodes only link forward and
+    // can outlive the list through other handles, so membership is checked by
+    // a walk from the head.
+    fn position_of(&self, target: &NodeHandle<T>) -> Option<usize> {
+        self.iter()
+            .flatten()
+            .position(|node| Rc::ptr_eq(&node, target))
+    }
+
+    // Links the value in behind the node. None if the node is gone, unlinked
+    // or in another list. O(1) for the tail, otherwise O(n): the membership
+    // check walks from the head, see `position_of`.
+    pub fn insert_after(&mut self, node: &NodeRef<T>, value: T) -> Option<NodeRef<T>> {
+        let prev = node.0.upgrade()?;
+        let index = match self.tail_node() {
+            Some(tail) if Rc::ptr_eq(&tail, &prev) => self.len - 1,
+            _ => self.position_of(&prev)?,
+        };
+        let prefix = self.hash_run(self.iter().flatten().take(index + 1));
+        let inserted = self.hash_one(&value);
+        self.hash_replaced(prefix, HashRun::EMPTY, inserted, self.len - index - 1);
+        let next = prev.borrow_mut().next.take();
+        let is_tail = next.is_none();
+        let new = Node::new(value, next);
+        prev.borrow_mut().next = Some(new.clone());
+        if is_tail {
+            self.set_tail(Some(&new));
+        }
+        self.len += 1;
+        self.cursors_inserted(index + 1);
+        Some(NodeRef::new(&new))
+    }
+
+    // Links the whole chain of other in so its head ends up at `at`. Finding the
+    // spot takes `at` steps, the relinking itself is O(1).
+    pub fn splice(&mut self, at: usize, mut other: LinkedList<T>) -> Result<(), ListError> {
+        if at > self.len {
+            return Err(self.out_of_bounds(at));
+        }
+        if other.is_empty() {
+            return Ok(());
+        }
+        let prefix = self.hash_run(self.iter().flatten().take(at));
+        let inserted = self.hash_run_of(&other);
+        self.hash_replaced(prefix, HashRun::EMPTY, inserted, self.len - at);
+        let other_head = other.head.take().unwrap();
+        let other_tail = other.tail_node().unwrap();
+        let count = mem::take(&mut other.len);
+        if at == self.len {
+            self.set_tail(Some(&other_tail));
+        }
+        match at {
+            0 => other_tail.borrow_mut().next = self.head.replace(other_head),
+            _ => {
+                let prev = self.iter().nth(at - 1).unwrap().unwrap();
+                other_tail.borrow_mut().next = prev.borrow_mut().next.replace(other_head);
+            }
+        }
+        other.tail = None;
+        self.len += count;
+        self.cursors_spliced(at, count);
+        Ok(())
+    }
+
+    // Kept for existing callers, the same as `insert(n + 1, value)`.
+    pub fn push_after_n(&mut self, n: usize, value: T) -> Result<(), ListError> {
+        if n >= self.len {
+            return Err(self.out_of_bounds(n));
+        }
+        self.insert(n + 1, value).map(drop)
+    }
+
+    pub fn iter(&self) -> LinkedListNodeIter<T> {
+        LinkedListNodeIter {
+            current: self.head.clone(),
+            len: self.len,
+            path: None,
+        }
+    }
+
+    pub fn get_nth(&self, nth: usize) -> Result<Link<T>, ListError> {
+        self.iter().nth(nth).ok_or_else(|| self.out_of_bounds(nth))
+    }
+
+    // Borrows the nth value in place. There's no `Index` impl: it would have to
+    // return a plain &T out of the node's RefCell, with nothing stopping a
+    // `borrow_mut` through a handle while that reference is alive. `at` is the
+    // panicking form instead.
+    pub fn get(&self, n: usize) -> Option<ValueRef<'_, T>> {
+        Some(ValueRef::new(self.iter().nth(n)??))
+    }
+
+    // Like `get`, mutably. Changes made through the guard aren't seen by the
+    // content hash until it is rebuilt, see `enable_content_hash`.
+    pub fn get_mut(&self, n: usize) -> Option<ValueRefMut<'_, T>> {
+        Some(ValueRefMut::new(self.iter().nth(n)??))
+    }
+
+    // `list.at(n)` where a Vec would be indexed, panics when out of bounds.
+    #[track_caller]
+    pub fn at(&self, n: usize) -> ValueRef<'_, T> {
+        match self.get(n) {
+            Some(value) => value,
+            None => self.index_out_of_bounds(n),
+        }
+    }
+
+    #[track_caller]
+    pub fn at_mut(&self, n: usize) -> ValueRefMut<'_, T> {
+        match self.get_mut(n) {
+            Some(value) => value,
+            None => self.index_out_of_bounds(n),
+        }
+    }
+
+    #[track_caller]
+    fn index_out_of_bounds(&self, n: usize) -> ! {
+        panic!("{}", self.out_of_bounds(n))
+    }
+
+    pub fn front(&self) -> Option<ValueRef<'_, T>> {
+        Some(ValueRef::new(self.head.clone()?))
+    }
+
+    pub fn back(&self) -> Option<ValueRef<'_, T>> {
+        Some(ValueRef::new(self.tail_node()?))
+    }
+
+    pub fn front_mut(&self) -> Option<ValueRefMut<'_, T>> {
+        Some(ValueRefMut::new(self.head.clone()?))
+    }
+
+    pub fn back_mut(&self) -> Option<ValueRefMut<'_, T>> {
+        Some(ValueRefMut::new(self.tail_node()?))
+    }
+
+    // The first value matching `pred`, borrowed in place.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<ValueRef<'_, T>> {
+        let node = self
+            .iter()
+            .flatten()
+            .find(|node| pred(&node.borrow().value))?;
+        Some(ValueRef::new(node))
+    }
+
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<usize> {
+        self.iter()
+            .flatten()
+            .position(|node| pred(&node.borrow().value))
+    }
+
+    pub fn contains<U>(&self, x: &U) -> bool
+    where
+        T: PartialEq<U>,
+    {
+        self.position(|value| value == x).is_some()
+    }
+
+    pub fn update_nth(&self, nth: usize, value: T) -> Result<(), ListError> {
+        let node = self.get_nth(nth)?.unwrap();
+        let inserted = self.hash_one(&value);
+        let old = mem::replace(&mut node.borrow_mut().value, value);
+        let removed = self.hash_one(&old);
+        // same length, so the prefix drops out
+        self.hash_replaced(HashRun::EMPTY, removed, inserted, self.len - nth - 1);
+        Ok(())
+    }
+
+    // Like `Vec::split_off`: self keeps the first `at` elements and the rest is
+    // returned, at == len gives an empty list.
+    pub fn split_off(&mut self, at: usize) -> Result<LinkedList<T>, ListError> {
+        if at > self.len {
+            return Err(self.out_of_bounds(at));
+        }
+        let prefix = self.hash_run(self.iter().flatten().take(at));
+        let mut suffix = LinkedList::new();
+        if at == 0 {
+            suffix.head = self.head.take();
+            suffix.tail = self.tail.take();
+        } else {
+            let last = self.iter().nth(at - 1).unwrap().unwrap();
+            suffix.head = last.borrow_mut().next.take();
+            if suffix.head.is_some() {
+                suffix.tail = self.tail.replace(Rc::downgrade(&last));
+            }
+        }
+        suffix.len = self.len - at;
+        self.len = at;
+        self.hash_split(&suffix, prefix);
+        suffix.display_limit = self.display_limit;
+        self.invalidate_cursors();
+        Ok(suffix)
+    }
+
+    // The nodes are freed front to back by the Drop of the list split off.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // Keeps the first len elements, a longer len leaves the list as it is.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            drop(self.split_off(len).unwrap());
+        }
+    }
+
+    // Makes the nth node the new head, the nodes before it are moved behind the old tail.
+    pub fn rotate_to(&mut self, n: usize) -> Result<(), ListError> {
+        if self.is_empty() {
+            return Err(ListError::EmptyList);
+        }
+        if n >= self.len {
+            return Err(self.out_of_bounds(n));
+        }
+        if n == 0 {
+            return Ok(());
+        }
+        let prefix = self.hash_run(self.iter().flatten().take(n));
+        self.hash_rotated(prefix);
+        let new_tail = self.get_nth(n - 1)?.unwrap();
+        let new_head = new_tail.borrow_mut().next.take().unwrap();
+        let old_tail = self.tail_node().unwrap();
+        self.set_tail(Some(&new_tail));
+        old_tail.borrow_mut().next = self.head.replace(new_head);
+        self.invalidate_cursors();
+        Ok(())
+    }
+
+    // Moves all nodes of other behind the tail in O(1), other is left empty.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        if other.is_empty() {
+            return;
+        }
+        self.hash_appended(self.hash_run_of(other));
+        other.hash_reset(HashRun::EMPTY);
+        let other_head = other.head.take().unwrap();
+        match self.tail_node() {
+            None => self.head = Some(other_head),
+            Some(tail) => tail.borrow_mut().next = Some(other_head),
+        }
+        self.tail = other.tail.take();
+        let appended = mem::take(&mut other.len);
+        self.len += appended;
+        self.cursors_appended(appended);
+        other.invalidate_cursors();
+    }
+
+    // Stable merge sort relinking the nodes, handles keep their values.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        let mut unsorted = LinkedList::new();
+        unsorted.head = self.head.take();
+        unsorted.tail = self.tail.take();
+        unsorted.len = mem::take(&mut self.len);
+        let mut sorted = unsorted.merge_sort_by(&mut compare);
+        self.head = sorted.head.take();
+        self.tail = sorted.tail.take();
+        self.len = mem::take(&mut sorted.len);
+        self.rehash();
+        self.invalidate_cursors();
+    }
+
+    pub fn sort_by_key<K: Ord>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    fn merge_sort_by<F>(mut self, compare: &mut F) -> LinkedList<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.len < 2 {
+            return self;
+        }
+        let right = self.split_off(self.len / 2).unwrap();
+        let left = self.merge_sort_by(compare);
+        let right = right.merge_sort_by(compare);
+        left.merge_by(right, compare)
+    }
+
+    // Merges two sorted lists, on ties the node of self goes first.
+    fn merge_by<F>(mut self, mut other: LinkedList<T>, compare: &mut F) -> LinkedList<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut merged = LinkedList::new();
+        while let (Some(a), Some(b)) = (&self.head, &other.head) {
+            let node = if compare(&b.borrow().value, &a.borrow().value) == Ordering::Less {
+                other.pop_front_node()
+            } else {
+                self.pop_front_node()
+            };
+            merged.push_back_node(node.unwrap());
+        }
+        merged.append(&mut self);
+        merged.append(&mut other);
+        merged
+    }
+
+    // One pass turning every next link around, the nodes themselves stay put.
+    pub fn reverse(&mut self) {
+        let mut prev: Link<T> = None;
+        let mut current = self.head.take();
+        self.set_tail(current.as_ref());
+        while let Some(node) = current {
+            current = mem::replace(&mut node.borrow_mut().next, prev);
+            prev = Some(node);
+        }
+        self.head = prev;
+        self.rehash();
+        self.invalidate_cursors();
+    }
+
+    // Exchanges the positions of two nodes in the chain, handles keep pointing at the
+    // same nodes. Relinking is O(1), but as nodes don't know their predecessors one
+    // traversal is needed to find them. StaleHandle if either node is gone or not
+    // in this list.
+    pub fn swap_nodes(&mut self, a: &NodeRef<T>, b: &NodeRef<T>) -> Result<(), ListError> {
+        let (a, b) =
+            a.0.upgrade()
+                .zip(b.0.upgrade())
+                .ok_or(ListError::StaleHandle)?;
+        let (a, b) = (&a, &b);
+        let mut pred_a = None;
+        let mut pred_b = None;
+        let mut prev: Link<T> = None;
+        for (index, node) in self.iter().flatten().enumerate() {
+            if Rc::ptr_eq(&node, a) {
+                pred_a = Some((index, prev.clone()));
+            }
+            if Rc::ptr_eq(&node, b) {
+                pred_b = Some((index, prev.clone()));
+            }
+            prev = Some(node);
+        }
+        let ((index_a, pred_a), (index_b, pred_b)) =
+            pred_a.zip(pred_b).ok_or(ListError::StaleHandle)?;
+        if Rc::ptr_eq(a, b) {
+            return Ok(());
+        }
+        self.hash_swapped(index_a, &a.borrow().value, index_b, &b.borrow().value);
+
+        match pred_a {
+            None => self.head = Some(b.clone()),
+            Some(pred) => pred.borrow_mut().next = Some(b.clone()),
+        }
+        match pred_b {
+            None => self.head = Some(a.clone()),
+            Some(pred) => pred.borrow_mut().next = Some(a.clone()),
+        }
+        // for adjacent nodes the step above made one of them point to itself,
+        // swapping the next links resolves that as well
+        let a_next = a.borrow_mut().next.take();
+        let b_next = b.borrow_mut().next.take();
+        a.borrow_mut().next = b_next;
+        b.borrow_mut().next = a_next;
+
+        if let Some(tail) = self.tail_node() {
+            if Rc::ptr_eq(&tail, a) {
+                self.set_tail(Some(b));
+            } else if Rc::ptr_eq(&tail, b) {
+                self.set_tail(Some(a));
+            }
+        }
+        self.invalidate_cursors();
+        Ok(())
+    }
+
+    // Exchanges the values at two indices like `Vec::swap`, the nodes stay where they
+    // are, so cursors stay valid. `swap_nodes` moves the nodes instead.
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), ListError> {
+        if i >= self.len || j >= self.len {
+            return Err(self.out_of_bounds(i.max(j)));
+        }
+        if i == j {
+            return Ok(());
+        }
+        let (low, high) = (i.min(j), i.max(j));
+        let mut nodes = self.iter().flatten().skip(low);
+        let a = nodes.next().unwrap();
+        let b = nodes.nth(high - low - 1).unwrap();
+        self.hash_swapped(low, &a.borrow().value, high, &b.borrow().value);
+        mem::swap(&mut a.borrow_mut().value, &mut b.borrow_mut().value);
+        Ok(())
+    }
+
+    // The values at i and j trade places, each is replaced in place by the other.
+    fn hash_swapped(&self, i: usize, at_i: &T, j: usize, at_j: &T) {
+        let (a, b) = (self.hash_one(at_i), self.hash_one(at_j));
+        self.hash_replaced(HashRun::EMPTY, a, b, self.len - i - 1);
+        self.hash_replaced(HashRun::EMPTY, b, a, self.len - j - 1);
+    }
+
+    // Flags the node as deleted without relinking anything, so it is safe to call
+    // while iterating. The node stays in the list until `sweep` runs. StaleHandle
+    // if the node is gone or not in this list, which takes a walk to tell, see
+    // `position_of`.
+    pub fn mark_remove(&self, node: &NodeRef<T>) -> Result<(), ListError> {
+        let node = node.0.upgrade().ok_or(ListError::StaleHandle)?;
+        self.position_of(&node).ok_or(ListError::StaleHandle)?;
+        node.borrow_mut().removed = true;
+        Ok(())
+    }
+
+    // Unlinks every node flagged by `mark_remove` in one pass, returns how many.
+    pub fn sweep(&mut self) -> usize {
+        let mut swept = 0;
+        let mut index = 0;
+        let mut kept = HashRun::EMPTY;
+        let mut prev: Link<T> = None;
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let next = node.borrow().next.clone();
+            if node.borrow().removed {
+                match &prev {
+                    None => self.head = next.clone(),
+                    Some(prev) => prev.borrow_mut().next = next.clone(),
+                }
+                node.borrow_mut().next = None;
+                self.cursors_removed(index, &next);
+                swept += 1;
+            } else {
+                self.hash_extend(&mut kept, &node.borrow().value);
+                prev = Some(node);
+                index += 1;
+            }
+            current = next;
+        }
+        self.set_tail(prev.as_ref());
+        self.len -= swept;
+        if swept > 0 {
+            self.hash_reset(kept);
+        }
+        swept
+    }
+
+    // Unlinks every node whose value fails `keep`, in one pass.
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        let mut removed = 0;
+        let mut index = 0;
+        let mut kept = HashRun::EMPTY;
+        let mut prev: Link<T> = None;
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let next = node.borrow_mut().next.take();
+            if keep(&node.borrow().value) {
+                self.hash_extend(&mut kept, &node.borrow().value);
+                node.borrow_mut().next = next.clone();
+                prev = Some(node);
+                index += 1;
+            } else {
+                match &prev {
+                    None => self.head = next.clone(),
+                    Some(prev) => prev.borrow_mut().next = next.clone(),
+                }
+                self.cursors_removed(index, &next);
+                removed += 1;
+            }
+            current = next;
+        }
+        self.set_tail(prev.as_ref());
+        self.len -= removed;
+        if removed > 0 {
+            self.hash_reset(kept);
+        }
+    }
+
+    // Unlinks every node for which `same(value, kept)` holds, kept being the last
+    // value left before it, like `Vec::dedup_by`. One pass.
+    pub fn dedup_by(&mut self, mut same: impl FnMut(&T, &T) -> bool) {
+        let Some(mut kept) = self.head.clone() else {
+            return;
+        };
+        let mut index = 1;
+        let mut removed = 0;
+        let mut kept_run = self.hash_one(&kept.borrow().value);
+        let mut current = kept.borrow().next.clone();
+        while let Some(node) = current {
+            let next = node.borrow_mut().next.take();
+            if same(&node.borrow().value, &kept.borrow().value) {
+                kept.borrow_mut().next = next.clone();
+                self.cursors_removed(index, &next);
+                removed += 1;
+            } else {
+                self.hash_extend(&mut kept_run, &node.borrow().value);
+                node.borrow_mut().next = next.clone();
+                kept = node;
+                index += 1;
+            }
+            current = next;
+        }
+        self.set_tail(Some(&kept));
+        self.len -= removed;
+        if removed > 0 {
+            self.hash_reset(kept_run);
+        }
+    }
+
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&T) -> K) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    // Moves every element matching `pred` to the back of `target` in one pass,
+    // both lists keep their order. Returns how many were moved.
+    pub fn drain_matching_into(
+        &mut self,
+        target: &mut LinkedList<T>,
+        mut pred: impl FnMut(&T) -> bool,
+    ) -> usize {
+        let mut moved = 0;
+        let mut index = 0;
+        let mut kept = HashRun::EMPTY;
+        let mut prev: Link<T> = None;
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            let next = node.borrow().next.clone();
+            if pred(&node.borrow().value) {
+                match &prev {
+                    None => self.head = next.clone(),
+                    Some(prev) => prev.borrow_mut().next = next.clone(),
+                }
+                self.cursors_removed(index, &next);
+                target.hash_pushed_back(&node.borrow().value);
+                target.cursors_pushed_back();
+                target.push_back_node(node);
+                moved += 1;
+            } else {
+                self.hash_extend(&mut kept, &node.borrow().value);
+                prev = Some(node);
+                index += 1;
+            }
+            current = next;
+        }
+        self.set_tail(prev.as_ref());
+        self.len -= moved;
+        if moved > 0 {
+            self.hash_reset(kept);
+        }
+        moved
+    }
+
+    // Cuts the list into `parts` contiguous lists whose lengths differ by at most
+    // one, the longer ones first. Some of them are empty if parts > length.
+    pub fn split_into(mut self, parts: usize) -> Vec<LinkedList<T>> {
+        assert!(parts > 0, "parts must be non-zero");
+        let len = self.len;
+        let mut chunks = Vec::with_capacity(parts);
+        for i in 0..parts {
+            let mut chunk = LinkedList::new();
+            let chunk_len = len / parts + usize::from(i < len % parts);
+            if chunk_len > 0 {
+                let last = self.iter().nth(chunk_len - 1).unwrap().unwrap();
+                chunk.head = mem::replace(&mut self.head, last.borrow_mut().next.take());
+                chunk.set_tail(Some(&last));
+                chunk.len = chunk_len;
+            }
+            chunks.push(chunk);
+        }
+        self.tail = None;
+        self.len = 0;
+        chunks
+    }
+
+    // Deals the nodes round-robin into k lists, each keeping their relative order.
+    pub fn distribute(mut self, k: usize) -> Vec<LinkedList<T>> {
+        assert!(k > 0, "k must be non-zero");
+        let mut hands: Vec<LinkedList<T>> = (0..k).map(|_| LinkedList::new()).collect();
+        let mut hand = 0;
+        while let Some(node) = self.pop_front_node() {
+            hands[hand].push_back_node(node);
+            hand = (hand + 1) % k;
+        }
+        hands
+    }
+
+    // Interleaves the lists element by element (a1, b1, c1, a2, ...) by relinking
+    // their nodes, lists that run out are skipped. Inverse of `distribute`.
+    pub fn riffle(mut lists: Vec<LinkedList<T>>) -> LinkedList<T> {
+        let mut result = LinkedList::new();
+        while !lists.is_empty() {
+            lists.retain(|list| list.head.is_some());
+            for list in lists.iter_mut() {
+                result.push_back_node(list.pop_front_node().unwrap());
+            }
+        }
+        result
+    }
+
+    // Element-wise comparison with a caller-defined equivalence, lengths must match.
+    pub fn eq_by<U>(&self, other: &LinkedList<U>, mut eq: impl FnMut(&T, &U) -> bool) -> bool {
+        self.eq_by_iter(other.iter().flatten(), |value, node| {
+            eq(value, &node.borrow().value)
+        })
+    }
+
+    pub fn eq_by_iter<I: IntoIterator>(
+        &self,
+        other: I,
+        mut eq: impl FnMut(&T, I::Item) -> bool,
+    ) -> bool {
+        let mut others = other.into_iter();
+        self.iter().flatten().all(|node| {
+            others
+                .next()
+                .is_some_and(|other| eq(&node.borrow().value, other))
+        }) && others.next().is_none()
+    }
+
+    // Lexicographic like slices: the first pair of values that isn't Equal
+    // decides, otherwise the shorter list is less.
+    fn compare_by(
+        &self,
+        other: &LinkedList<T>,
+        mut compare: impl FnMut(&T, &T) -> Option<Ordering>,
+    ) -> Option<Ordering> {
+        let mut others = other.iter().flatten();
+        for node in self.iter().flatten() {
+            let Some(other) = others.next() else {
+                return Some(Ordering::Greater);
+            };
+            let ordering = compare(&node.borrow().value, &other.borrow().value);
+            if ordering != Some(Ordering::Equal) {
+                return ordering;
+            }
+        }
+        Some(self.len.cmp(&other.len))
+    }
+
+    // The nth value, pushing values made by `f` at the back first if the list is
+    // shorter than n + 1. Like `get_mut`, changes through the guard aren't seen
+    // by the content hash.
+    pub fn get_or_insert_with(&mut self, n: usize, mut f: impl FnMut() -> T) -> ValueRefMut<'_, T> {
+        if n < self.len {
+            return self.at_mut(n);
+        }
+        for _ in self.len..=n {
+            self.push_back(f());
+        }
+        ValueRefMut::new(self.tail_node().unwrap())
+    }
+
+    pub fn get_or_insert_default(&mut self, n: usize) -> ValueRefMut<'_, T>
+    where
+        T: Default,
+    {
+        self.get_or_insert_with(n, T::default)
+    }
+
+    pub fn step_by(&self, step: usize) -> LinkedListStepIter<T> {
+        assert!(step > 0, "step must be non-zero");
+        LinkedListStepIter {
+            current: self.head.clone(),
+            step,
+        }
+    }
+
+    pub fn tuples<const N: usize>(&self) -> LinkedListTuples<T, N> {
+        assert!(N > 0, "group size must be non-zero");
+        LinkedListTuples {
+            nodes: self.iter(),
+            remainder: Vec::new(),
+        }
+    }
+
+    // Keeps the nodes at 0, n, 2n, ... and drops the rest, in one pass.
+    pub fn every_nth(mut self, n: usize) -> LinkedList<T> {
+        assert!(n > 0, "n must be non-zero");
+        let mut picked = LinkedList::new();
+        let mut current = self.head.take();
+        self.tail = None;
+        self.len = 0;
+        while let Some(node) = current {
+            current = node.borrow_mut().next.take();
+            // the skipped nodes are unlinked and dropped one at a time
+            for _ in 1..n {
+                let Some(skipped) = current else { break };
+                current = skipped.borrow_mut().next.take();
+            }
+            picked.push_back_node(node);
+        }
+        picked
+    }
+
+    fn push_back_node(&mut self, node: NodeHandle<T>) {
+        node.borrow_mut().next = None;
+        match self.tail_node() {
+            None => self.head = Some(node.clone()),
+            Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+        }
+        self.set_tail(Some(&node));
+        self.len += 1;
+    }
+
+    fn pop_front_node(&mut self) -> Link<T> {
+        let node = self.head.take()?;
+        self.head = node.borrow_mut().next.take();
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        self.len -= 1;
+        Some(node)
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+// Consuming operations move the nodes into other lists and drop this one.
+// The chain is taken apart front to back, so a long list doesn't recurse
+// through every `next` on the way down. A node still held elsewhere keeps
+// the rest of the chain it links to.
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.invalidate_cursors();
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_inner().next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<T: Ord> LinkedList<T> {
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+
+    // Keeps a sorted list sorted: the value goes behind the ones equal to it.
+    // Returns the index it was inserted at.
+    pub fn insert_sorted(&mut self, value: T) -> usize {
+        let index = self.position(|other| *other > value).unwrap_or(self.len);
+        self.insert(index, value).unwrap();
+        index
+    }
+
+    // Splices the sorted other into this sorted list in O(n + m) by relinking,
+    // on ties the elements of self go first.
+    pub fn merge(&mut self, other: LinkedList<T>) {
+        let mut first = LinkedList::new();
+        first.head = self.head.take();
+        first.tail = self.tail.take();
+        first.len = mem::take(&mut self.len);
+        let mut merged = first.merge_by(other, &mut T::cmp);
+        self.head = merged.head.take();
+        self.tail = merged.tail.take();
+        self.len = mem::take(&mut merged.len);
+        self.rehash();
+        self.invalidate_cursors();
+    }
+
+    fn front_cmp(&self, other: &LinkedList<T>) -> Option<Ordering> {
+        match (&self.head, &other.head) {
+            (None, None) => None,
+            (Some(_), None) => Some(Ordering::Less),
+            (None, Some(_)) => Some(Ordering::Greater),
+            (Some(a), Some(b)) => Some(a.borrow().value.cmp(&b.borrow().value)),
+        }
+    }
+
+    // Both lists must be sorted; nodes are relinked into the result, equal
+    // elements are taken from self and the duplicates from other dropped.
+    pub fn union_sorted(mut self, mut other: LinkedList<T>) -> LinkedList<T> {
+        let mut result = LinkedList::new();
+        while let Some(ord) = self.front_cmp(&other) {
+            let node = match ord {
+                Ordering::Less => self.pop_front_node(),
+                Ordering::Greater => other.pop_front_node(),
+                Ordering::Equal => {
+                    other.pop_front_node();
+                    self.pop_front_node()
+                }
+            };
+            result.push_back_node(node.unwrap());
+        }
+        result
+    }
+
+    pub fn intersection_sorted(mut self, mut other: LinkedList<T>) -> LinkedList<T> {
+        let mut result = LinkedList::new();
+        while let Some(ord) = self.front_cmp(&other) {
+            match ord {
+                Ordering::Less => {
+                    self.pop_front_node();
+                }
+                Ordering::Greater => {
+                    other.pop_front_node();
+                }
+                Ordering::Equal => {
+                    other.pop_front_node();
+                    result.push_back_node(self.pop_front_node().unwrap());
+                }
+            }
+        }
+        result
+    }
+
+    pub fn difference_sorted(mut self, mut other: LinkedList<T>) -> LinkedList<T> {
+        let mut result = LinkedList::new();
+        while let Some(ord) = self.front_cmp(&other) {
+            match ord {
+                Ordering::Less => result.push_back_node(self.pop_front_node().unwrap()),
+                Ordering::Greater => {
+                    other.pop_front_node();
+                }
+                Ordering::Equal => {
+                    other.pop_front_node();
+                    self.pop_front_node();
+                }
+            }
+        }
+        result
+    }
+}
+
+// Undo log of `transaction`: the nodes in their old order with a copy of their
+// old values, put back when dropped before the transaction commits.
+struct Rollback<'a, T: Clone> {
+    list: &'a mut LinkedList<T>,
+    nodes: Vec<(NodeHandle<T>, T, bool)>,
+    content_hash: Option<ContentHash<T>>,
+    committed: bool,
+}
+
+impl<T: Clone> Drop for Rollback<'_, T> {
+    fn drop(&mut self) {
+        if self.committed {
+            return;
+        }
+        // nodes `f` moved into another list stay there, copies take their place;
+        // a node only the log still holds is free to link in again
+        let mut linked: Vec<_> = self
+            .list
+            .iter()
+            .flatten()
+            .map(|node| Rc::as_ptr(&node))
+            .collect();
+        linked.sort_unstable();
+        let mut current = self.list.head.take();
+        while let Some(node) = current {
+            current = node.borrow_mut().next.take();
+        }
+        let mut tail: Link<T> = None;
+        self.list.len = self.nodes.len();
+        for (node, value, removed) in mem::take(&mut self.nodes) {
+            let free = Rc::strong_count(&node) == 1;
+            let node = match linked.binary_search(&Rc::as_ptr(&node)) {
+                Err(_) if !free => Node::new(value, None),
+                _ => {
+                    node.borrow_mut().value = value;
+                    node
+                }
+            };
+            node.borrow_mut().removed = removed;
+            match &tail {
+                None => self.list.head = Some(node.clone()),
+                Some(tail) => tail.borrow_mut().next = Some(node.clone()),
+            }
+            tail = Some(node);
+        }
+        self.list.set_tail(tail.as_ref());
+        self.list.content_hash.set(self.content_hash);
+        self.list.invalidate_cursors();
+    }
+}
+
+impl<T: Clone> LinkedList<T> {
+    // Runs `f` on the list and keeps its changes only if `f` returns Ok. On Err,
+    // or a panic inside `f`, the list is put back the way it was from an undo
+    // log holding a copy of every value. The nodes are put back too, so handles
+    // taken before stay valid either way, unless `f` moved their node into
+    // another list: the list then gets a copy. Cursors stay valid through a
+    // commit, a rollback invalidates them.
+    pub fn transaction<R, E>(
+        &mut self,
+        f: impl FnOnce(&mut LinkedList<T>) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let nodes = self
+            .iter()
+            .flatten()
+            .map(|node| {
+                let (value, removed) = (node.borrow().value.clone(), node.borrow().removed);
+                (node, value, removed)
+            })
+            .collect();
+        let mut undo = Rollback {
+            content_hash: self.content_hash.get(),
+            list: self,
+            nodes,
+            committed: false,
+        };
+        let result = f(undo.list);
+        undo.committed = result.is_ok();
+        result
+    }
+
+    // Unlinks the head and returns its value, which is moved out if the list
+    // held the only handle to the node and cloned otherwise.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let node = self.pop_front_node()?;
+        self.hash_popped_front(&node.borrow().value);
+        self.cursors_removed(0, &self.head);
+        Some(into_value(node))
+    }
+
+    // Empties the list front to back while the values are yielded.
+    pub fn drain(&mut self) -> LinkedListDrain<'_, T> {
+        LinkedListDrain { list: self }
+    }
+
+    // The lazy counterpart of `retain` with the test inverted: matching values
+    // are unlinked and yielded, the rest stays linked in order.
+    pub fn extract_if<F>(&mut self, pred: F) -> LinkedListExtractIf<'_, T, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        LinkedListExtractIf {
+            current: self.head.clone(),
+            list: self,
+            pred,
+            prev: None,
+            index: 0,
+            prefix: HashRun::EMPTY,
+        }
+    }
+
+    // O(n): the node has no back link, so its predecessor is found from the
+    // head. None if the node is gone or not in this list.
+    pub fn remove(&mut self, node: &NodeRef<T>) -> Option<T> {
+        let target = node.0.upgrade()?;
+        let index = self.position_of(&target)?;
+        drop(target);
+        self.remove_nth(index).ok()
+    }
+
+    // Like `Vec::remove`, the value is moved out unless the node is still
+    // referenced elsewhere.
+    pub fn remove_nth(&mut self, n: usize) -> Result<T, ListError> {
+        if n >= self.len {
+            return Err(self.out_of_bounds(n));
+        }
+        if n == 0 {
+            return Ok(self.pop_front().unwrap());
+        }
+        let prefix = self.hash_run(self.iter().flatten().take(n));
+        let prev = self.iter().nth(n - 1).unwrap().unwrap();
+        let node = prev.borrow_mut().next.take().unwrap();
+        let next = node.borrow_mut().next.take();
+        if next.is_none() {
+            self.set_tail(Some(&prev));
+        }
+        prev.borrow_mut().next = next.clone();
+        let removed = self.hash_one(&node.borrow().value);
+        self.hash_replaced(prefix, removed, HashRun::EMPTY, self.len - n - 1);
+        self.len -= 1;
+        self.cursors_removed(n, &next);
+        Ok(into_value(node))
+    }
+
+    // O(n): nodes only link forward, so the new tail is found from the head.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail_node()?;
+        let mut index = 0;
+        let mut prev: Link<T> = None;
+        for node in self.iter().flatten() {
+            if Rc::ptr_eq(&node, &tail) {
+                break;
+            }
+            prev = Some(node);
+            index += 1;
+        }
+        match &prev {
+            None => self.head = None,
+            Some(prev) => prev.borrow_mut().next = None,
+        }
+        self.set_tail(prev.as_ref());
+        self.len -= 1;
+        self.hash_popped_back(&tail.borrow().value);
+        self.cursors_removed(index, &None);
+        Some(into_value(tail))
+    }
+
+    pub fn values(&self) -> LinkedListValues<T> {
+        LinkedListValues { nodes: self.iter() }
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len);
+        values.extend(self.values());
+        values
+    }
+
+    pub fn repeated(&self, n: usize) -> LinkedList<T> {
+        self.cycle_take(n * self.len)
+    }
+
+    // First k elements of the endless repetition of the list.
+    pub fn cycle_take(&self, k: usize) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        if self.head.is_none() {
+            return list;
+        }
+        for node in core::iter::repeat_with(|| self.iter().flatten())
+            .flatten()
+            .take(k)
+        {
+            list.push_back(node.borrow().value.clone());
+        }
+        list
+    }
+}
+
+impl<T: ?Sized> LinkedList<Box<T>> {
+    pub fn push_boxed(&mut self, value: Box<T>) {
+        self.push_back(value);
+    }
+
+    // Yields the trait objects themselves rather than their boxes.
+    pub fn iter_dyn(&self) -> LinkedListDynIter<'_, T> {
+        LinkedListDynIter {
+            nodes: self.iter(),
+            _list: PhantomData,
+        }
+    }
+
+    // Mutation goes through a closure so the content hash can be redone after.
+    pub fn for_each_dyn_mut(&self, mut f: impl FnMut(&mut T)) {
+        for node in self.iter().flatten() {
+            f(&mut node.borrow_mut().value);
+        }
+        self.rehash();
+    }
+}
+
+impl LinkedList<Box<dyn Any>> {
+    // Unlinks the first element of concrete type U and returns it downcast.
+    pub fn remove_downcast<U: Any>(&mut self) -> Option<Box<U>> {
+        let mut prev: Link<Box<dyn Any>> = None;
+        let mut current = self.head.clone();
+        let mut index = 0;
+        let mut prefix = HashRun::EMPTY;
+        while let Some(node) = current {
+            if node.borrow().value.is::<U>() {
+                let removed = self.hash_one(&node.borrow().value);
+                self.hash_replaced(prefix, removed, HashRun::EMPTY, self.len - index - 1);
+                let next = node.borrow_mut().next.take();
+                match &prev {
+                    None => self.head = next.clone(),
+                    Some(prev) => prev.borrow_mut().next = next.clone(),
+                }
+                if next.is_none() {
+                    self.set_tail(prev.as_ref());
+                }
+                self.len -= 1;
+                self.cursors_removed(index, &next);
+                let value = mem::replace(&mut node.borrow_mut().value, Box::new(()));
+                return value.downcast().ok();
+            }
+            self.hash_extend(&mut prefix, &node.borrow().value);
+            current = node.borrow().next.clone();
+            prev = Some(node);
+            index += 1;
+        }
+        None
+    }
+}
+
+impl<T: Display> LinkedList<T> {
+    pub fn join(&self, sep: &str) -> String {
+        self.format_with(sep).to_string()
+    }
+
+    // The values separated by sep, without brackets and ignoring the display limit.
+    pub fn format_with<'a>(&'a self, sep: &'a str) -> DisplayJoined<'a, T> {
+        DisplayJoined { list: self, sep }
+    }
+}
+
+// Element-wise like slices, the display limit and content hash mode don't take part.
+impl<T: PartialEq<U>, U> PartialEq<LinkedList<U>> for LinkedList<T> {
+    fn eq(&self, other: &LinkedList<U>) -> bool {
+        self.len == other.len
+            && self.eq_by_iter(other.iter().flatten(), |value, other| {
+                *value == other.borrow().value
+            })
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+// Hashes like a slice of the values, so it agrees with `Eq`.
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for node in self.iter().flatten() {
+            node.borrow().value.hash(state);
+        }
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.compare_by(other, |a, b| a.partial_cmp(b))
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare_by(other, |a, b| Some(a.cmp(b))).unwrap()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for LinkedList<T> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.eq_by_iter(other, |value, other| value == other)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<&[U]> for LinkedList<T> {
+    fn eq(&self, other: &&[U]) -> bool {
+        *self == **other
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for LinkedList<T> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<Vec<U>> for LinkedList<T> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(values: [T; N]) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T: Clone> From<&[T]> for LinkedList<T> {
+    fn from(values: &[T]) -> Self {
+        values.iter().cloned().collect()
+    }
+}
+
+// Values are moved out, or cloned from nodes that are still shared.
+impl<T: Clone> From<LinkedList<T>> for Vec<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T: Clone> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = LinkedListIntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        LinkedListIntoIter { list: self }
+    }
+}
+
+impl<T: Clone> IntoIterator for &LinkedList<T> {
+    type Item = T;
+    type IntoIter = LinkedListValues<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.values()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        let mut list = LinkedList::new();
+        for node in self.iter().flatten() {
+            list.push_back(node.borrow().value.clone());
+        }
+        list.content_hash.set(self.content_hash.get());
+        list.display_limit = self.display_limit;
+        list
+    }
+
+    // Overwrites the values of the existing nodes and only allocates or drops
+    // the nodes making up the difference in length.
+    fn clone_from(&mut self, source: &Self) {
+        self.content_hash.set(None);
+        self.invalidate_cursors();
+        let mut source_nodes = source.iter().flatten();
+        let mut current = self.head.clone();
+        let mut last: Link<T> = None;
+        while let Some(node) = current {
+            match source_nodes.next() {
+                None => {
+                    // the surplus is cut off as a list of its own, so its
+                    // iterative Drop frees it instead of a recursive one
+                    let mut surplus = LinkedList::new();
+                    surplus.head = Some(node);
+                    match last {
+                        None => self.head = None,
+                        Some(ref last) => last.borrow_mut().next = None,
+                    }
+                    self.set_tail(last.as_ref());
+                    self.len = source.len;
+                    drop(surplus);
+                    break;
+                }
+                Some(source_node) => {
+                    node.borrow_mut()
+                        .value
+                        .clone_from(&source_node.borrow().value);
+                    current = node.borrow().next.clone();
+                    last = Some(node);
+                }
+            }
+        }
+        for source_node in source_nodes {
+            self.push_back(source_node.borrow().value.clone());
+        }
+        self.content_hash.set(source.content_hash.get());
+        self.display_limit = source.display_limit;
+    }
+}
+
+impl<T> LinkedList<T> {
+    fn fmt_with<F>(&self, f: &mut Formatter<'_>, fmt_value: F) -> core::fmt::Result
+    where
+        F: Fn(&T, &mut Formatter<'_>) -> core::fmt::Result,
+    {
+        self.fmt_limited(f, fmt_value, self.display_limit)
+    }
+
+    // Elements past `limit` are only counted, as in `[1, 2, … (+98 more)]`.
+    // The alternate form `{:#}` prints one element per line without brackets.
+    fn fmt_limited<F>(&self, f: &mut Formatter<'_>, fmt_value: F, limit: usize) -> core::fmt::Result
+    where
+        F: Fn(&T, &mut Formatter<'_>) -> core::fmt::Result,
+    {
+        // iteration stops after len elements anyway, a cycle would print as a
+        // plausible looking list
+        debug_assert!(!self.is_cyclic(), "formatting a cyclic list");
+        let (open, sep, close) = if f.alternate() {
+            ("", "\n", "")
+        } else {
+            ("[", ", ", "]")
+        };
+        f.write_str(open)?;
+        let mut nodes = self.iter().flatten();
+        for (i, node) in nodes.by_ref().take(limit).enumerate() {
+            if i > 0 {
+                f.write_str(sep)?;
+            }
+            fmt_value(&node.borrow().value, f)?;
+        }
+        let more = nodes.count();
+        if more > 0 {
+            if limit > 0 {
+                f.write_str(sep)?;
+            }
+            write!(f, "… (+{more} more)")?;
+        }
+        f.write_str(close)
+    }
+
+    pub fn display_limit(&self) -> usize {
+        self.display_limit
+    }
+
+    // Caps Display and Debug output, `display_full` still prints everything.
+    pub fn set_display_limit(&mut self, limit: usize) {
+        self.display_limit = limit;
+    }
+
+    pub fn display_full(&self) -> DisplayFull<'_, T> {
+        DisplayFull { list: self }
+    }
+
+    // Renders the list like Display does, but each element through fmt_value.
+    pub fn display_with<F>(&self, fmt_value: F) -> DisplayWith<'_, T, F>
+    where
+        F: Fn(&T, &mut Formatter<'_>) -> core::fmt::Result,
+    {
+        DisplayWith {
+            list: self,
+            fmt_value,
+        }
+    }
+
+    pub fn to_string_with<F>(&self, fmt_value: F) -> String
+    where
+        F: Fn(&T, &mut Formatter<'_>) -> core::fmt::Result,
+    {
+        self.display_with(fmt_value).to_string()
+    }
+}
+
+// Handle to a node returned by the pushes and `insert`. It doesn't keep the
+// node alive: once the node is removed the handle is stale and the methods
+// taking it return None. Only `value` is O(1). `insert_after` is O(1) on the
+// tail and O(n) anywhere else, `remove` is always O(n): a singly linked node
+// can't tell which list it's in or reach its predecessor, so both walk from
+// the head. `DoublyLinkedList` has back links and unlinks in O(1).
+pub struct NodeRef<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> NodeRef<T> {
+    fn new(node: &NodeHandle<T>) -> NodeRef<T> {
+        NodeRef(Rc::downgrade(node))
+    }
+}
+
+// For nodes reached through `get_nth` or the iterators.
+impl<T> From<&NodeHandle<T>> for NodeRef<T> {
+    fn from(node: &NodeHandle<T>) -> Self {
+        NodeRef::new(node)
+    }
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        NodeRef(self.0.clone())
+    }
+}
+
+// Opaque, like the Weak inside.
+impl<T> Debug for NodeRef<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str("NodeRef")
+    }
+}
+
+// Borrow guard of one value. It holds its node too, so it stays valid if the
+// node is unlinked meanwhile; the value is shared as with any RefCell borrow.
+// N is the element type of the node, which differs from T for the trait
+// objects `iter_dyn` borrows out of their boxes.
+pub struct ValueRef<'a, T: ?Sized, N = T> {
+    // declared first so it's dropped before the handle it borrows from
+    value: Ref<'a, T>,
+    _node: NodeHandle<N>,
+}
+
+impl<'a, T> ValueRef<'a, T> {
+    fn new(node: NodeHandle<T>) -> ValueRef<'a, T> {
+        // the RefCell stays where it is for as long as the guard keeps the handle
+        let value = unsafe { &*Rc::as_ptr(&node) }.borrow();
+        ValueRef {
+            value: Ref::map(value, |node| &node.value),
+            _node: node,
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ValueRef<'a, T, Box<T>> {
+    fn unboxed(node: NodeHandle<Box<T>>) -> ValueRef<'a, T, Box<T>> {
+        let value = unsafe { &*Rc::as_ptr(&node) }.borrow();
+        ValueRef {
+            value: Ref::map(value, |node| &*node.value),
+            _node: node,
+        }
+    }
+}
+
+impl<T: ?Sized, N> Deref for ValueRef<'_, T, N> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct ValueRefMut<'a, T> {
+    value: RefMut<'a, T>,
+    _node: NodeHandle<T>,
+}
+
+impl<'a, T> ValueRefMut<'a, T> {
+    fn new(node: NodeHandle<T>) -> ValueRefMut<'a, T> {
+        let value = unsafe { &*Rc::as_ptr(&node) }.borrow_mut();
+        ValueRefMut {
+            value: RefMut::map(value, |node| &mut node.value),
+            _node: node,
+        }
+    }
+}
+
+impl<T> Deref for ValueRefMut<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for ValueRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+pub struct DisplayWith<'a, T, F> {
+    list: &'a LinkedList<T>,
+    fmt_value: F,
+}
+
+impl<T, F> Display for DisplayWith<'_, T, F>
+where
+    F: Fn(&T, &mut Formatter<'_>) -> core::fmt::Result,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt_with(f, &self.fmt_value)
+    }
+}
+
+pub struct DisplayFull<'a, T> {
+    list: &'a LinkedList<T>,
+}
+
+impl<T: Display> Display for DisplayFull<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.list.fmt_limited(f, Display::fmt, usize::MAX)
+    }
+}
+
+pub struct DisplayJoined<'a, T> {
+    list: &'a LinkedList<T>,
+    sep: &'a str,
+}
+
+impl<T: Display> Display for DisplayJoined<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, node) in self.list.iter().flatten().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            Display::fmt(&node.borrow().value, f)?;
+        }
+        Ok(())
+    }
+}
+
+// Values are printed with their Display impl, `{:?}` goes through Debug.
+impl<T: Display> Display for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.fmt_with(f, Display::fmt)
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // same as for Display, see `fmt_limited`
+        debug_assert!(!self.is_cyclic(), "formatting a cyclic list");
+        let mut nodes = self.iter().flatten();
+        let mut list = f.debug_list();
+        for node in nodes.by_ref().take(self.display_limit) {
+            list.entry(&node.borrow().value);
+        }
+        let more = nodes.count();
+        if more > 0 {
+            list.entry(&format_args!("… (+{more} more)"));
+        }
+        list.finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format> defmt::Format for LinkedList<T> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        for (i, node) in self.iter().flatten().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", &node.borrow().value);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_back() {
+        let mut list = LinkedList::<i32>::new();
+
+        list.push_back(1);
+        assert_eq!(
+            list.head.clone().unwrap().borrow().value,
+            list.tail_node().unwrap().borrow().value
+        );
+
+        list.push_back(2);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 2);
+
+        list.push_back(3);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 3);
+
+        assert_eq!(list, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_push_front() {
+        let mut list = LinkedList::<i32>::new();
+
+        list.push_front(3);
+        assert_eq!(
+            list.head.clone().unwrap().borrow().value,
+            list.tail_node().unwrap().borrow().value
+        );
+
+        list.push_front(2);
+        assert_eq!(list.head.clone().unwrap().borrow().value, 2);
+
+        list.push_front(1);
+        assert_eq!(list.head.clone().unwrap().borrow().value, 1);
+
+        assert_eq!(list, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_push_after_n() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        list.push_after_n(0, 77).unwrap();
+        list.push_after_n(2, 78).unwrap();
+
+        assert_eq!(list.get_nth(1).unwrap().unwrap().borrow().value, 77);
+        assert_eq!(list.get_nth(3).unwrap().unwrap().borrow().value, 78);
+
+        list.push_after_n(4, 79).unwrap();
+        assert_eq!(list.tail_node().unwrap().borrow().value, 79);
+    }
+
+    #[test]
+    fn test_get_nth() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let targets = [1, 2, 3];
+        for (i, value) in targets.into_iter().enumerate() {
+            assert_eq!(list.get_nth(i).unwrap().unwrap().borrow().value, value)
+        }
+        match list.get_nth(3) {
+            Err(ListError::IndexOutOfBounds { index, len }) => assert_eq!((index, len), (3, 3)),
+            _ => panic!("expected an out of bounds error"),
+        }
+        assert_eq!(
+            list.update_nth(5, 0).unwrap_err().to_string(),
+            "index 5 out of bounds for list of length 3"
+        );
+        assert!(list.push_after_n(3, 0).is_err());
+        let err: Box<dyn core::error::Error> = Box::new(ListError::EmptyList);
+        assert_eq!(err.to_string(), "list is empty");
+    }
+
+    #[test]
+    fn test_update_nth() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        list.update_nth(0, 4).unwrap();
+        list.update_nth(1, 5).unwrap();
+        list.update_nth(2, 6).unwrap();
+
+        assert_eq!(list, [4, 5, 6]);
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        list.push_back(4);
+        list.push_back(5);
+
+        let sec = list.split_off(3).unwrap();
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(sec, [4, 5]);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 3);
+        assert_eq!(sec.tail_node().as_ref().unwrap().borrow().value, 5);
+        list.push_back(6);
+        assert_eq!(list, [1, 2, 3, 6]);
+        assert_eq!(sec, [4, 5]);
+
+        let empty = list.split_off(4).unwrap();
+        assert!(empty.is_empty() && empty.tail.is_none());
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 6);
+        assert_eq!(
+            list.split_off(5).unwrap_err(),
+            ListError::IndexOutOfBounds { index: 5, len: 4 }
+        );
+
+        let all = list.split_off(0).unwrap();
+        assert_eq!(all, [1, 2, 3, 6]);
+        assert_eq!(all.len(), 4);
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+    }
+
+    #[test]
+    fn iter() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(list, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_union_sorted() {
+        let mut first = LinkedList::<i32>::new();
+        let mut sec = LinkedList::<i32>::new();
+        for value in [1, 3, 5, 7] {
+            first.push_back(value);
+        }
+        for value in [2, 3, 4, 8] {
+            sec.push_back(value);
+        }
+
+        let union = first.union_sorted(sec);
+
+        assert_eq!(union, [1, 2, 3, 4, 5, 7, 8]);
+        assert_eq!(union.tail_node().unwrap().borrow().value, 8);
+    }
+
+    #[test]
+    fn test_intersection_sorted() {
+        let mut first = LinkedList::<i32>::new();
+        let mut sec = LinkedList::<i32>::new();
+        for value in [1, 2, 3, 5, 8] {
+            first.push_back(value);
+        }
+        for value in [2, 3, 4, 8, 9] {
+            sec.push_back(value);
+        }
+
+        let intersection = first.intersection_sorted(sec);
+
+        assert_eq!(intersection, [2, 3, 8]);
+        assert_eq!(intersection.tail_node().unwrap().borrow().value, 8);
+
+        let empty = LinkedList::<i32>::new().intersection_sorted(LinkedList::new());
+        assert!(empty.head.is_none());
+        assert!(empty.tail.is_none());
+    }
+
+    #[test]
+    fn test_difference_sorted() {
+        let mut first = LinkedList::<i32>::new();
+        let mut sec = LinkedList::<i32>::new();
+        for value in [1, 2, 3, 5, 8] {
+            first.push_back(value);
+        }
+        for value in [0, 2, 3, 4, 8] {
+            sec.push_back(value);
+        }
+
+        let difference = first.difference_sorted(sec);
+
+        assert_eq!(difference, [1, 5]);
+        assert_eq!(difference.tail_node().unwrap().borrow().value, 5);
+    }
+
+    #[test]
+    fn test_rotate_to() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        list.push_back(4);
+
+        list.rotate_to(0).unwrap();
+        list.rotate_to(1).unwrap();
+        list.rotate_to(2).unwrap();
+
+        assert_eq!(list, [4, 1, 2, 3]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 3);
+
+        assert_eq!(
+            list.rotate_to(4),
+            Err(ListError::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        assert_eq!(
+            LinkedList::<i32>::new().rotate_to(0),
+            Err(ListError::EmptyList)
+        );
+        assert_eq!(list, [4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let copy = list.clone();
+        list.update_nth(0, 10).unwrap();
+
+        assert_eq!(copy, [1, 2, 3]);
+        assert_eq!(copy.tail_node().unwrap().borrow().value, 3);
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let mut source = LinkedList::<i32>::new();
+        source.push_back(1);
+        source.push_back(2);
+        source.push_back(3);
+
+        let mut shorter = LinkedList::<i32>::new();
+        shorter.push_back(7);
+        let reused = shorter.head.clone().unwrap();
+        shorter.clone_from(&source);
+        assert!(Rc::ptr_eq(&reused, &shorter.head.clone().unwrap()));
+        assert_eq!(shorter.tail_node().unwrap().borrow().value, 3);
+
+        let mut longer = LinkedList::<i32>::new();
+        for value in [7, 8, 9, 10, 11] {
+            longer.push_back(value);
+        }
+        longer.clone_from(&source);
+        assert_eq!(longer.tail_node().unwrap().borrow().value, 3);
+
+        assert_eq!(shorter, [1, 2, 3]);
+        assert_eq!(longer, [1, 2, 3]);
+
+        longer.clone_from(&LinkedList::new());
+        assert!(longer.head.is_none());
+        assert!(longer.tail.is_none());
+
+        // the cut off surplus is freed without recursing through it
+        let mut long: LinkedList<i32> = (0..1_000_000).collect();
+        long.clone_from(&source);
+        assert_eq!(long, [1, 2, 3]);
+        long = (0..1_000_000).collect();
+        long.clone_from(&LinkedList::new());
+        assert!(long.is_empty());
+    }
+
+    #[test]
+    fn test_swap_nodes() {
+        let mut list = LinkedList::<i32>::new();
+        let first = list.push_back(1);
+        let sec = list.push_back(2);
+        let third = list.push_back(3);
+        list.push_back(4);
+        let last = list.push_back(5);
+
+        // head and tail
+        list.swap_nodes(&first, &last).unwrap();
+        // adjacent nodes, given in reverse order
+        list.swap_nodes(&third, &sec).unwrap();
+        list.swap_nodes(&sec, &sec).unwrap();
+
+        assert_eq!(list, [5, 3, 2, 4, 1]);
+        assert!(Rc::ptr_eq(
+            &list.head.clone().unwrap(),
+            &last.0.upgrade().unwrap()
+        ));
+        assert!(Rc::ptr_eq(
+            &list.tail_node().unwrap(),
+            &first.0.upgrade().unwrap()
+        ));
+
+        let foreign = Node::new(6, None);
+        assert_eq!(
+            list.swap_nodes(&first, &NodeRef::from(&foreign)),
+            Err(ListError::StaleHandle)
+        );
+        let popped = list.push_front(7);
+        list.pop_front();
+        assert_eq!(
+            list.swap_nodes(&popped, &first),
+            Err(ListError::StaleHandle)
+        );
+        assert_eq!(list, [5, 3, 2, 4, 1]);
+    }
+
+    #[test]
+    fn test_repeated() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        let repeated = list.repeated(3);
+
+        assert_eq!(repeated, [1, 2, 1, 2, 1, 2]);
+        assert!(list.repeated(0).head.is_none());
+        assert!(LinkedList::<i32>::new().repeated(3).head.is_none());
+    }
+
+    #[test]
+    fn test_cycle_take() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        let cycled = list.cycle_take(7);
+
+        assert_eq!(cycled, [1, 2, 3, 1, 2, 3, 1]);
+        assert_eq!(cycled.tail_node().unwrap().borrow().value, 1);
+        assert!(LinkedList::<i32>::new().cycle_take(5).head.is_none());
+    }
+
+    #[test]
+    fn test_join() {
+        let mut list = LinkedList::<&str>::new();
+        assert_eq!(list.join(", "), "");
+
+        list.push_back("a");
+        assert_eq!(list.join(", "), "a");
+
+        list.push_back("b");
+        list.push_back("c");
+        assert_eq!(list.join(", "), "a, b, c");
+        assert_eq!(list.join(""), "abc");
+    }
+
+    #[test]
+    fn test_to_string_with() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.to_string_with(|value, f| write!(f, "{value:x}")), "[]");
+
+        list.push_back(10);
+        list.push_back(255);
+        assert_eq!(
+            list.to_string_with(|value, f| write!(f, "{value:#x}")),
+            "[0xa, 0xff]"
+        );
+        assert_eq!(
+            list.display_with(|_, f| write!(f, "***")).to_string(),
+            "[***, ***]"
+        );
+        assert_eq!(list.to_string(), "[10, 255]");
+    }
+
+    #[test]
+    fn test_mark_remove_and_sweep() {
+        let mut list = LinkedList::<i32>::new();
+        for value in [1, 2, 3, 4, 5] {
+            list.push_back(value);
+        }
+
+        for node in list.iter().flatten() {
+            if node.borrow().value % 2 == 1 {
+                list.mark_remove(&NodeRef::from(&node)).unwrap();
+            }
+        }
+        assert_eq!(list.iter().count(), 5);
+        assert_eq!(list.sweep(), 3);
+        assert_eq!(list.sweep(), 0);
+
+        assert_eq!(list, [2, 4]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 4);
+
+        let swept = list.push_back(6);
+        let mut other = LinkedList::new();
+        let foreign = other.push_back(7);
+        list.mark_remove(&swept).unwrap();
+        list.sweep();
+        assert_eq!(list.mark_remove(&swept), Err(ListError::StaleHandle));
+        assert_eq!(list.mark_remove(&foreign), Err(ListError::StaleHandle));
+        assert_eq!(other, [7]);
+
+        for node in list.iter().flatten() {
+            list.mark_remove(&NodeRef::from(&node)).unwrap();
+        }
+        assert_eq!(list.sweep(), 2);
+        assert!(list.head.is_none());
+        assert!(list.tail.is_none());
+    }
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn scale(&mut self, factor: f64);
+    }
+
+    struct Square(f64);
+
+    impl Shape for Square {
+        fn area(&self) -> f64 {
+            self.0 * self.0
+        }
+        fn scale(&mut self, factor: f64) {
+            self.0 *= factor
+        }
+    }
+
+    struct Circle(f64);
+
+    impl Shape for Circle {
+        fn area(&self) -> f64 {
+            3.0 * self.0 * self.0
+        }
+        fn scale(&mut self, factor: f64) {
+            self.0 *= factor
+        }
+    }
+
+    #[test]
+    fn test_for_each_dyn() {
+        let mut list = LinkedList::<Box<dyn Shape>>::new();
+        list.push_boxed(Box::new(Square(2.0)));
+        list.push_boxed(Box::new(Circle(1.0)));
+
+        list.for_each_dyn_mut(|shape| shape.scale(2.0));
+
+        let areas: Vec<f64> = list.iter_dyn().map(|shape| shape.area()).collect();
+        assert_eq!(areas, [16.0, 12.0]);
+        assert_eq!(
+            list.iter_dyn().next_back().map(|shape| shape.area()),
+            Some(12.0)
+        );
+    }
+
+    #[test]
+    fn test_remove_downcast() {
+        let mut list = LinkedList::<Box<dyn Any>>::new();
+        list.push_boxed(Box::new(1_u8));
+        list.push_boxed(Box::new("two"));
+        list.push_boxed(Box::new(3_u8));
+        list.push_boxed(Box::new(4_i64));
+
+        assert_eq!(list.remove_downcast::<u8>(), Some(Box::new(1)));
+        assert_eq!(list.remove_downcast::<i64>(), Some(Box::new(4)));
+        assert_eq!(list.remove_downcast::<String>(), None);
+        assert_eq!(list.iter().count(), 2);
+        assert!(list.tail_node().unwrap().borrow().value.is::<u8>());
+
+        assert_eq!(list.remove_downcast::<&str>(), Some(Box::new("two")));
+        assert_eq!(list.remove_downcast::<u8>(), Some(Box::new(3)));
+        assert!(list.head.is_none());
+        assert!(list.tail.is_none());
+    }
+
+    #[test]
+    fn test_split_into() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 1..=8 {
+            list.push_back(value);
+        }
+
+        let chunks = list.split_into(3);
+
+        let targets: [&[i32]; 3] = [&[1, 2, 3], &[4, 5, 6], &[7, 8]];
+        assert_eq!(chunks.len(), targets.len());
+        for (chunk, target) in chunks.iter().zip(targets) {
+            assert_eq!(*chunk, target);
+            assert_eq!(
+                chunk.tail_node().unwrap().borrow().value,
+                *target.last().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_into_more_parts_than_elements() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        let chunks = list.split_into(4);
+
+        assert_eq!(chunks.len(), 4);
+        let lens: Vec<usize> = chunks.iter().map(|chunk| chunk.iter().count()).collect();
+        assert_eq!(lens, [1, 1, 0, 0]);
+        assert!(chunks[2].tail.is_none());
+        assert_eq!(LinkedList::<i32>::new().split_into(2).len(), 2);
+    }
+
+    #[test]
+    fn test_distribute() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 1..=7 {
+            list.push_back(value);
+        }
+
+        let hands = list.distribute(3);
+
+        let targets: [&[i32]; 3] = [&[1, 4, 7], &[2, 5], &[3, 6]];
+        assert_eq!(hands.len(), targets.len());
+        for (hand, target) in hands.iter().zip(targets) {
+            assert_eq!(*hand, target);
+            assert_eq!(
+                hand.tail_node().unwrap().borrow().value,
+                *target.last().unwrap()
+            );
+        }
+
+        let hands = LinkedList::<i32>::new().distribute(2);
+        assert!(hands.iter().all(|hand| hand.head.is_none()));
+    }
+
+    #[test]
+    fn test_riffle() {
+        let mut lists = Vec::new();
+        for values in [&[1, 4, 6][..], &[2, 5], &[], &[3]] {
+            let mut list = LinkedList::<i32>::new();
+            for value in values {
+                list.push_back(*value);
+            }
+            lists.push(list);
+        }
+
+        let riffled = LinkedList::riffle(lists);
+
+        assert_eq!(riffled, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(riffled.tail_node().unwrap().borrow().value, 6);
+        assert!(LinkedList::<i32>::riffle(Vec::new()).head.is_none());
+    }
+
+    #[test]
+    fn test_step_by() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 0..8 {
+            list.push_back(value);
+        }
+
+        let targets = [0, 3, 6];
+        assert_eq!(list.step_by(3).count(), targets.len());
+        for (node, value) in list.step_by(3).zip(targets) {
+            assert_eq!(node.clone().unwrap().borrow().value, value)
+        }
+        assert_eq!(list.step_by(1).count(), 8);
+        assert_eq!(list.step_by(9).count(), 1);
+        assert_eq!(LinkedList::<i32>::new().step_by(2).count(), 0);
+
+        let long: LinkedList<i32> = (0..1_000_000).collect();
+        let strided: Vec<_> = long.step_by(999_999).flatten().collect();
+        assert_eq!(strided.len(), 2);
+        assert_eq!(strided[1].borrow().value, 999_999);
+        assert_eq!(long.step_by(1_000_000).count(), 1);
+    }
+
+    #[test]
+    fn test_step_by_leaves_skipped_nodes() {
+        let list: LinkedList<i32> = (0..7).collect();
+        let skipped = [1, 2, 4, 5].map(|i| list.get_nth(i).unwrap().unwrap());
+        let mut strided = list.step_by(3);
+        let first = strided.next().unwrap().unwrap();
+        // held by their predecessor and the test, not by the iterator
+        assert!(skipped.iter().all(|node| Rc::strong_count(node) == 2));
+        let second = strided.next().unwrap().unwrap();
+        assert!(skipped.iter().all(|node| Rc::strong_count(node) == 2));
+        assert_eq!((first.borrow().value, second.borrow().value), (0, 3));
+    }
+
+    #[test]
+    fn test_every_nth() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 0..8 {
+            list.push_back(value);
+        }
+        let skipped = list.get_nth(2).unwrap().unwrap();
+
+        let picked = list.every_nth(3);
+
+        assert_eq!(picked, [0, 3, 6]);
+        assert_eq!(picked.tail_node().unwrap().borrow().value, 6);
+        // dropped nodes don't keep pointing into the extracted list
+        assert!(skipped.borrow().next.is_none());
+        assert!(LinkedList::<i32>::new().every_nth(2).head.is_none());
+
+        let long: LinkedList<i32> = (0..1_000_000).collect();
+        assert_eq!(long.every_nth(1_000_000), [0]);
+    }
+
+    #[test]
+    fn test_tuples() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 0..8 {
+            list.push_back(value);
+        }
+
+        let mut tuples = list.tuples::<3>();
+        let groups: Vec<[i32; 3]> = tuples
+            .by_ref()
+            .map(|group| group.map(|node| node.borrow().value))
+            .collect();
+        assert_eq!(groups, [[0, 1, 2], [3, 4, 5]]);
+        let remainder: Vec<i32> = tuples
+            .remainder()
+            .iter()
+            .map(|node| node.borrow().value)
+            .collect();
+        assert_eq!(remainder, [6, 7]);
+        assert!(tuples.next().is_none());
+        assert_eq!(tuples.remainder().len(), 2);
+
+        let mut pairs = list.tuples::<2>();
+        assert_eq!(pairs.by_ref().count(), 4);
+        assert!(pairs.remainder().is_empty());
+    }
+
+    #[test]
+    fn test_iter_peek() {
+        let mut list = LinkedList::<i32>::new();
+        for value in [1, 2, 3] {
+            list.push_back(value);
+        }
+
+        let mut iter = list.iter();
+        assert_eq!(iter.peek().unwrap().borrow().value, 1);
+        assert_eq!(iter.peek_nth(0).unwrap().borrow().value, 1);
+        assert_eq!(iter.peek_nth(2).unwrap().borrow().value, 3);
+        assert!(iter.peek_nth(3).is_none());
+
+        iter.next();
+        iter.next();
+        assert_eq!(iter.peek().unwrap().borrow().value, 3);
+        assert!(iter.peek_nth(1).is_none());
+        assert_eq!(iter.next().unwrap().unwrap().borrow().value, 3);
+        assert!(iter.peek().is_none());
+        assert!(iter.peek_nth(0).is_none());
+
+        // far lookahead is a loop, not one stack frame per node
+        let long: LinkedList<i32> = (0..1_000_000).collect();
+        let far = long.iter().peek_nth(999_999).unwrap();
+        assert_eq!(far.borrow().value, 999_999);
+    }
+
+    #[test]
+    fn test_eq_slices() {
+        let mut list = LinkedList::<i32>::new();
+        for value in [1, 2, 3] {
+            list.push_back(value);
+        }
+
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(list, [1, 2, 3][..]);
+        assert_ne!(list, [1, 2]);
+        assert_ne!(list, [1, 2, 3, 4]);
+        assert_ne!(list, vec![1, 2, 4]);
+        assert_eq!(LinkedList::<i32>::new(), [0; 0]);
+        assert_ne!(LinkedList::<i32>::new(), [0]);
+
+        let mut names = LinkedList::<String>::new();
+        names.push_back("a".to_string());
+        assert_eq!(names, ["a"]);
+    }
+
+    #[test]
+    fn test_get_or_insert_with() {
+        let mut list = LinkedList::<i32>::new();
+        list.push_back(7);
+
+        assert_eq!(*list.get_or_insert_with(0, || unreachable!()), 7);
+
+        let mut made = 0;
+        let value = list.get_or_insert_with(3, || {
+            made += 1;
+            made * 10
+        });
+        assert_eq!(*value, 30);
+        drop(value);
+        assert_eq!(list, [7, 10, 20, 30]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 30);
+
+        *list.get_or_insert_default(1) += 1;
+        *list.get_or_insert_default(4) += 1;
+        assert_eq!(list, [7, 11, 20, 30, 1]);
+    }
+
+    #[test]
+    fn test_transaction() {
+        let mut list = LinkedList::<i32>::new();
+        for value in [1, 2, 3] {
+            list.push_back(value);
+        }
+        list.enable_content_hash();
+        let hash = list.content_hash();
+
+        let result = list.transaction(|staged| {
+            staged.push_back(4);
+            staged.update_nth(0, 0)?;
+            staged.update_nth(7, 0)
+        });
+        assert!(result.is_err());
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list.content_hash(), hash);
+
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = list.transaction(|staged| -> Result<(), ()> {
+                staged.push_front(0);
+                panic!("failed midway");
+            });
+        }));
+        assert!(panicked.is_err());
+        assert_eq!(list, [1, 2, 3]);
+
+        let cursor = list.cursor();
+        let len = list
+            .transaction(|staged| {
+                staged.rotate_to(1)?;
+                staged.push_back(4);
+                Ok::<_, ListError>(staged.iter().count())
+            })
+            .unwrap();
+        assert_eq!(len, 4);
+        assert_eq!(list, [2, 3, 1, 4]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 4);
+        assert!(!cursor.is_valid());
+        let mut expected = LinkedList::new();
+        expected.enable_content_hash();
+        for value in [2, 3, 1, 4] {
+            expected.push_back(value);
+        }
+        assert_eq!(list.content_hash(), expected.content_hash());
+    }
+
+    #[test]
+    fn test_transaction_keeps_handles() {
+        let mut list = LinkedList::<i32>::new();
+        let first = list.push_back(1);
+        let second = list.push_back(2);
+        let third = list.push_back(3);
+        let cursor = list.cursor_at(2).unwrap();
+        let mut elsewhere = LinkedList::new();
+
+        let result = list.transaction(|staged| {
+            staged.update_nth(0, 10)?;
+            staged.drain_matching_into(&mut elsewhere, |value| *value == 2);
+            staged.remove_nth(1)?;
+            staged.push_front(0);
+            staged.get_nth(9).map(drop)
+        });
+        assert!(result.is_err());
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 3);
+        let linked = |n: usize, node: &NodeRef<i32>| {
+            Rc::ptr_eq(
+                &list.get_nth(n).unwrap().unwrap(),
+                &node.0.upgrade().unwrap(),
+            )
+        };
+        assert!(linked(0, &first));
+        assert_eq!(*list.value(&first).unwrap(), 1);
+        // its node went to another list, which keeps it
+        assert!(!linked(1, &second));
+        assert_eq!(elsewhere, [2]);
+        // unlinked and dropped by `f`, but kept alive by the undo log
+        assert!(linked(2, &third));
+        assert!(!cursor.is_valid());
+
+        let cursor = list.cursor_at(2).unwrap();
+        list.transaction(|staged| {
+            staged.push_front(0);
+            staged.update_nth(1, 5)
+        })
+        .unwrap();
+        assert_eq!(list, [0, 5, 2, 3]);
+        assert_eq!(*list.value(&first).unwrap(), 5);
+        assert_eq!(cursor.index(), Ok(3));
+    }
+
+    #[test]
+    fn test_bounded_display() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 0..150 {
+            list.push_back(value);
+        }
+
+        let display = list.to_string();
+        assert!(display.ends_with("98, 99, … (+50 more)]"));
+        assert!(format!("{list:?}").ends_with("98, 99, … (+50 more)]"));
+        assert!(list.display_full().to_string().ends_with("148, 149]"));
+
+        list.set_display_limit(2);
+        assert_eq!(list.to_string(), "[0, 1, … (+148 more)]");
+        assert_eq!(format!("{list:?}"), "[0, 1, … (+148 more)]");
+        assert_eq!(
+            list.to_string_with(|v, f| write!(f, "{v:x}")),
+            "[0, 1, … (+148 more)]"
+        );
+        assert_eq!(list.clone().to_string(), "[0, 1, … (+148 more)]");
+        list.set_display_limit(0);
+        assert_eq!(list.to_string(), "[… (+150 more)]");
+
+        let mut short = LinkedList::<i32>::new();
+        short.push_back(1);
+        short.set_display_limit(1);
+        assert_eq!(short.to_string(), "[1]");
+        assert_eq!(format!("{short:?}"), "[1]");
+        assert_eq!(LinkedList::<i32>::new().to_string(), "[]");
+    }
+
+    #[test]
+    fn test_eq_by() {
+        let mut list = LinkedList::<f64>::new();
+        for value in [0.1 + 0.2, 1.0] {
+            list.push_back(value);
+        }
+        let mut other = LinkedList::<f32>::new();
+        for value in [0.3, 1.0] {
+            other.push_back(value);
+        }
+        let close = |a: &f64, b: &f32| (a - f64::from(*b)).abs() < 1e-6;
+
+        assert!(list.eq_by(&other, close));
+        other.push_back(2.0);
+        assert!(!list.eq_by(&other, close));
+        assert!(!other.eq_by(&list, |a, b| close(b, a)));
+
+        let mut words = LinkedList::<String>::new();
+        words.push_back("Hello".to_string());
+        words.push_back("WORLD".to_string());
+        assert!(words.eq_by_iter(["hello", "world"], |a, b| a.eq_ignore_ascii_case(b)));
+        assert!(!words.eq_by_iter(["hello"], |a, b| a.eq_ignore_ascii_case(b)));
+        assert!(!words.eq_by_iter(["hello", "word"], |a, b| a.eq_ignore_ascii_case(b)));
+        assert!(LinkedList::<i32>::new().eq_by_iter(0..0, |_, _| false));
+    }
+
+    #[test]
+    fn test_drain_matching_into() {
+        let mut list = LinkedList::<i32>::new();
+        for value in 1..=8 {
+            list.push_back(value);
+        }
+        let mut target = LinkedList::<i32>::new();
+        target.push_back(0);
+        target.enable_content_hash();
+        let cursor = list.cursor_at(3).unwrap();
+
+        let moved = list.drain_matching_into(&mut target, |value| value % 2 == 0 || *value > 6);
+
+        assert_eq!(moved, 5);
+        assert_eq!(list, [1, 3, 5]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 5);
+        assert_eq!(target, [0, 2, 4, 6, 7, 8]);
+        assert_eq!(target.tail_node().unwrap().borrow().value, 8);
+        assert_eq!(target.content_hash(), {
+            let mut expected = LinkedList::new();
+            expected.enable_content_hash();
+            for value in [0, 2, 4, 6, 7, 8] {
+                expected.push_back(value);
+            }
+            expected.content_hash()
+        });
+        // the cursor was on 4, it moves on to 5
+        assert_eq!(cursor.index(), Ok(2));
+        assert_eq!(cursor.current().unwrap().unwrap().borrow().value, 5);
+
+        assert_eq!(list.drain_matching_into(&mut target, |_| false), 0);
+        assert_eq!(list.drain_matching_into(&mut target, |_| true), 3);
+        assert!(list.head.is_none() && list.tail.is_none());
+        assert_eq!(target.iter().count(), 9);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let list = LinkedList::from_fn(4, |i| i * 10);
+        assert_eq!(list, [0, 10, 20, 30]);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 30);
+
+        let mut calls = 0;
+        let empty = LinkedList::from_fn(0, |i| {
+            calls += 1;
+            i
+        });
+        assert_eq!(empty, [0; 0]);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_successors() {
+        let collatz = LinkedList::successors(Some(6_u32), |&n| match n {
+            1 => None,
+            n if n % 2 == 0 => Some(n / 2),
+            n => Some(3 * n + 1),
+        });
+        assert_eq!(collatz, [6, 3, 10, 5, 16, 8, 4, 2, 1]);
+        assert_eq!(collatz.tail_node().as_ref().unwrap().borrow().value, 1);
+
+        let empty = LinkedList::successors(None, |n: &u32| Some(n + 1));
+        assert!(empty.head.is_none() && empty.tail.is_none());
+        assert_eq!(LinkedList::successors(Some('a'), |_| None), ['a']);
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.pop_front(), None::<String>);
+        list.push_back("a".to_string());
+        list.push_back("b".to_string());
+        list.push_back("c".to_string());
+
+        assert_eq!(list.pop_front().as_deref(), Some("a"));
+        let held = list.get_nth(0).unwrap().unwrap();
+        assert_eq!(list.pop_front().as_deref(), Some("b"));
+        assert_eq!(held.borrow().value, "b");
+        assert_eq!(list.pop_front().as_deref(), Some("c"));
+        assert!(list.head.is_none() && list.tail.is_none());
+        assert_eq!(list.pop_front(), None);
+
+        list.push_back("d".to_string());
+        assert_eq!(list, ["d"]);
+        assert!(Rc::ptr_eq(
+            list.head.as_ref().unwrap(),
+            list.tail_node().as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_pop_front_moves_cursors() {
+        let mut list = LinkedList::from_fn(3, |i| i);
+        let first = list.cursor();
+        let last = list.cursor_at(2).unwrap();
+        list.pop_front();
+        assert_eq!(first.index(), Ok(0));
+        assert_eq!(first.current().unwrap().unwrap().borrow().value, 1);
+        assert_eq!(last.index(), Ok(1));
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.pop_back(), None::<i32>);
+        list.push_back(1);
+        assert_eq!(list.pop_back(), Some(1));
+        assert!(list.head.is_none() && list.tail.is_none());
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 2);
+        list.push_back(4);
+        assert_eq!(list, [1, 2, 4]);
+        let cursor = list.cursor_at(2).unwrap();
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(cursor.index(), Ok(2));
+        assert!(cursor.current().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = LinkedList::new();
+        let mut model = std::collections::VecDeque::new();
+        for i in 0..200 {
+            match i % 7 {
+                0 | 3 => {
+                    list.push_back(i);
+                    model.push_back(i);
+                }
+                1 | 5 => {
+                    list.push_front(i);
+                    model.push_front(i);
+                }
+                2 | 6 => assert_eq!(list.pop_back(), model.pop_back()),
+                _ => assert_eq!(list.pop_front(), model.pop_front()),
+            }
+            assert_eq!(list, model.iter().copied().collect::<Vec<_>>());
+            let tail = list.tail_node().as_ref().map(|tail| tail.borrow().value);
+            assert_eq!(tail, model.back().copied());
+        }
+        while let Some(value) = model.pop_back() {
+            assert_eq!(list.pop_back(), Some(value));
+        }
+        assert!(list.head.is_none() && list.tail.is_none());
+    }
+
+    fn assert_len<T>(list: &LinkedList<T>) {
+        assert_eq!(list.len(), list.iter().count());
+        assert_eq!(list.is_empty(), list.head.is_none());
+    }
+
+    #[test]
+    fn test_len_tracks_mutations() {
+        let mut list = LinkedList::from_fn(6, |i| i);
+        assert_len(&list);
+        list.push_front(10);
+        list.push_after_n(2, 11).unwrap();
+        assert_eq!(list.len(), 8);
+        list.pop_front();
+        list.pop_back();
+        assert_len(&list);
+
+        let node = list.get_nth(1).unwrap().unwrap();
+        list.mark_remove(&NodeRef::from(&node)).unwrap();
+        list.sweep();
+        assert_len(&list);
+        let mut odd = LinkedList::new();
+        list.drain_matching_into(&mut odd, |value| value % 2 == 1);
+        assert_len(&list);
+        assert_len(&odd);
+
+        let mut first = list.clone();
+        let sec = first.split_off(2).unwrap();
+        assert_eq!((first.len(), sec.len()), (2, 1));
+        assert_len(&first);
+        assert_len(&sec);
+        for chunk in first.clone().split_into(3) {
+            assert_len(&chunk);
+        }
+        assert_len(&first.clone().every_nth(2));
+        assert_len(&first.clone().union_sorted(odd.clone()));
+        assert_len(&LinkedList::riffle(odd.clone().distribute(2)));
+
+        let mut copy = LinkedList::from_fn(9, |i| i);
+        copy.clone_from(&odd);
+        assert_len(&copy);
+        copy.clone_from(&LinkedList::from_fn(5, |i| i));
+        assert_len(&copy);
+        let _ = copy.transaction(|staged| {
+            staged.pop_front();
+            Ok::<_, ()>(())
+        });
+        assert_len(&copy);
+
+        let mut empty = LinkedList::<i32>::new();
+        assert!(empty.is_empty());
+        empty.push_back(1);
+        empty.pop_back();
+        assert!(empty.is_empty());
+        assert_len(&empty);
+    }
+
+    #[test]
+    fn test_values() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.values().next(), None::<String>);
+        for word in ["a", "b", "c"] {
+            list.push_back(word.to_string());
+        }
+        let mut joined = String::new();
+        for value in list.values() {
+            joined += &value;
+        }
+        assert_eq!(joined, "abc");
+
+        // the clones are independent of the nodes
+        let mut values = list.values();
+        let first = values.next().unwrap();
+        list.update_nth(0, "z".to_string()).unwrap();
+        assert_eq!(first, "a");
+        assert_eq!(values.collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn test_remove_nth() {
+        let mut list = LinkedList::from_fn(5, |i| i * 10);
+        assert_eq!(list.remove_nth(2), Ok(20));
+        assert_eq!(list, [0, 10, 30, 40]);
+        assert_eq!(list.remove_nth(0), Ok(0));
+        assert_eq!(list.head.as_ref().unwrap().borrow().value, 10);
+        assert_eq!(list.remove_nth(2), Ok(40));
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 30);
+        assert_eq!(list, [10, 30]);
+        assert_eq!(list.len(), 2);
+
+        assert_eq!(
+            list.remove_nth(2),
+            Err(ListError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(list.remove_nth(1), Ok(30));
+        assert_eq!(list.remove_nth(0), Ok(10));
+        assert!(list.head.is_none() && list.tail.is_none());
+        assert_eq!(
+            list.remove_nth(0).unwrap_err().to_string(),
+            "index 0 out of bounds for list of length 0"
+        );
+        list.push_back(1);
+        assert_eq!(list, [1]);
+    }
+
+    #[test]
+    fn test_remove_nth_moves_cursors() {
+        let mut list = LinkedList::from_fn(4, |i| i);
+        let removed = list.cursor_at(1).unwrap();
+        let after = list.cursor_at(3).unwrap();
+        list.remove_nth(1).unwrap();
+        assert_eq!(removed.index(), Ok(1));
+        assert_eq!(removed.current().unwrap().unwrap().borrow().value, 2);
+        assert_eq!(after.index(), Ok(2));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let list = LinkedList::from_fn(4, |i| i.to_string());
+        let mut borrowed = Vec::new();
+        for value in &list {
+            borrowed.push(value);
+        }
+        assert_eq!(borrowed, ["0", "1", "2", "3"]);
+
+        let held = list.get_nth(3).unwrap().unwrap();
+        let mut owned = list.into_iter();
+        assert_eq!(owned.len(), 4);
+        assert_eq!(owned.next().as_deref(), Some("0"));
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned.collect::<Vec<_>>(), ["1", "2", "3"]);
+        assert_eq!(held.borrow().value, "3");
+
+        let sum: usize = LinkedList::from_fn(5, |i| i).into_iter().sum();
+        assert_eq!(sum, 10);
+        assert_eq!(LinkedList::<u8>::new().into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_collect_and_extend() {
+        let mut list: LinkedList<i32> = (1..=4).collect();
+        assert_eq!(list, [1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+        list.extend(vec![5, 6]);
+        list.extend(core::iter::empty());
+        assert_eq!(list, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 6);
+
+        let empty: LinkedList<i32> = Vec::new().into_iter().collect();
+        assert!(empty.is_empty() && empty.tail.is_none());
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut empty = LinkedList::<i32>::new();
+        empty.reverse();
+        assert!(empty.head.is_none() && empty.tail.is_none());
+
+        let mut single = LinkedList::from_fn(1, |i| i);
+        single.reverse();
+        assert_eq!(single, [0]);
+        assert!(Rc::ptr_eq(
+            single.head.as_ref().unwrap(),
+            single.tail_node().as_ref().unwrap()
+        ));
+
+        let mut list = LinkedList::from_fn(1000, |i| i);
+        let first = list.get_nth(0).unwrap().unwrap();
+        list.reverse();
+        assert_eq!(list, (0..1000).rev().collect::<Vec<_>>());
+        assert!(Rc::ptr_eq(list.tail_node().as_ref().unwrap(), &first));
+        assert!(first.borrow().next.is_none());
+        assert_eq!(list.len(), 1000);
+        list.push_front(1000);
+        list.reverse();
+        assert_eq!(list, (0..=1000).collect::<Vec<_>>());
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 1000);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        let mut other: LinkedList<i32> = (4..=5).collect();
+        let moved = other.get_nth(0).unwrap().unwrap();
+        list.append(&mut other);
+        assert_eq!(list, [1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 5);
+        assert!(Rc::ptr_eq(&list.get_nth(3).unwrap().unwrap(), &moved));
+        assert!(other.is_empty() && other.head.is_none() && other.tail.is_none());
+
+        // other stays usable, and appending an empty list is a no-op
+        other.push_back(6);
+        list.append(&mut LinkedList::new());
+        let mut empty = LinkedList::new();
+        empty.append(&mut list);
+        empty.append(&mut other);
+        assert_eq!(empty, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(empty.len(), 6);
+        assert!(list.is_empty() && other.is_empty());
+    }
+
+    #[test]
+    fn test_append_keeps_hash_and_cursors() {
+        fn hashed(values: impl IntoIterator<Item = i32>) -> LinkedList<i32> {
+            let mut list: LinkedList<i32> = values.into_iter().collect();
+            list.enable_content_hash();
+            list
+        }
+        let mut list = hashed(1..=2);
+        let mut other = hashed(3..=4);
+        let mut end = list.cursor_at(1).unwrap();
+        end.move_next().unwrap();
+        let moved = other.cursor();
+
+        list.append(&mut other);
+        assert_eq!(list.content_hash(), hashed(1..=4).content_hash());
+        assert_eq!(other.content_hash(), hashed([]).content_hash());
+        assert_eq!(end.index(), Ok(4));
+        assert!(!moved.is_valid());
+
+        let mut unhashed: LinkedList<i32> = (5..=5).collect();
+        list.append(&mut unhashed);
+        assert_eq!(list.content_hash(), hashed(1..=5).content_hash());
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut list = LinkedList::<i32>::new();
+        list.sort();
+        assert!(list.is_empty() && list.tail.is_none());
+        list.push_back(1);
+        list.sort();
+        assert_eq!(list, [1]);
+
+        let mut values: Vec<i32> = (0..500).map(|i| (i * 7919) % 503 - 250).collect();
+        let mut list: LinkedList<i32> = values.iter().copied().collect();
+        let node = list.get_nth(0).unwrap().unwrap();
+        list.enable_content_hash();
+        list.sort();
+        values.sort();
+        assert_eq!(list, values);
+        assert_eq!(list.len(), 500);
+        assert_eq!(
+            list.tail_node().as_ref().unwrap().borrow().value,
+            values[499]
+        );
+        assert!(list.iter().flatten().any(|other| Rc::ptr_eq(&other, &node)));
+        let mut rebuilt: LinkedList<i32> = values.iter().copied().collect();
+        rebuilt.enable_content_hash();
+        assert_eq!(list.content_hash(), rebuilt.content_hash());
+
+        list.sort_by(|a, b| b.cmp(a));
+        values.reverse();
+        assert_eq!(list, values);
+    }
+
+    #[test]
+    fn test_sort_by_key_is_stable() {
+        let mut list: LinkedList<(u8, char)> =
+            [(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd'), (1, 'e'), (2, 'f')]
+                .into_iter()
+                .collect();
+        list.sort_by_key(|pair| pair.0);
+        assert_eq!(
+            list,
+            [(0, 'd'), (1, 'b'), (1, 'e'), (2, 'a'), (2, 'c'), (2, 'f')]
+        );
+        list.push_back((0, 'g'));
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, (0, 'g'));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list: LinkedList<i32> = (0..10).collect();
+        let kept = list.get_nth(4).unwrap().unwrap();
+        let dropped = list.get_nth(5).unwrap().unwrap();
+        let cursor = list.cursor_at(5).unwrap();
+        list.retain(|value| value % 2 == 0);
+        assert_eq!(list, [0, 2, 4, 6, 8]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 8);
+        assert!(Rc::ptr_eq(&list.get_nth(2).unwrap().unwrap(), &kept));
+        assert!(dropped.borrow().next.is_none());
+        assert_eq!(cursor.index(), Ok(3));
+        assert_eq!(cursor.current().unwrap().unwrap().borrow().value, 6);
+
+        // head and tail dropped
+        list.retain(|value| (1..8).contains(value));
+        assert_eq!(list, [2, 4, 6]);
+        assert_eq!(list.head.as_ref().unwrap().borrow().value, 2);
+        assert_eq!(list.tail_node().as_ref().unwrap().borrow().value, 6);
+        list.retain(|_| true);
+        assert_eq!(list.len(), 3);
+        list.retain(|_| false);
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+        list.push_back(1);
+        assert_eq!(list, [1]);
+    }
+
+    // Each node is held by exactly one link, and the tail only weakly.
+    fn assert_single_owner<T>(list: &LinkedList<T>) {
+        for node in list.iter().flatten() {
+            // the owning link plus the handle yielded here
+            assert_eq!(Rc::strong_count(&node), 2);
+        }
+        if let Some(tail) = list.tail_node() {
+            assert_eq!(Rc::weak_count(&tail), 1);
+        }
+    }
+
+    #[test]
+    fn test_tail_is_weak_after_split_and_append() {
+        let mut list: LinkedList<i32> = (1..=6).collect();
+        assert_single_owner(&list);
+        let mut rest = list.split_off(4).unwrap();
+        assert_single_owner(&list);
+        assert_single_owner(&rest);
+
+        let dropped = Rc::downgrade(&rest.tail_node().unwrap());
+        let kept = Rc::downgrade(&list.tail_node().unwrap());
+        let mut empty = list.split_off(4).unwrap();
+        assert!(empty.tail.is_none());
+        rest.append(&mut empty);
+        drop(rest);
+        assert!(dropped.upgrade().is_none());
+        assert!(kept.upgrade().is_some());
+
+        let mut other: LinkedList<i32> = (7..=9).collect();
+        list.append(&mut other);
+        list.reverse();
+        list.sort();
+        assert_single_owner(&list);
+        assert!(other.tail.is_none());
+        assert_eq!(list.pop_back(), Some(9));
+        assert_single_owner(&list);
+        drop(list);
+        assert!(kept.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let list = LinkedList::from_fn(1_000_000, |i| i);
+        drop(list);
+
+        // a handle kept into the middle keeps its part of the chain
+        let list: LinkedList<i32> = (1..=4).collect();
+        let third = list.get_nth(2).unwrap().unwrap();
+        let first = Rc::downgrade(&list.get_nth(0).unwrap().unwrap());
+        drop(list);
+        assert!(first.upgrade().is_none());
+        assert_eq!(third.borrow().next.as_ref().unwrap().borrow().value, 4);
+    }
+
+    #[test]
+    fn test_eq_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        fn hash_of(list: &LinkedList<i32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let list: LinkedList<i32> = (1..=3).collect();
+        let copy = list.clone();
+        assert!(!Rc::ptr_eq(
+            list.head.as_ref().unwrap(),
+            copy.head.as_ref().unwrap()
+        ));
+        assert_eq!(list, copy);
+        assert_eq!(hash_of(&list), hash_of(&copy));
+        assert_ne!(list, (1..=2).collect::<LinkedList<i32>>());
+        assert_ne!(list, (1..=4).collect::<LinkedList<i32>>());
+        assert_ne!(list, [1, 3, 2].into_iter().collect::<LinkedList<i32>>());
+        assert_eq!(LinkedList::<i32>::new(), LinkedList::<i32>::new());
+
+        copy.update_nth(0, 7).unwrap();
+        assert_eq!(list, [1, 2, 3]);
+        assert_ne!(list, copy);
+
+        let mut seen = HashMap::new();
+        seen.insert(list.clone(), "first");
+        seen.insert(copy, "second");
+        assert_eq!(seen[&list], "first");
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn test_lexicographic_order() {
+        fn list(values: &[f64]) -> LinkedList<f64> {
+            values.iter().copied().collect()
+        }
+        assert!(list(&[1.0, 2.0]) < list(&[1.0, 3.0]));
+        assert!(list(&[1.0, 2.0]) < list(&[1.0, 2.0, 0.0]));
+        assert!(list(&[2.0]) > list(&[1.0, 9.0]));
+        assert!(list(&[]) < list(&[0.0]));
+        assert_eq!(
+            list(&[1.0, 2.0]).partial_cmp(&list(&[1.0, 2.0])),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(list(&[f64::NAN]).partial_cmp(&list(&[1.0])), None);
+
+        let mut lists: Vec<LinkedList<i32>> = [vec![2], vec![1, 5], vec![], vec![1], vec![1, 2, 3]]
+            .into_iter()
+            .map(|values| values.into_iter().collect())
+            .collect();
+        lists.sort();
+        let sorted: Vec<Vec<i32>> = lists.iter().map(|list| list.values().collect()).collect();
+        assert_eq!(
+            sorted,
+            [vec![], vec![1], vec![1, 2, 3], vec![1, 5], vec![2]]
+        );
+        assert_eq!(lists.iter().max(), lists.last());
+    }
+
+    #[test]
+    fn test_display_uses_display_of_values() {
+        let mut list: LinkedList<String> = ["a b", "c"].into_iter().map(String::from).collect();
+        assert_eq!(list.to_string(), "[a b, c]");
+        assert_eq!(format!("{list:?}"), r#"["a b", "c"]"#);
+        assert_eq!(format!("{list:#}"), "a b\nc");
+        assert_eq!(list.format_with(" | ").to_string(), "a b | c");
+        assert_eq!(list.join("/"), "a b/c");
+
+        list.push_back("d".to_string());
+        list.set_display_limit(1);
+        assert_eq!(format!("{list:#}"), "a b\n… (+2 more)");
+        assert_eq!(format!("{:#}", list.display_full()), "a b\nc\nd");
+        assert_eq!(list.format_with("").to_string(), "a bcd");
+        assert_eq!(LinkedList::<u8>::new().format_with(", ").to_string(), "");
+    }
+
+    #[test]
+    fn test_get_and_get_mut() {
+        let mut list: LinkedList<String> = ["a", "b"].into_iter().map(String::from).collect();
+        assert_eq!(list.get(1).as_deref().map(String::as_str), Some("b"));
+        assert!(list.get(2).is_none() && list.get_mut(2).is_none());
+
+        list.get_mut(0).unwrap().push('!');
+        assert_eq!(list, ["a!", "b"]);
+        {
+            let first = list.get(0).unwrap();
+            let again = list.get(0).unwrap();
+            assert_eq!(*first, *again);
+        }
+
+        // a guard holds the list borrowed, and the value borrowed like RefCell does
+        let guard = list.get_mut(1).unwrap();
+        assert!(list.tail_node().unwrap().try_borrow().is_err());
+        drop(guard);
+        assert_eq!(list.pop_front().as_deref(), Some("a!"));
+    }
+
+    #[test]
+    fn test_at() {
+        let list: LinkedList<i32> = (1..=3).collect();
+        *list.at_mut(2) += 10;
+        assert_eq!((*list.at(0), *list.at(2)), (1, 13));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of bounds for list of length 3")]
+    fn test_at_out_of_bounds() {
+        let list: LinkedList<i32> = (1..=3).collect();
+        list.at(3);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut list = LinkedList::new();
+        list.insert(0, 2).unwrap();
+        list.insert(0, 0).unwrap();
+        list.insert(1, 1).unwrap();
+        list.insert(3, 4).unwrap();
+        list.insert(3, 3).unwrap();
+        assert_eq!(list, [0, 1, 2, 3, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 4);
+        assert_eq!(
+            list.insert(6, 9).unwrap_err(),
+            ListError::IndexOutOfBounds { index: 6, len: 5 }
+        );
+
+        list.enable_content_hash();
+        list.insert(2, 7).unwrap();
+        let expected: LinkedList<i32> = [0, 1, 7, 2, 3, 4].into_iter().collect();
+        assert_eq!(list, expected);
+        assert_eq!(list.content_hash(), {
+            let mut expected = expected;
+            expected.enable_content_hash();
+            expected.content_hash()
+        });
+
+        // push_after_n is insert after the nth
+        list.push_after_n(5, 5).unwrap();
+        assert_eq!(list.tail_node().unwrap().borrow().value, 5);
+        assert_eq!(
+            list.push_after_n(7, 9),
+            Err(ListError::IndexOutOfBounds { index: 7, len: 7 })
+        );
+    }
+
+    fn content_hash_of(values: &[i32]) -> Option<u64> {
+        let mut list: LinkedList<i32> = values.iter().copied().collect();
+        list.enable_content_hash();
+        list.content_hash()
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        list.enable_content_hash();
+        let mut drain = list.drain();
+        assert_eq!(drain.len(), 5);
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next(), Some(2));
+        drop(drain);
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+        assert_eq!(list.content_hash(), content_hash_of(&[]));
+
+        list.extend([6, 7]);
+        assert_eq!(list.drain().collect::<Vec<_>>(), [6, 7]);
+        assert!(list.is_empty());
+        list.push_back(8);
+        assert_eq!(list, [8]);
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut list: LinkedList<i32> = (1..=10).collect();
+        list.enable_content_hash();
+        let evens: Vec<_> = list.extract_if(|value| value % 2 == 0).collect();
+        assert_eq!(evens, [2, 4, 6, 8, 10]);
+        assert_eq!(list, [1, 3, 5, 7, 9]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 9);
+        assert_eq!(list.content_hash(), content_hash_of(&[1, 3, 5, 7, 9]));
+
+        // stopping early leaves the rest untouched
+        let mut small = list.extract_if(|value| *value < 5);
+        assert_eq!(small.next(), Some(1));
+        drop(small);
+        assert_eq!(list, [3, 5, 7, 9]);
+
+        assert_eq!(list.extract_if(|_| true).count(), 4);
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+        list.push_back(1);
+        assert_eq!(list, [1]);
+    }
+
+    #[test]
+    fn test_contains_find_position() {
+        let list: LinkedList<String> = ["ant", "bee", "cat", "bat"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(list.contains(&String::from("cat")));
+        assert!(list.contains(&"bee"));
+        assert!(!list.contains(&"dog"));
+        assert_eq!(list.position(|word| word.starts_with('b')), Some(1));
+        assert_eq!(list.position(|word| word.is_empty()), None);
+        assert_eq!(
+            list.find(|word| word.ends_with("at")).as_deref(),
+            Some(&String::from("cat"))
+        );
+        assert!(list.find(|word| word.len() > 3).is_none());
+        assert!(!LinkedList::<i32>::new().contains(&0));
+    }
+
+    #[test]
+    fn test_front_and_back() {
+        let mut list = LinkedList::new();
+        assert!(list.front().is_none() && list.back().is_none());
+        assert!(list.front_mut().is_none() && list.back_mut().is_none());
+
+        list.push_back(1);
+        assert_eq!((*list.front().unwrap(), *list.back().unwrap()), (1, 1));
+        list.push_back(2);
+        *list.front_mut().unwrap() += 10;
+        *list.back_mut().unwrap() *= 10;
+        assert_eq!(list, [11, 20]);
+        assert_eq!(*list.back().unwrap(), 20);
+
+        // both guards share the only node
+        list.pop_back();
+        let (front, back) = (list.front().unwrap(), list.back().unwrap());
+        assert_eq!(*front + *back, 22);
+    }
+
+    #[test]
+    fn test_clear_and_truncate() {
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        list.enable_content_hash();
+        list.truncate(7);
+        assert_eq!(list.len(), 5);
+        list.truncate(3);
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 3);
+        assert_eq!(list.content_hash(), content_hash_of(&[1, 2, 3]));
+        list.push_back(4);
+        assert_eq!(list, [1, 2, 3, 4]);
+
+        list.clear();
+        assert!(list.is_empty() && list.head.is_none() && list.tail.is_none());
+        assert_eq!(list.content_hash(), content_hash_of(&[]));
+        list.clear();
+
+        let mut long = LinkedList::from_fn(1_000_000, |i| i);
+        long.truncate(1);
+        assert_eq!(long, [0]);
+        long.clear();
+        assert!(long.is_empty());
+    }
+
+    #[test]
+    fn test_linked_list_macro() {
+        let empty: LinkedList<i32> = linked_list![];
+        assert!(empty.is_empty());
+        let list = linked_list![1, 2, 3,];
+        assert_eq!(list, [1, 2, 3]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 3);
+        assert_eq!(linked_list!["a"], ["a"]);
+
+        let repeated = linked_list![String::from("x"); 3];
+        assert_eq!(repeated, ["x", "x", "x"]);
+        let none: LinkedList<String> = linked_list![String::new(); 0];
+        assert!(none.is_empty());
+        assert_eq!(linked_list![linked_list![0; 2]; 2].len(), 2);
+    }
+
+    #[test]
+    fn test_vec_array_slice_conversions() {
+        let from_vec = LinkedList::from(vec![1, 2, 3]);
+        let from_array = LinkedList::from([1, 2, 3]);
+        let from_slice = LinkedList::from(&[1, 2, 3][..]);
+        assert_eq!(from_vec, from_array);
+        assert_eq!(from_array, from_slice);
+        assert_eq!(from_slice.tail_node().unwrap().borrow().value, 3);
+        assert!(LinkedList::<u8>::from([]).is_empty());
+
+        assert_eq!(from_vec.to_vec(), [1, 2, 3]);
+        assert_eq!(Vec::from(from_vec), [1, 2, 3]);
+        // a node still shared hands out a clone of its value
+        let kept = from_array.head.clone().unwrap();
+        let values: Vec<i32> = from_array.into();
+        assert_eq!(values, [1, 2, 3]);
+        assert_eq!(kept.borrow().value, 1);
+    }
+
+    #[test]
+    fn test_iterate_from_both_ends() {
+        let list = linked_list![1, 2, 3, 4, 5];
+        let reversed: Vec<i32> = list.values().rev().collect();
+        assert_eq!(reversed, [5, 4, 3, 2, 1]);
+        assert_eq!(list.values().rfind(|value| value % 2 == 0), Some(4));
+        assert_eq!(list.values().last(), Some(5));
+        assert_eq!(list.iter().len(), 5);
+
+        let mut nodes = list.iter();
+        assert_eq!(nodes.next().flatten().unwrap().borrow().value, 1);
+        assert_eq!(nodes.next_back().flatten().unwrap().borrow().value, 5);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes.peek().unwrap().borrow().value, 2);
+        assert!(nodes.peek_nth(3).is_none());
+        assert_eq!(nodes.next_back().flatten().unwrap().borrow().value, 4);
+        let rest: Vec<i32> = nodes
+            .by_ref()
+            .flatten()
+            .map(|node| node.borrow().value)
+            .collect();
+        assert_eq!(rest, [2, 3]);
+        assert!(nodes.next().is_none() && nodes.next_back().is_none());
+        assert!(nodes.peek().is_none());
+
+        assert_eq!(LinkedList::<i32>::new().values().next_back(), None);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut list = linked_list![1, 1, 2, 3, 3, 3, 1, 4, 4];
+        list.enable_content_hash();
+        list.dedup();
+        assert_eq!(list, [1, 2, 3, 1, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 4);
+        assert_eq!(list.content_hash(), content_hash_of(&[1, 2, 3, 1, 4]));
+
+        // compared against the last value kept, not the one just removed
+        let mut runs = linked_list![10, 11, 12, 20, 25, 31];
+        runs.dedup_by(|value, kept| value - kept < 2);
+        assert_eq!(runs, [10, 12, 20, 25, 31]);
+
+        let mut events = linked_list![("open", 1), ("open", 2), ("close", 3), ("open", 4)];
+        events.dedup_by_key(|event| event.0);
+        assert_eq!(events, [("open", 1), ("close", 3), ("open", 4)]);
+
+        let mut same = linked_list![7; 4];
+        same.dedup();
+        assert_eq!(same, [7]);
+        assert!(Rc::ptr_eq(
+            same.head.as_ref().unwrap(),
+            &same.tail_node().unwrap()
+        ));
+        let mut empty = LinkedList::<i32>::new();
+        empty.dedup();
+        assert!(empty.is_empty() && empty.tail.is_none());
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let mut list = linked_list![1, 4, 4, 9];
+        list.enable_content_hash();
+        let first_four = list.get_nth(1).unwrap().unwrap();
+        let other = linked_list![0, 4, 5, 10, 12];
+        let other_four = other.get_nth(1).unwrap().unwrap();
+        list.merge(other);
+        assert_eq!(list, [0, 1, 4, 4, 4, 5, 9, 10, 12]);
+        assert_eq!(list.len(), 9);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 12);
+        assert_eq!(
+            list.content_hash(),
+            content_hash_of(&[0, 1, 4, 4, 4, 5, 9, 10, 12])
+        );
+        // relinked, not copied, and stable on ties
+        assert!(Rc::ptr_eq(&list.get_nth(2).unwrap().unwrap(), &first_four));
+        assert!(Rc::ptr_eq(&list.get_nth(4).unwrap().unwrap(), &other_four));
+
+        list.merge(LinkedList::new());
+        assert_eq!(list.len(), 9);
+        let mut empty = LinkedList::new();
+        empty.merge(linked_list![2, 3]);
+        assert_eq!(empty, [2, 3]);
+        empty.push_back(7);
+        assert_eq!(empty, [2, 3, 7]);
+    }
+
+    #[test]
+    fn test_swap_by_index() {
+        let mut list = linked_list![1, 2, 3, 4, 5];
+        list.enable_content_hash();
+        let first = list.head.clone().unwrap();
+        list.swap(0, 4).unwrap();
+        list.swap(2, 1).unwrap();
+        list.swap(3, 3).unwrap();
+        assert_eq!(list, [5, 3, 2, 4, 1]);
+        assert_eq!(list.content_hash(), content_hash_of(&[5, 3, 2, 4, 1]));
+        // the values moved, the nodes didn't
+        assert!(Rc::ptr_eq(&list.head.clone().unwrap(), &first));
+        assert_eq!(
+            list.swap(1, 5),
+            Err(ListError::IndexOutOfBounds { index: 5, len: 5 })
+        );
+        assert_eq!(list, [5, 3, 2, 4, 1]);
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut list = linked_list![1, 5];
+        list.enable_content_hash();
+        let other = linked_list![2, 3, 4];
+        let moved = other.head.clone().unwrap();
+        list.splice(1, other).unwrap();
+        assert_eq!(list, [1, 2, 3, 4, 5]);
+        assert!(Rc::ptr_eq(&list.get_nth(1).unwrap().unwrap(), &moved));
+        assert_eq!(list.content_hash(), content_hash_of(&[1, 2, 3, 4, 5]));
+
+        list.splice(0, linked_list![0]).unwrap();
+        list.splice(6, linked_list![6, 7]).unwrap();
+        list.splice(3, LinkedList::new()).unwrap();
+        assert_eq!(list, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.len(), 8);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 7);
+        list.push_back(8);
+        assert_eq!(list.iter().count(), 9);
+        assert_eq!(
+            list.splice(10, linked_list![1]),
+            Err(ListError::IndexOutOfBounds { index: 10, len: 9 })
+        );
+
+        let mut empty = LinkedList::new();
+        empty.splice(0, linked_list![1, 2]).unwrap();
+        assert_eq!(empty, [1, 2]);
+        assert_eq!(empty.tail_node().unwrap().borrow().value, 2);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut list = LinkedList::new();
+        list.enable_content_hash();
+        for value in [5, 1, 3, 3, 9, 0] {
+            list.insert_sorted(value);
+        }
+        assert_eq!(list, [0, 1, 3, 3, 5, 9]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 9);
+        assert_eq!(list.content_hash(), content_hash_of(&[0, 1, 3, 3, 5, 9]));
+
+        // stable: equal values keep their insertion order
+        let mut tasks = LinkedList::new();
+        assert_eq!(tasks.insert_sorted((2, "b")), 0);
+        assert_eq!(tasks.insert_sorted((1, "a")), 0);
+        assert_eq!(tasks.insert_sorted((2, "a")), 1);
+        assert_eq!(tasks, [(1, "a"), (2, "a"), (2, "b")]);
+    }
+
+    #[test]
+    fn test_node_refs() {
+        let mut list = LinkedList::new();
+        list.enable_content_hash();
+        let two = list.push_back(2);
+        let one = list.push_front(1);
+        let four = list.insert(2, 4).unwrap();
+        let three = list.insert_after(&two, 3).unwrap();
+        let five = list.insert_after(&four, 5).unwrap();
+        assert_eq!(list, [1, 2, 3, 4, 5]);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 5);
+        assert_eq!(list.content_hash(), content_hash_of(&[1, 2, 3, 4, 5]));
+        assert_eq!(*list.value(&three).unwrap(), 3);
+
+        // the value is moved out, the handle doesn't keep the node
+        assert_eq!(list.remove(&one), Some(1));
+        assert_eq!(list.remove(&five), Some(5));
+        assert_eq!(list.remove(&one), None);
+        assert!(list.value(&one).is_none());
+        assert!(list.insert_after(&five, 6).is_none());
+        assert_eq!(list, [2, 3, 4]);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.tail_node().unwrap().borrow().value, 4);
+
+        let other = linked_list![7];
+        let foreign = other.head.as_ref().map(NodeRef::new).unwrap();
+        assert_eq!(list.remove(&foreign), None);
+        assert_eq!(*list.value(&four.clone()).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_node_refs_of_other_lists() {
+        let mut a = linked_list![1];
+        let mut b = LinkedList::new();
+        assert!(a.insert_after(&b.push_back(100), 5).is_none());
+        a.push_back(6);
+        assert_eq!((a.len(), b.len()), (2, 1));
+        assert_eq!(a, [1, 6]);
+        assert_eq!(b, [100]);
+
+        // unlinked, but still alive through a node handle
+        let first = a.push_front(0);
+        let held = a.get_nth(0).unwrap();
+        assert_eq!(a.pop_front(), Some(0));
+        assert!(a.value(&first).is_some());
+        assert!(a.insert_after(&first, 7).is_none());
+        assert_eq!(a.remove(&first), None);
+        assert_eq!(a, [1, 6]);
+        assert_eq!(a.tail_node().unwrap().borrow().value, 6);
+        drop(held);
+    }
+
+    // Links the tail back to the node at `to`, a cycle the API itself can't build.
+    fn close_cycle(list: &LinkedList<i32>, to: usize) {
+        let target = list.get_nth(to).unwrap();
+        list.tail_node().unwrap().borrow_mut().next = target;
+    }
+
+    #[test]
+    fn test_is_cyclic() {
+        assert!(!LinkedList::<i32>::new().is_cyclic());
+        for len in 1..6 {
+            for to in 0..len {
+                let list: LinkedList<i32> = (0..len as i32).collect();
+                assert!(!list.is_cyclic());
+                close_cycle(&list, to);
+                assert!(list.is_cyclic(), "{len} nodes back to {to}");
+                // bounded by len, so it still stops
+                assert_eq!(list.iter().count(), len);
+                list.tail_node().unwrap().borrow_mut().next = None;
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "formatting a cyclic list")]
+    fn test_display_cyclic() {
+        let list = linked_list![1, 2, 3];
+        close_cycle(&list, 1);
+        let _ = list.to_string();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "formatting a cyclic list")]
+    fn test_debug_cyclic() {
+        let list = linked_list![1, 2, 3];
+        close_cycle(&list, 0);
+        let _ = format!("{list:?}");
+    }
+}
@@ -0,0 +1,227 @@
+use alloc::string::String;
+use core::mem;
+
+use crate::cursor::Cursor;
+use crate::{LinkedList, ListError, NodeRef};
+
+// Editor-style text buffer, one node per line. The cursor is one of the list's
+// tracked cursors, so it stays on its line while lines are inserted or deleted
+// around it; deleting the line under it moves it to the following line (or the
+// new last line).
+pub struct LineBuffer {
+    lines: LinkedList<String>,
+    len: usize,
+    cursor: Cursor<String>,
+}
+
+const TRACKED: &str = "buffer cursor is only moved by pushes and sweeps";
+
+impl LineBuffer {
+    pub fn new() -> LineBuffer {
+        let lines = LinkedList::new();
+        let cursor = lines.cursor();
+        LineBuffer {
+            lines,
+            len: 0,
+            cursor,
+        }
+    }
+
+    pub fn from_text(text: &str) -> LineBuffer {
+        let mut buffer = LineBuffer::new();
+        for line in text.lines() {
+            buffer.lines.push_back(line.into());
+            buffer.len += 1;
+        }
+        buffer.cursor = buffer.lines.cursor();
+        buffer
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn line(&self, n: usize) -> Option<String> {
+        let node = self.lines.iter().nth(n)??;
+        let line = node.borrow().value.clone();
+        Some(line)
+    }
+
+    // Inserts before line n, n == len appends.
+    pub fn insert_line(&mut self, n: usize, line: &str) -> Result<(), ListError> {
+        self.lines.insert(n, line.into())?;
+        self.len += 1;
+        if self.len == 1 {
+            // the cursor of an empty buffer sits past the end
+            self.cursor = self.lines.cursor();
+        }
+        Ok(())
+    }
+
+    pub fn delete_line(&mut self, n: usize) -> Result<String, ListError> {
+        let node = self.lines.get_nth(n)?.unwrap();
+        self.lines.mark_remove(&NodeRef::from(&node))?;
+        self.lines.sweep();
+        self.len -= 1;
+        if self.cursor.current().expect(TRACKED).is_none() && self.len > 0 {
+            self.cursor.seek_backward(&self.lines, 1).expect(TRACKED);
+        }
+        let line = mem::take(&mut node.borrow_mut().value);
+        Ok(line)
+    }
+
+    // Moves line `from` so that it ends up at index `to`.
+    pub fn move_line(&mut self, from: usize, to: usize) -> Result<(), ListError> {
+        if from >= self.len || to >= self.len {
+            return Err(ListError::IndexOutOfBounds {
+                index: from.max(to),
+                len: self.len,
+            });
+        }
+        let line = self.delete_line(from)?;
+        self.insert_line(to, &line)
+    }
+
+    pub fn cursor_line(&self) -> usize {
+        self.cursor.index().expect(TRACKED)
+    }
+
+    pub fn current_line(&self) -> Option<String> {
+        let node = self.cursor.current().expect(TRACKED)?;
+        let line = node.borrow().value.clone();
+        Some(line)
+    }
+
+    // Both return how many lines the cursor actually moved.
+    pub fn cursor_down(&mut self, k: usize) -> usize {
+        let last = self.len.saturating_sub(1);
+        let k = k.min(last.saturating_sub(self.cursor_line()));
+        self.cursor.seek_forward(k).expect(TRACKED)
+    }
+
+    pub fn cursor_up(&mut self, k: usize) -> usize {
+        self.cursor.seek_backward(&self.lines, k).expect(TRACKED)
+    }
+}
+
+impl Default for LineBuffer {
+    fn default() -> Self {
+        LineBuffer::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_edit_lines() {
+        let mut buffer = LineBuffer::from_text("a\nb\nc");
+        assert_eq!(buffer.len(), 3);
+
+        buffer.insert_line(1, "x").unwrap();
+        buffer.insert_line(4, "end").unwrap();
+        assert!(buffer.insert_line(9, "no").is_err());
+        assert_eq!(buffer.text(), "a\nx\nb\nc\nend");
+
+        assert_eq!(buffer.delete_line(2).unwrap(), "b");
+        assert_eq!(
+            buffer.delete_line(4),
+            Err(ListError::IndexOutOfBounds { index: 4, len: 4 })
+        );
+        buffer.move_line(0, 3).unwrap();
+        assert_eq!(buffer.text(), "x\nc\nend\na");
+        assert_eq!(buffer.line(1).as_deref(), Some("c"));
+        assert!(buffer.line(4).is_none());
+    }
+
+    #[test]
+    fn test_cursor_navigation() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(buffer.cursor_line(), 0);
+        assert!(buffer.current_line().is_none());
+        assert_eq!(buffer.cursor_down(3), 0);
+
+        buffer.insert_line(0, "one").unwrap();
+        buffer.insert_line(1, "two").unwrap();
+        buffer.insert_line(2, "three").unwrap();
+        assert_eq!(buffer.current_line().as_deref(), Some("one"));
+        assert_eq!(buffer.cursor_down(5), 2);
+        assert_eq!(buffer.current_line().as_deref(), Some("three"));
+
+        buffer.insert_line(0, "zero").unwrap();
+        assert_eq!(buffer.cursor_line(), 3);
+        assert_eq!(buffer.cursor_up(2), 2);
+        assert_eq!(buffer.current_line().as_deref(), Some("one"));
+
+        buffer.delete_line(1).unwrap();
+        assert_eq!(buffer.current_line().as_deref(), Some("two"));
+        buffer.delete_line(2).unwrap();
+        buffer.delete_line(1).unwrap();
+        assert_eq!(buffer.current_line().as_deref(), Some("zero"));
+        buffer.delete_line(0).unwrap();
+        assert!(buffer.is_empty());
+        assert!(buffer.current_line().is_none());
+    }
+
+    // Random edits checked against a Vec of lines and a plain cursor index.
+    #[test]
+    fn test_against_model() {
+        let mut seed = 0x2545_f491_u32;
+        let mut random = |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as usize % bound
+        };
+        let mut buffer = LineBuffer::new();
+        let mut model: Vec<String> = Vec::new();
+        let mut cursor = 0;
+
+        for step in 0..2000 {
+            match random(5) {
+                0 | 1 => {
+                    let at = random(model.len() + 1);
+                    let line = format!("line {step}");
+                    buffer.insert_line(at, &line).unwrap();
+                    model.insert(at, line);
+                    if model.len() > 1 && at <= cursor {
+                        cursor += 1;
+                    }
+                }
+                2 if !model.is_empty() => {
+                    let at = random(model.len());
+                    assert_eq!(buffer.delete_line(at).unwrap(), model.remove(at));
+                    if at < cursor || cursor == model.len() && cursor > 0 {
+                        cursor -= 1;
+                    }
+                }
+                3 => {
+                    let k = random(4);
+                    let moved = buffer.cursor_down(k);
+                    assert_eq!(moved, k.min(model.len().saturating_sub(1) - cursor));
+                    cursor += moved;
+                }
+                _ => {
+                    let k = random(4);
+                    assert_eq!(buffer.cursor_up(k), k.min(cursor));
+                    cursor -= k.min(cursor);
+                }
+            }
+            assert_eq!(buffer.len(), model.len());
+            assert_eq!(buffer.cursor_line(), cursor);
+            assert_eq!(buffer.current_line(), model.get(cursor).cloned());
+        }
+        assert_eq!(buffer.text(), model.join("\n"));
+    }
+}
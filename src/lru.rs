@@ -0,0 +1,140 @@
+use std::cell::Ref;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use crate::{DoublyLinkedList, DoublyNodeHandle};
+
+const LINKED: &str = "the map only holds nodes linked in the list";
+
+// Least recently used cache: entries are kept in a DoublyLinkedList, most
+// recently used at the front, and the map points at their nodes. Using an
+// entry moves its node to the front and evicting takes the back node, both
+// O(1) thanks to the back links.
+pub struct LruCache<K, V> {
+    list: DoublyLinkedList<(K, V)>,
+    map: HashMap<K, DoublyNodeHandle<(K, V)>>,
+    capacity: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    // Panics if capacity is 0.
+    pub fn new(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "LruCache capacity must be at least 1");
+        LruCache {
+            list: DoublyLinkedList::new(),
+            map: HashMap::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    // Marks the entry as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<Ref<'_, V>> {
+        let node = self.map.get(key)?;
+        self.list.move_to_front(node).expect(LINKED);
+        Some(Ref::map(node.borrow(), |node| &node.value.1))
+    }
+
+    // Like `get`, without changing the order.
+    pub fn peek(&self, key: &K) -> Option<Ref<'_, V>> {
+        let node = self.map.get(key)?;
+        Some(Ref::map(node.borrow(), |node| &node.value.1))
+    }
+
+    // Inserts or replaces the value, returns the one replaced. A new key
+    // beyond the capacity evicts the least recently used entry.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(node) = self.map.get(&key) {
+            self.list.move_to_front(node).expect(LINKED);
+            return Some(std::mem::replace(&mut node.borrow_mut().value.1, value));
+        }
+        if self.list.len() == self.capacity {
+            let oldest = self.list.back_node().unwrap().borrow().value.0.clone();
+            self.remove(&oldest);
+        }
+        self.list.push_front((key.clone(), value));
+        self.map.insert(key, self.list.front_node().unwrap());
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.map.remove(key)?;
+        self.list.unlink(&node).expect(LINKED);
+        // the map and the list held the only handles
+        let node = Rc::try_unwrap(node).ok().unwrap();
+        Some(node.into_inner().value.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(cache: &LruCache<&'static str, i32>) -> Vec<&'static str> {
+        cache
+            .list
+            .iter()
+            .map(|node| node.borrow().value.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(*cache.get(&"a").unwrap(), 1);
+        assert_eq!(cache.put("c", 3), None);
+        assert!(!cache.contains(&"b"));
+        assert_eq!(keys(&cache), ["c", "a"]);
+
+        // peek leaves the order alone
+        assert_eq!(*cache.peek(&"a").unwrap(), 1);
+        assert_eq!(cache.put("d", 4), None);
+        assert_eq!(keys(&cache), ["d", "c"]);
+        assert_eq!(cache.len(), cache.capacity());
+        assert!(cache.get(&"a").is_none());
+    }
+
+    #[test]
+    fn test_replace_and_remove() {
+        let mut cache = LruCache::new(3);
+        for (key, value) in [("a", 1), ("b", 2), ("c", 3)] {
+            cache.put(key, value);
+        }
+        assert_eq!(cache.put("a", 10), Some(1));
+        assert_eq!(keys(&cache), ["a", "c", "b"]);
+        assert_eq!(cache.remove(&"c"), Some(3));
+        assert_eq!(cache.remove(&"c"), None);
+        assert_eq!(keys(&cache), ["a", "b"]);
+        cache.put("d", 4);
+        cache.put("e", 5);
+        assert_eq!(keys(&cache), ["e", "d", "a"]);
+        assert_eq!(cache.remove(&"a"), Some(10));
+        assert_eq!(cache.remove(&"e"), Some(5));
+        assert_eq!(cache.remove(&"d"), Some(4));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zero_capacity() {
+        LruCache::<u8, u8>::new(0);
+    }
+}
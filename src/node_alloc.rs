@@ -0,0 +1,117 @@
+use core::alloc::Layout;
+use core::cell::{Cell, UnsafeCell};
+use core::mem::MaybeUninit;
+use core::ptr::NonNull;
+
+// Where `boxed::LinkedList` gets its node memory from. A stable stand-in for
+// the unstable `Allocator` trait, narrowed to what a list needs: one fixed
+// layout per node type, no growing or shrinking.
+/// # Safety
+///
+/// A block returned by `allocate` must fit `layout` and stay valid, and not be
+/// handed out again, until it's passed to `deallocate`. That has to hold when
+/// the allocator value is moved, since the list moves it along with the nodes.
+pub unsafe trait NodeAlloc {
+    // None when out of memory, the list then aborts like `Box::new` does.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// # Safety
+    ///
+    /// `ptr` came from `allocate` on this allocator with the same layout.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+// The global heap, what `Box` allocates from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Global;
+
+unsafe impl NodeAlloc for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        // nodes always hold a link, so the layout is never zero sized
+        NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { alloc::alloc::dealloc(ptr.as_ptr(), layout) }
+    }
+}
+
+// Bump allocator over an inline buffer of N bytes, for targets without a heap
+// or for batches of lists that are all dropped together. Freed blocks are not
+// reused, `reset` reclaims the whole buffer once no list borrows it anymore.
+// Lists allocate through a shared reference, which keeps the buffer in place:
+//
+//     let bump = BumpAlloc::<1024>::new();
+//     let mut list = boxed::LinkedList::new_in(&bump);
+pub struct BumpAlloc<const N: usize> {
+    buffer: UnsafeCell<[MaybeUninit<u8>; N]>,
+    used: Cell<usize>,
+}
+
+impl<const N: usize> BumpAlloc<N> {
+    pub const fn new() -> BumpAlloc<N> {
+        BumpAlloc {
+            buffer: UnsafeCell::new([MaybeUninit::uninit(); N]),
+            used: Cell::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    // Bytes handed out since the last reset, alignment padding included.
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    pub fn reset(&mut self) {
+        self.used.set(0);
+    }
+}
+
+impl<const N: usize> Default for BumpAlloc<N> {
+    fn default() -> Self {
+        BumpAlloc::new()
+    }
+}
+
+unsafe impl<const N: usize> NodeAlloc for &BumpAlloc<N> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let base = self.buffer.get().cast::<u8>();
+        let used = self.used.get();
+        // align the address, the buffer itself is only byte aligned
+        let start = used + unsafe { base.add(used) }.align_offset(layout.align());
+        let end = start.checked_add(layout.size())?;
+        if end > N {
+            return None;
+        }
+        self.used.set(end);
+        NonNull::new(unsafe { base.add(start) })
+    }
+
+    // Blocks are only reclaimed by `reset`.
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_aligns_and_runs_out() {
+        let mut bump = BumpAlloc::<64>::new();
+        let byte = (&bump).allocate(Layout::new::<u8>()).unwrap();
+        let word = (&bump).allocate(Layout::new::<u64>()).unwrap();
+        assert_eq!(word.as_ptr() as usize % 8, 0);
+        assert!(word.as_ptr() as usize > byte.as_ptr() as usize);
+        assert!(bump.used() >= 9 && bump.used() <= 16);
+        assert!((&bump).allocate(Layout::new::<[u8; 64]>()).is_none());
+
+        bump.reset();
+        assert_eq!(bump.used(), 0);
+        assert!((&bump).allocate(Layout::new::<[u8; 64]>()).is_some());
+        assert!((&bump).allocate(Layout::new::<u8>()).is_none());
+        assert_eq!(bump.capacity(), 64);
+    }
+}
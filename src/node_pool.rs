@@ -0,0 +1,438 @@
+use core::cell::{Cell, UnsafeCell};
+use core::fmt::{Display, Formatter};
+#[cfg(target_has_atomic = "ptr")]
+use core::hint::spin_loop;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ListError;
+
+// The links of all nodes, guarded by the pool lock.
+struct PoolSlots<const N: usize> {
+    next: [Option<usize>; N],
+    free: Option<usize>,
+    used: usize,
+}
+
+// How a pool keeps its links to itself, in the shape of `critical_section::with`.
+// The closure only updates links: it never calls back into the pool or runs
+// user code, so the lock is never taken while already held. A pool used from an
+// interrupt handler on a single core needs a lock that masks that interrupt,
+// `SpinLock` would deadlock once the handler interrupts a holder.
+/// # Safety
+///
+/// Two calls of `with` on the same lock must never overlap, from any thread or
+/// interrupt handler the pool is reachable from.
+pub unsafe trait PoolLock {
+    const UNLOCKED: Self;
+
+    fn with<R>(&self, f: impl FnOnce() -> R) -> R;
+}
+
+// Spins between threads. Only on targets with compare-and-swap, and never for
+// a pool that an interrupt handler touches.
+#[cfg(target_has_atomic = "ptr")]
+pub struct SpinLock(AtomicBool);
+
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl PoolLock for SpinLock {
+    const UNLOCKED: Self = SpinLock(AtomicBool::new(false));
+
+    fn with<R>(&self, f: impl FnOnce() -> R) -> R {
+        while self
+            .0
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        let result = f();
+        self.0.store(false, Ordering::Release);
+        result
+    }
+}
+
+// No locking at all: the pool is not Sync, so it stays in the one context that
+// owns it and can't end up in a `static`.
+pub struct LocalLock(PhantomData<Cell<()>>);
+
+unsafe impl PoolLock for LocalLock {
+    const UNLOCKED: Self = LocalLock(PhantomData);
+
+    fn with<R>(&self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+pub type DefaultPoolLock = SpinLock;
+#[cfg(not(target_has_atomic = "ptr"))]
+pub type DefaultPoolLock = LocalLock;
+
+// Fixed set of nodes shared by any number of `PoolList`s. It is built in a
+// const context, so it can live in a `static` and be used without an allocator:
+//
+//     static POOL: NodePool<u32, 64> = NodePool::new();
+//     let mut list = PoolList::new(&POOL);
+//
+// Links are updated under the `PoolLock` L, held only for the few instructions
+// of a link update, which keeps allocation deterministic. Values sit outside
+// it, each owned by the list its slot is linked into, so user code (closures,
+// formatting, drops) never runs with the lock held and may use other lists of
+// the same pool. The default lock spins where compare-and-swap exists; without
+// it, or for a pool shared with interrupt handlers, plug in a lock that masks
+// interrupts.
+pub struct NodePool<T, const N: usize, L: PoolLock = DefaultPoolLock> {
+    lock: L,
+    slots: UnsafeCell<PoolSlots<N>>,
+    values: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// links are only reached under the lock, a value only through the list owning
+// its slot, and values move between threads with the lists
+unsafe impl<T: Send, const N: usize, L: PoolLock + Sync> Sync for NodePool<T, N, L> {}
+
+impl<T, const N: usize, L: PoolLock> NodePool<T, N, L> {
+    pub const fn new() -> NodePool<T, N, L> {
+        let mut next = [None; N];
+        let mut i = 0;
+        while i + 1 < N {
+            next[i] = Some(i + 1);
+            i += 1;
+        }
+        NodePool {
+            lock: L::UNLOCKED,
+            slots: UnsafeCell::new(PoolSlots {
+                next,
+                free: if N > 0 { Some(0) } else { None },
+                used: 0,
+            }),
+            values: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+        }
+    }
+
+    fn with_slots<R>(&self, f: impl FnOnce(&mut PoolSlots<N>) -> R) -> R {
+        // the lock makes this the only reference to the links
+        self.lock.with(|| f(unsafe { &mut *self.slots.get() }))
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn available(&self) -> usize {
+        N - self.with_slots(|slots| slots.used)
+    }
+}
+
+impl<T, const N: usize, L: PoolLock> Default for NodePool<T, N, L> {
+    fn default() -> Self {
+        NodePool::new()
+    }
+}
+
+impl<const N: usize> PoolSlots<N> {
+    fn alloc(&mut self, next: Option<usize>) -> Result<usize, ListError> {
+        let slot = self
+            .free
+            .ok_or(ListError::CapacityExceeded { capacity: N })?;
+        self.free = self.next[slot];
+        self.next[slot] = next;
+        self.used += 1;
+        Ok(slot)
+    }
+
+    fn release(&mut self, slot: usize) {
+        self.next[slot] = self.free;
+        self.free = Some(slot);
+        self.used -= 1;
+    }
+}
+
+impl<T, const N: usize, L: PoolLock> NodePool<T, N, L> {
+    // Takes a free slot and stores the value in it, the slot is then owned by
+    // the caller's list.
+    fn alloc(&self, value: T, next: Option<usize>) -> Result<usize, ListError> {
+        let slot = self.with_slots(|slots| slots.alloc(next))?;
+        // no one else reaches a slot that was just taken off the free list
+        unsafe { (*self.values[slot].get()).write(value) };
+        Ok(slot)
+    }
+
+    // Moves the value out of a slot unlinked by its list. `release` must follow,
+    // once the value is read another list may take the slot.
+    fn take(&self, slot: usize) -> T {
+        unsafe { (*self.values[slot].get()).assume_init_read() }
+    }
+}
+
+// List whose nodes are taken from, and returned to, a `NodePool`.
+pub struct PoolList<'a, T, const N: usize, L: PoolLock = DefaultPoolLock> {
+    pool: &'a NodePool<T, N, L>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    // values are read without the lock, sharing a list shares them
+    _values: PhantomData<T>,
+}
+
+impl<'a, T, const N: usize, L: PoolLock> PoolList<'a, T, N, L> {
+    pub fn new(pool: &'a NodePool<T, N, L>) -> PoolList<'a, T, N, L> {
+        PoolList {
+            pool,
+            head: None,
+            tail: None,
+            len: 0,
+            _values: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_back(&mut self, value: T) -> Result<(), ListError> {
+        let slot = self.pool.alloc(value, None)?;
+        match self.tail {
+            None => self.head = Some(slot),
+            Some(tail) => self.pool.with_slots(|slots| slots.next[tail] = Some(slot)),
+        }
+        self.tail = Some(slot);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, value: T) -> Result<(), ListError> {
+        let slot = self.pool.alloc(value, self.head)?;
+        if self.head.is_none() {
+            self.tail = Some(slot);
+        }
+        self.head = Some(slot);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let slot = self.head?;
+        let value = self.pool.take(slot);
+        self.head = self.pool.with_slots(|slots| {
+            let next = slots.next[slot];
+            slots.release(slot);
+            next
+        });
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    // The lock is only taken to follow each link, f may use other lists of the
+    // same pool.
+    pub fn for_each(&self, mut f: impl FnMut(&T)) {
+        let mut current = self.head;
+        while let Some(slot) = current {
+            // the slot is linked into this list, which is borrowed for the call
+            f(unsafe { (*self.pool.values[slot].get()).assume_init_ref() });
+            current = self.pool.with_slots(|slots| slots.next[slot]);
+        }
+    }
+}
+
+// Values are dropped one by one after their slot is returned, not under the lock.
+impl<T, const N: usize, L: PoolLock> Drop for PoolList<'_, T, N, L> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T: Display, const N: usize, L: PoolLock> Display for PoolList<'_, T, N, L> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut result = write!(f, "[");
+        let mut first = true;
+        self.for_each(|value| {
+            if result.is_ok() {
+                result = if first {
+                    write!(f, "{value}")
+                } else {
+                    write!(f, ", {value}")
+                };
+                first = false;
+            }
+        });
+        result?;
+        write!(f, "]")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, const N: usize, L: PoolLock> defmt::Format for PoolList<'_, T, N, L> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        let mut first = true;
+        self.for_each(|value| {
+            if !first {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", value);
+            first = false;
+        });
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::thread;
+
+    #[test]
+    fn test_lists_share_pool() {
+        static POOL: NodePool<u32, 4> = NodePool::new();
+        let mut first = PoolList::new(&POOL);
+        let mut sec = PoolList::new(&POOL);
+
+        first.push_back(1).unwrap();
+        first.push_back(2).unwrap();
+        sec.push_front(4).unwrap();
+        sec.push_front(3).unwrap();
+        assert_eq!(POOL.available(), 0);
+        assert_eq!(
+            first.push_back(5),
+            Err(ListError::CapacityExceeded { capacity: 4 })
+        );
+        assert!(sec.push_front(0).is_err());
+
+        assert_eq!(first.pop_front(), Some(1));
+        sec.push_back(5).unwrap();
+        assert_eq!(first.to_string(), "[2]");
+        assert_eq!(sec.to_string(), "[3, 4, 5]");
+        assert_eq!(sec.len(), 3);
+
+        drop(sec);
+        assert_eq!(POOL.available(), 3);
+        assert_eq!(first.pop_front(), Some(2));
+        assert_eq!(first.pop_front(), None);
+        assert!(first.is_empty());
+        assert_eq!(POOL.available(), POOL.capacity());
+    }
+
+    #[test]
+    fn test_drop_returns_values() {
+        let pool = NodePool::<Rc<i32>, 3>::new();
+        let value = Rc::new(1);
+        let mut list = PoolList::new(&pool);
+        list.push_back(value.clone()).unwrap();
+        list.push_back(value.clone()).unwrap();
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(pool.available(), 3);
+    }
+
+    #[test]
+    fn test_pool_across_threads() {
+        static POOL: NodePool<usize, 64> = NodePool::new();
+        let workers: Vec<_> = (0..4)
+            .map(|id| {
+                thread::spawn(move || {
+                    let mut list = PoolList::new(&POOL);
+                    for round in 0..1000 {
+                        list.push_back(id * round).unwrap();
+                        if list.len() > 8 {
+                            list.pop_front();
+                        }
+                    }
+                    list.len()
+                })
+            })
+            .collect();
+        for worker in workers {
+            assert_eq!(worker.join().unwrap(), 8);
+        }
+        assert_eq!(POOL.available(), POOL.capacity());
+    }
+
+    // Values whose drop goes back to the pool they live in.
+    struct Reentrant(u32);
+
+    static REENTRANT: NodePool<Reentrant, 4> = NodePool::new();
+
+    impl Drop for Reentrant {
+        fn drop(&mut self) {
+            assert!(REENTRANT.available() > 0);
+        }
+    }
+
+    impl Display for Reentrant {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+            write!(
+                f,
+                "{} of {}",
+                self.0,
+                REENTRANT.capacity() - REENTRANT.available()
+            )
+        }
+    }
+
+    #[test]
+    fn test_user_code_runs_unlocked() {
+        let mut list = PoolList::new(&REENTRANT);
+        list.push_back(Reentrant(1)).unwrap();
+        list.push_back(Reentrant(2)).unwrap();
+        assert_eq!(list.to_string(), "[1 of 2, 2 of 2]");
+
+        let mut copies = PoolList::new(&REENTRANT);
+        list.for_each(|value| copies.push_back(Reentrant(value.0 * 10)).unwrap());
+        assert_eq!(copies.to_string(), "[10 of 4, 20 of 4]");
+        drop(copies.pop_front());
+        drop(copies);
+        drop(list);
+        assert_eq!(REENTRANT.available(), REENTRANT.capacity());
+    }
+
+    // Stands in for an interrupt-masking lock and checks it is never nested.
+    struct CheckedLock(Cell<bool>);
+
+    unsafe impl PoolLock for CheckedLock {
+        const UNLOCKED: Self = CheckedLock(Cell::new(false));
+
+        fn with<R>(&self, f: impl FnOnce() -> R) -> R {
+            assert!(!self.0.replace(true), "pool lock taken while held");
+            let result = f();
+            self.0.set(false);
+            result
+        }
+    }
+
+    #[test]
+    fn test_custom_lock() {
+        let pool = NodePool::<String, 4, CheckedLock>::new();
+        let mut list = PoolList::new(&pool);
+        list.push_back("a".to_string()).unwrap();
+        list.push_front("b".to_string()).unwrap();
+        let mut lengths = PoolList::new(&pool);
+        list.for_each(|value| lengths.push_back(format!("{}", value.len())).unwrap());
+        assert_eq!(
+            lengths.push_back("x".to_string()),
+            Err(ListError::CapacityExceeded { capacity: 4 })
+        );
+        assert_eq!(list.to_string(), "[b, a]");
+        assert_eq!(list.pop_front().as_deref(), Some("b"));
+        drop(list);
+        assert_eq!(pool.available(), 2);
+
+        let local = NodePool::<u8, 1, LocalLock>::new();
+        let mut list = PoolList::new(&local);
+        list.push_back(7).unwrap();
+        assert_eq!(list.to_string(), "[7]");
+    }
+}
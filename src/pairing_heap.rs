@@ -0,0 +1,235 @@
+use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+use core::mem;
+
+use crate::{LinkedList, ListError, Node};
+
+type HeapHandle<T> = Rc<RefCell<HeapNode<T>>>;
+// Children are kept in a list of the crate's own nodes. A child cut out by
+// `decrease_key` leaves an empty, removed slot behind, so it is O(1) and the
+// slot is skipped once the parent is popped.
+type ChildSlot<T> = Node<Option<HeapHandle<T>>>;
+
+struct HeapNode<T> {
+    value: T,
+    children: LinkedList<Option<HeapHandle<T>>>,
+    // list node in the parent's children holding this one, dangling for the root
+    slot: Weak<RefCell<ChildSlot<T>>>,
+}
+
+// Min-heap: `pop` returns the smallest value. Push, peek, merge and
+// decrease_key are O(1), pop is amortized O(log n).
+pub struct PairingHeap<T> {
+    root: Option<HeapHandle<T>>,
+    len: usize,
+}
+
+// Returned by `push`, refers to the value for `decrease_key` until it's popped.
+// Only valid with the heap it was pushed to, or the heap that one was merged into.
+pub struct HeapEntry<T>(Weak<RefCell<HeapNode<T>>>);
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> PairingHeap<T> {
+        PairingHeap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, value: T) -> HeapEntry<T> {
+        let node = Rc::new(RefCell::new(HeapNode {
+            value,
+            children: LinkedList::new(),
+            slot: Weak::new(),
+        }));
+        let entry = HeapEntry(Rc::downgrade(&node));
+        self.root = Some(meld(self.root.take(), node));
+        self.len += 1;
+        entry
+    }
+
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        let root = self.root.as_ref()?;
+        Some(Ref::map(root.borrow(), |root| &root.value))
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let root = match Rc::try_unwrap(root) {
+            Ok(root) => root.into_inner(),
+            Err(_) => unreachable!("heap nodes are owned by their parent only"),
+        };
+        let mut children = Vec::new();
+        for slot in root.children.iter().flatten() {
+            if let Some(child) = slot.borrow_mut().value.take() {
+                child.borrow_mut().slot = Weak::new();
+                children.push(child);
+            }
+        }
+        // pair up the children left to right, then meld the pairs right to left
+        let mut pairs = Vec::with_capacity(children.len() / 2 + 1);
+        let mut children = children.into_iter();
+        while let Some(first) = children.next() {
+            pairs.push(match children.next() {
+                None => first,
+                Some(second) => meld(Some(first), second),
+            });
+        }
+        self.root = pairs
+            .into_iter()
+            .rev()
+            .fold(None, |root, pair| Some(meld(root, pair)));
+        self.len -= 1;
+        Some(root.value)
+    }
+
+    pub fn merge(&mut self, mut other: PairingHeap<T>) {
+        if let Some(other_root) = other.root.take() {
+            self.root = Some(meld(self.root.take(), other_root));
+            self.len += mem::take(&mut other.len);
+        }
+    }
+
+    pub fn decrease_key(&mut self, entry: &HeapEntry<T>, value: T) -> Result<(), ListError> {
+        let node = entry.0.upgrade().ok_or(ListError::StaleHandle)?;
+        if value > node.borrow().value {
+            return Err(ListError::KeyIncreased);
+        }
+        node.borrow_mut().value = value;
+        let slot = mem::take(&mut node.borrow_mut().slot);
+        if let Some(slot) = slot.upgrade() {
+            let mut slot = slot.borrow_mut();
+            slot.value = None;
+            slot.removed = true;
+            self.root = Some(meld(self.root.take(), node));
+        }
+        Ok(())
+    }
+}
+
+// A chain of children can be as deep as the heap is long, so the nodes are
+// taken apart from a worklist instead of dropping each subtree recursively.
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<HeapHandle<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            let Ok(node) = Rc::try_unwrap(node) else {
+                continue;
+            };
+            for slot in node.into_inner().children.iter().flatten() {
+                pending.extend(slot.borrow_mut().value.take());
+            }
+        }
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        PairingHeap::new()
+    }
+}
+
+// Makes the root with the larger value the first child of the other one.
+fn meld<T: Ord>(root: Option<HeapHandle<T>>, node: HeapHandle<T>) -> HeapHandle<T> {
+    let Some(root) = root else {
+        return node;
+    };
+    let (parent, child) = if node.borrow().value < root.borrow().value {
+        (node, root)
+    } else {
+        (root, node)
+    };
+    let mut parent_node = parent.borrow_mut();
+    parent_node.children.push_front(Some(child.clone()));
+    child.borrow_mut().slot = Rc::downgrade(parent_node.children.head.as_ref().unwrap());
+    drop(parent_node);
+    parent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(heap: &mut PairingHeap<i32>) -> Vec<i32> {
+        let mut values = Vec::new();
+        while let Some(value) = heap.pop() {
+            values.push(value);
+        }
+        values
+    }
+
+    #[test]
+    fn test_push_pop_in_order() {
+        let mut heap = PairingHeap::new();
+        for value in [5, 1, 8, 3, 9, 2, 7, 3] {
+            heap.push(value);
+        }
+
+        assert_eq!(heap.len(), 8);
+        assert_eq!(*heap.peek().unwrap(), 1);
+        assert_eq!(drain(&mut heap), [1, 2, 3, 3, 5, 7, 8, 9]);
+        assert!(heap.is_empty());
+        assert!(heap.peek().is_none());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut heap = PairingHeap::new();
+        let mut other = PairingHeap::new();
+        for value in [4, 6, 2] {
+            heap.push(value);
+        }
+        for value in [5, 1, 3] {
+            other.push(value);
+        }
+
+        heap.merge(other);
+        heap.merge(PairingHeap::new());
+
+        assert_eq!(heap.len(), 6);
+        assert_eq!(drain(&mut heap), [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = PairingHeap::new();
+        let entries: Vec<_> = (10..20).map(|value| heap.push(value)).collect();
+        assert_eq!(heap.pop(), Some(10));
+
+        heap.decrease_key(&entries[7], 1).unwrap();
+        heap.decrease_key(&entries[3], 12).unwrap();
+        heap.decrease_key(&entries[5], 5).unwrap();
+        assert_eq!(
+            heap.decrease_key(&entries[9], 30),
+            Err(ListError::KeyIncreased)
+        );
+        assert_eq!(
+            heap.decrease_key(&entries[0], 0),
+            Err(ListError::StaleHandle)
+        );
+
+        assert_eq!(heap.len(), 9);
+        assert_eq!(drain(&mut heap), [1, 5, 11, 12, 12, 14, 16, 18, 19]);
+    }
+
+    #[test]
+    fn test_deep_heap() {
+        // descending pushes chain every node below the next one
+        let mut heap = PairingHeap::new();
+        let entries: Vec<_> = (0..1_000_000)
+            .rev()
+            .map(|value| heap.push(value + 10))
+            .collect();
+        heap.decrease_key(&entries[0], 1).unwrap();
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.len(), 999_998);
+        drop(heap);
+    }
+}
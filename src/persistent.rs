@@ -0,0 +1,166 @@
+use alloc::rc::Rc;
+
+struct PersistentNode<T> {
+    value: T,
+    next: Option<Rc<PersistentNode<T>>>,
+}
+
+// Immutable cons list: `push_front` and `tail` return new lists sharing the
+// nodes behind them through Rc, so every version stays valid and a snapshot
+// is a clone of one Rc. Nodes are never mutated, no RefCell is needed.
+pub struct PersistentList<T> {
+    head: Option<Rc<PersistentNode<T>>>,
+    len: usize,
+}
+
+pub struct PersistentListIter<'a, T> {
+    current: Option<&'a PersistentNode<T>>,
+}
+
+impl<'a, T> Iterator for PersistentListIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+impl<T> PersistentList<T> {
+    pub fn new() -> PersistentList<T> {
+        PersistentList { head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // O(1), self is left as it was.
+    pub fn push_front(&self, value: T) -> PersistentList<T> {
+        PersistentList {
+            head: Some(Rc::new(PersistentNode {
+                value,
+                next: self.head.clone(),
+            })),
+            len: self.len + 1,
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    // Everything after the head, sharing its nodes; empty for an empty list.
+    pub fn tail(&self) -> PersistentList<T> {
+        match &self.head {
+            None => PersistentList::new(),
+            Some(node) => PersistentList {
+                head: node.next.clone(),
+                len: self.len - 1,
+            },
+        }
+    }
+
+    pub fn iter(&self) -> PersistentListIter<'_, T> {
+        PersistentListIter {
+            current: self.head.as_deref(),
+        }
+    }
+
+    // Whether both lists start at the same node, i.e. one is a snapshot of the other.
+    pub fn ptr_eq(&self, other: &PersistentList<T>) -> bool {
+        match (&self.head, &other.head) {
+            (None, None) => true,
+            (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Clone for PersistentList<T> {
+    fn clone(&self) -> Self {
+        PersistentList {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentList<T> {
+    fn default() -> Self {
+        PersistentList::new()
+    }
+}
+
+// The list reads front to back in iteration order, so it's built from the back.
+impl<T> FromIterator<T> for PersistentList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let values: alloc::vec::Vec<T> = iter.into_iter().collect();
+        values
+            .into_iter()
+            .rev()
+            .fold(PersistentList::new(), |list, value| list.push_front(value))
+    }
+}
+
+// Frees the nodes only this list holds, front to back, and stops at the first
+// one shared with another version.
+impl<T> Drop for PersistentList<T> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(node) = current {
+            current = match Rc::try_unwrap(node) {
+                Ok(node) => node.next,
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_versions_share_nodes() {
+        let empty = PersistentList::default();
+        let one = empty.push_front(1);
+        let two = one.push_front(2);
+        let other = one.push_front(3);
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), [3, 1]);
+        assert_eq!(one.iter().copied().collect::<Vec<_>>(), [1]);
+        assert!(empty.is_empty() && empty.head().is_none());
+
+        assert_eq!(two.head(), Some(&2));
+        assert!(two.tail().ptr_eq(&one));
+        assert!(other.tail().ptr_eq(&two.tail()));
+        assert_eq!(two.tail().len(), 1);
+        assert!(one.tail().tail().is_empty());
+
+        let snapshot = two.clone();
+        drop(two);
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.tail().ptr_eq(&one));
+    }
+
+    #[test]
+    fn test_drop_keeps_shared_nodes() {
+        let value = Rc::new(());
+        let base: PersistentList<_> = (0..3).map(|_| value.clone()).collect();
+        let longer = base.push_front(value.clone());
+        drop(base);
+        assert_eq!(Rc::strong_count(&value), 5);
+        drop(longer);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let long: PersistentList<_> = (0..1_000_000).collect();
+        assert_eq!(long.head(), Some(&0));
+        drop(long);
+    }
+}
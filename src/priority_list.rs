@@ -0,0 +1,115 @@
+use crate::{SortedLinkedList, ValueRef};
+
+// Simple priority queue over a SortedLinkedList: push is O(n), the minimum is
+// at the head so peek_min, pop_min and peek_max are O(1). pop_max is O(n), it
+// has to find the node before the tail. Equal priorities come out in the order
+// they were pushed from the min end.
+pub struct PriorityList<T> {
+    sorted: SortedLinkedList<T>,
+}
+
+impl<T: Ord> PriorityList<T> {
+    pub fn new() -> PriorityList<T> {
+        PriorityList {
+            sorted: SortedLinkedList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.sorted.insert(value);
+    }
+
+    pub fn peek_min(&self) -> Option<ValueRef<'_, T>> {
+        self.sorted.first()
+    }
+
+    pub fn peek_max(&self) -> Option<ValueRef<'_, T>> {
+        self.sorted.last()
+    }
+}
+
+impl<T: Ord + Clone> PriorityList<T> {
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.sorted.pop_first()
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.sorted.pop_last()
+    }
+}
+
+impl<T: Ord> Default for PriorityList<T> {
+    fn default() -> Self {
+        PriorityList::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PriorityList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PriorityList {
+            sorted: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pop_both_ends() {
+        let mut queue = PriorityList::default();
+        assert!(queue.peek_min().is_none() && queue.pop_max().is_none());
+        for value in [4, 9, 1, 7, 1] {
+            queue.push(value);
+        }
+        assert_eq!(queue.len(), 5);
+        assert_eq!(*queue.peek_min().unwrap(), 1);
+        assert_eq!(*queue.peek_max().unwrap(), 9);
+        assert_eq!(queue.pop_max(), Some(9));
+        assert_eq!(queue.pop_min(), Some(1));
+        assert_eq!(queue.pop_min(), Some(1));
+        assert_eq!(*queue.peek_max().unwrap(), 7);
+        assert_eq!(queue.pop_max(), Some(7));
+        assert_eq!(queue.pop_max(), Some(4));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_equal_priorities_in_push_order() {
+        // ordered by priority only
+        #[derive(Clone, Debug)]
+        struct Task(u8, &'static str);
+        impl PartialEq for Task {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Task {}
+        impl PartialOrd for Task {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Task {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut queue: PriorityList<_> = [Task(2, "c"), Task(1, "a")].into_iter().collect();
+        queue.push(Task(2, "b"));
+        let names: Vec<_> = core::iter::from_fn(|| queue.pop_min())
+            .map(|task| task.1)
+            .collect();
+        assert_eq!(names, ["a", "c", "b"]);
+    }
+}
@@ -0,0 +1,46 @@
+use serde::ser::SerializeSeq;
+use serde::{Serialize, Serializer};
+
+use crate::LinkedList;
+
+// A plain sequence, the same shape Vec<T> has, so the list reads back through
+// the Deserialize impl or into any other sequence type.
+impl<T: Serialize> Serialize for LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for node in self.iter().flatten() {
+            seq.serialize_element(&node.borrow().value)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_json_round_trip() {
+        let list: LinkedList<(u8, String)> = [(1, "a"), (2, "b\"c")]
+            .into_iter()
+            .map(|(n, s)| (n, String::from(s)))
+            .collect();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"[[1,"a"],[2,"b\"c"]]"#);
+        let back: LinkedList<(u8, String)> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, list);
+
+        let empty: LinkedList<u8> = serde_json::from_str("[]").unwrap();
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "[]");
+        let nested: LinkedList<LinkedList<u8>> = serde_json::from_str("[[1],[],[2,3]]").unwrap();
+        assert_eq!(serde_json::to_string(&nested).unwrap(), "[[1],[],[2,3]]");
+
+        // same shape as a Vec
+        let as_vec: Vec<Vec<u8>> =
+            serde_json::from_str(&serde_json::to_string(&nested).unwrap()).unwrap();
+        assert_eq!(as_vec, [vec![1], vec![], vec![2, 3]]);
+    }
+}
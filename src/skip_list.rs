@@ -0,0 +1,273 @@
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefCell};
+
+const MAX_LEVEL: usize = 16;
+
+type SkipHandle<T> = Rc<RefCell<SkipNode<T>>>;
+type SkipLink<T> = Option<SkipHandle<T>>;
+
+struct SkipNode<T> {
+    value: T,
+    // one forward link per level the node is on, level 0 links every node
+    next: Vec<SkipLink<T>>,
+}
+
+// Sorted set over Rc<RefCell<..>> nodes with O(log n) expected search, insert
+// and remove. Level 0 is a plain sorted linked list, every level above skips
+// over about half the nodes of the one below. Node heights come from a small
+// xorshift generator, so a list built from the same seed and the same
+// operations always has the same shape.
+pub struct SkipList<T> {
+    head: [SkipLink<T>; MAX_LEVEL],
+    // levels in use, the head links above are None
+    level: usize,
+    len: usize,
+    rng: u64,
+}
+
+// Yields clones of the values in ascending order.
+pub struct SkipListIter<'a, T> {
+    _list: &'a SkipList<T>,
+    current: SkipLink<T>,
+}
+
+impl<T: Clone> Iterator for SkipListIter<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        let node = node.borrow();
+        self.current = node.next[0].clone();
+        Some(node.value.clone())
+    }
+}
+
+impl<T: Ord> SkipList<T> {
+    pub fn new() -> SkipList<T> {
+        SkipList::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+
+    // Same seed, same node heights: for reproducible tests and benchmarks.
+    pub fn with_seed(seed: u64) -> SkipList<T> {
+        SkipList {
+            head: Default::default(),
+            level: 0,
+            len: 0,
+            // xorshift gets stuck on 0
+            rng: seed.max(1),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn random_height(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        // each further level with probability 1/2
+        (self.rng.trailing_ones() as usize + 1).min(MAX_LEVEL)
+    }
+
+    // None stands for the head.
+    fn next_of(&self, pred: &SkipLink<T>, level: usize) -> SkipLink<T> {
+        match pred {
+            None => self.head[level].clone(),
+            Some(node) => node.borrow().next[level].clone(),
+        }
+    }
+
+    fn set_next(&mut self, pred: &SkipLink<T>, level: usize, next: SkipLink<T>) {
+        match pred {
+            None => self.head[level] = next,
+            Some(node) => node.borrow_mut().next[level] = next,
+        }
+    }
+
+    // The last node before `value` on every level in use, None for the head.
+    fn predecessors(&self, value: &T) -> Vec<SkipLink<T>> {
+        let mut preds = vec![None; self.level];
+        let mut current = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next_of(&current, level) {
+                if next.borrow().value >= *value {
+                    break;
+                }
+                current = Some(next);
+            }
+            preds[level] = current.clone();
+        }
+        preds
+    }
+
+    // The node holding `value`, found from its predecessors.
+    fn find_after(&self, preds: &[SkipLink<T>], value: &T) -> SkipLink<T> {
+        let pred = preds.first().cloned().flatten();
+        self.next_of(&pred, 0)
+            .filter(|node| node.borrow().value == *value)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.find_after(&self.predecessors(value), value).is_some()
+    }
+
+    // Returns false and leaves the list alone if the value is already in it.
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut preds = self.predecessors(&value);
+        if self.find_after(&preds, &value).is_some() {
+            return false;
+        }
+        let height = self.random_height();
+        if height > self.level {
+            preds.resize(height, None);
+            self.level = height;
+        }
+        let node = Rc::new(RefCell::new(SkipNode {
+            value,
+            next: vec![None; height],
+        }));
+        for (level, pred) in preds.iter().enumerate().take(height) {
+            node.borrow_mut().next[level] = self.next_of(pred, level);
+            self.set_next(pred, level, Some(node.clone()));
+        }
+        self.len += 1;
+        true
+    }
+
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let preds = self.predecessors(value);
+        let node = self.find_after(&preds, value)?;
+        let height = node.borrow().next.len();
+        for (level, pred) in preds.iter().enumerate().take(height) {
+            let next = node.borrow_mut().next[level].take();
+            self.set_next(pred, level, next);
+        }
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+        // the links just replaced were the only other handles
+        Some(Rc::try_unwrap(node).ok().unwrap().into_inner().value)
+    }
+
+    // The smallest value.
+    pub fn first(&self) -> Option<Ref<'_, T>> {
+        let first = self.head[0].as_ref()?;
+        Some(Ref::map(first.borrow(), |node| &node.value))
+    }
+
+    pub fn iter(&self) -> SkipListIter<'_, T> {
+        SkipListIter {
+            _list: self,
+            current: self.head[0].clone(),
+        }
+    }
+}
+
+impl<T: Ord> Default for SkipList<T> {
+    fn default() -> Self {
+        SkipList::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SkipList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        for value in iter {
+            list.insert(value);
+        }
+        list
+    }
+}
+
+// Walks level 0 and clears each node's links on the way, so neither the
+// chain nor the upper levels are dropped recursively.
+impl<T> Drop for SkipList<T> {
+    fn drop(&mut self) {
+        for link in self.head.iter_mut().skip(1) {
+            *link = None;
+        }
+        let mut current = self.head[0].take();
+        while let Some(node) = current {
+            let mut node = node.borrow_mut();
+            current = node.next[0].take();
+            node.next.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::BTreeSet;
+
+    fn heights<T: Ord>(list: &SkipList<T>) -> Vec<usize> {
+        let mut heights = Vec::new();
+        let mut current = list.head[0].clone();
+        while let Some(node) = current {
+            heights.push(node.borrow().next.len());
+            current = node.borrow().next[0].clone();
+        }
+        heights
+    }
+
+    #[test]
+    fn test_matches_btreeset() {
+        let mut list = SkipList::with_seed(42);
+        let mut model = BTreeSet::new();
+        let mut seed = 11u32;
+        for _ in 0..5000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let value = (seed >> 16) % 500;
+            match seed % 3 {
+                0 => assert_eq!(list.remove(&value), model.take(&value)),
+                _ => assert_eq!(list.insert(value), model.insert(value)),
+            }
+            assert_eq!(list.len(), model.len());
+        }
+        assert!(list.iter().eq(model.iter().copied()));
+        assert_eq!(list.first().map(|first| *first), model.first().copied());
+        for value in 0..500 {
+            assert_eq!(list.contains(&value), model.contains(&value));
+        }
+        // about half the nodes reach level 2
+        let tall = heights(&list).iter().filter(|&&height| height > 1).count();
+        assert!(tall > list.len() / 4 && tall < list.len() * 3 / 4);
+    }
+
+    #[test]
+    fn test_seed_fixes_shape() {
+        let mut ascending = SkipList::with_seed(7);
+        let mut descending = SkipList::with_seed(7);
+        let mut other_seed = SkipList::with_seed(8);
+        for value in 0..100 {
+            ascending.insert(value);
+            descending.insert(99 - value);
+            other_seed.insert(value);
+        }
+        // same seed, same heights handed out, here in reverse order
+        let mut reversed = heights(&descending);
+        reversed.reverse();
+        assert_eq!(heights(&ascending), reversed);
+        assert_ne!(heights(&ascending), heights(&other_seed));
+
+        let mut emptied = SkipList::default();
+        emptied.insert(1);
+        assert!(!emptied.insert(1));
+        assert_eq!(emptied.remove(&1), Some(1));
+        assert_eq!(emptied.remove(&1), None);
+        assert!(emptied.is_empty() && emptied.level == 0 && emptied.first().is_none());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let list: SkipList<u32> = (0..300_000).collect();
+        drop(list);
+    }
+}
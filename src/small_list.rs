@@ -0,0 +1,341 @@
+use core::fmt::{Debug, Formatter};
+use core::mem::{self, MaybeUninit};
+use core::{ptr, slice};
+
+use crate::boxed;
+
+// Up to N values kept in order at the start of an inline array.
+struct Inline<T, const N: usize> {
+    values: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Inline<T, N> {
+    fn new() -> Inline<T, N> {
+        Inline {
+            values: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    fn as_slice(&self) -> &[T] {
+        // the first `len` values are initialized
+        unsafe { slice::from_raw_parts(self.values.as_ptr().cast(), self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.values.as_mut_ptr().cast(), self.len) }
+    }
+
+    // The caller checks there is room, shifting at most N values.
+    fn insert(&mut self, index: usize, value: T) {
+        let base = self.values.as_mut_ptr().cast::<T>();
+        unsafe {
+            ptr::copy(base.add(index), base.add(index + 1), self.len - index);
+            base.add(index).write(value);
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        let base = self.values.as_mut_ptr().cast::<T>();
+        self.len -= 1;
+        unsafe {
+            let value = base.add(index).read();
+            ptr::copy(base.add(index + 1), base.add(index), self.len - index);
+            value
+        }
+    }
+
+    // Moves the values out and leaves the array empty.
+    fn take_all(&mut self) -> boxed::LinkedList<T> {
+        let len = mem::replace(&mut self.len, 0);
+        self.values[..len]
+            .iter()
+            .map(|value| unsafe { value.assume_init_read() })
+            .collect()
+    }
+}
+
+impl<T, const N: usize> Drop for Inline<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+enum SmallRepr<T, const N: usize> {
+    Inline(Inline<T, N>),
+    Linked(boxed::LinkedList<T>),
+}
+
+// List of up to N values stored inline, without allocating, that moves them
+// into a `boxed::LinkedList` on the push that would overflow. Once spilled it
+// stays linked, like smallvec, so a list that hovers around N doesn't move its
+// values back and forth.
+pub struct SmallList<T, const N: usize> {
+    repr: SmallRepr<T, N>,
+}
+
+pub enum SmallListIter<'a, T> {
+    Inline(slice::Iter<'a, T>),
+    Linked(boxed::Iter<'a, T>),
+}
+
+impl<'a, T> Iterator for SmallListIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SmallListIter::Inline(iter) => iter.next(),
+            SmallListIter::Linked(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            SmallListIter::Inline(iter) => iter.size_hint(),
+            SmallListIter::Linked(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for SmallListIter<'_, T> {}
+
+impl<T, const N: usize> SmallList<T, N> {
+    pub fn new() -> SmallList<T, N> {
+        SmallList {
+            repr: SmallRepr::Inline(Inline::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            SmallRepr::Inline(inline) => inline.len,
+            SmallRepr::Linked(list) => list.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Whether the values have moved to the heap.
+    pub fn spilled(&self) -> bool {
+        matches!(self.repr, SmallRepr::Linked(_))
+    }
+
+    // The linked list to push into when the inline array is full.
+    fn linked_for_push(&mut self) -> Option<&mut boxed::LinkedList<T>> {
+        if let SmallRepr::Inline(inline) = &mut self.repr {
+            if inline.len < N {
+                return None;
+            }
+            self.repr = SmallRepr::Linked(inline.take_all());
+        }
+        match &mut self.repr {
+            SmallRepr::Linked(list) => Some(list),
+            SmallRepr::Inline(_) => unreachable!(),
+        }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        match self.linked_for_push() {
+            Some(list) => list.push_back(value),
+            None => match &mut self.repr {
+                SmallRepr::Inline(inline) => inline.insert(inline.len, value),
+                SmallRepr::Linked(_) => unreachable!(),
+            },
+        }
+    }
+
+    // O(N) while inline, the values shift up by one.
+    pub fn push_front(&mut self, value: T) {
+        match self.linked_for_push() {
+            Some(list) => list.push_front(value),
+            None => match &mut self.repr {
+                SmallRepr::Inline(inline) => inline.insert(0, value),
+                SmallRepr::Linked(_) => unreachable!(),
+            },
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        match &mut self.repr {
+            SmallRepr::Inline(inline) if inline.len == 0 => None,
+            SmallRepr::Inline(inline) => Some(inline.remove(0)),
+            SmallRepr::Linked(list) => list.pop_front(),
+        }
+    }
+
+    // O(n) once spilled, see `boxed::LinkedList::pop_back`.
+    pub fn pop_back(&mut self) -> Option<T> {
+        match &mut self.repr {
+            SmallRepr::Inline(inline) if inline.len == 0 => None,
+            SmallRepr::Inline(inline) => Some(inline.remove(inline.len - 1)),
+            SmallRepr::Linked(list) => list.pop_back(),
+        }
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        match &self.repr {
+            SmallRepr::Inline(inline) => inline.as_slice().last(),
+            SmallRepr::Linked(list) => list.back(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match &self.repr {
+            SmallRepr::Inline(inline) => inline.as_slice().get(index),
+            SmallRepr::Linked(list) => list.get(index),
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match &mut self.repr {
+            SmallRepr::Inline(inline) => inline.as_mut_slice().get_mut(index),
+            SmallRepr::Linked(list) => list.get_mut(index),
+        }
+    }
+
+    pub fn iter(&self) -> SmallListIter<'_, T> {
+        match &self.repr {
+            SmallRepr::Inline(inline) => SmallListIter::Inline(inline.as_slice().iter()),
+            SmallRepr::Linked(list) => SmallListIter::Linked(list.iter()),
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallList<T, N> {
+    fn default() -> Self {
+        SmallList::new()
+    }
+}
+
+// The copy is inline again if the values fit.
+impl<T: Clone, const N: usize> Clone for SmallList<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SmallList<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallList<T, N> {}
+
+impl<T: Debug, const N: usize> Debug for SmallList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = SmallList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallList<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallList<T, N> {
+    type Item = &'a T;
+    type IntoIter = SmallListIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+
+    #[test]
+    fn test_matches_vecdeque_across_spill() {
+        let mut list: SmallList<u32, 4> = SmallList::new();
+        let mut model = VecDeque::new();
+        let mut seed = 9u32;
+        for step in 0..400 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            // stays short for a while, then grows past N
+            let grow = step > 200;
+            match (seed >> 16) % 5 {
+                0 | 1 if grow || model.len() < 3 => {
+                    list.push_back(seed);
+                    model.push_back(seed);
+                }
+                2 if grow || model.len() < 3 => {
+                    list.push_front(seed);
+                    model.push_front(seed);
+                }
+                3 => assert_eq!(list.pop_back(), model.pop_back()),
+                _ => assert_eq!(list.pop_front(), model.pop_front()),
+            }
+            assert_eq!(list.len(), model.len());
+            if !grow {
+                assert!(!list.spilled());
+            }
+            assert_eq!((list.front(), list.back()), (model.front(), model.back()));
+        }
+        assert!(list.spilled());
+        assert!(list.iter().eq(model.iter()));
+        assert_eq!(list.iter().len(), model.len());
+    }
+
+    #[test]
+    fn test_inline_until_full() {
+        let mut list: SmallList<i32, 3> = [2, 3].into_iter().collect();
+        list.push_front(1);
+        assert!(!list.spilled());
+        *list.get_mut(1).unwrap() *= 10;
+        assert_eq!(format!("{list:?}"), "[1, 20, 3]");
+        let copy = list.clone();
+        list.push_back(4);
+        assert!(list.spilled());
+        assert_eq!(list.get(3), Some(&4));
+        list.pop_back();
+        assert_eq!(list, copy);
+        // shrinking doesn't move the values back, a copy does
+        assert!(list.spilled() && !list.clone().spilled());
+        assert_eq!((&copy).into_iter().sum::<i32>(), 24);
+
+        let mut never_inline: SmallList<i32, 0> = SmallList::default();
+        never_inline.push_back(1);
+        assert!(never_inline.spilled() && never_inline.pop_front() == Some(1));
+        assert!(never_inline.is_empty());
+    }
+
+    #[test]
+    fn test_drops_inline_values_once() {
+        let value = Rc::new(());
+        let mut list: SmallList<Rc<()>, 4> = SmallList::new();
+        for _ in 0..3 {
+            list.push_back(value.clone());
+        }
+        drop(list.pop_front());
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(list);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let mut spilled: SmallList<Rc<()>, 2> = SmallList::new();
+        spilled.extend((0..5).map(|_| value.clone()));
+        assert_eq!(Rc::strong_count(&value), 6);
+        drop(spilled);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{LinkedList, ValueRef};
+
+// A LinkedList kept in ascending order, equal values in insertion order. Only
+// ordered insertion is exposed for changes, reads go through `as_list`.
+// Lookups walk from the head and stop at the first value not smaller than the
+// one searched for, so a miss doesn't have to reach the tail.
+pub struct SortedLinkedList<T> {
+    list: LinkedList<T>,
+}
+
+impl<T: Ord> SortedLinkedList<T> {
+    pub fn new() -> SortedLinkedList<T> {
+        SortedLinkedList {
+            list: LinkedList::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    // Returns the index the value was inserted at.
+    pub fn insert(&mut self, value: T) -> usize {
+        self.list.insert_sorted(value)
+    }
+
+    // Like `slice::binary_search`: Ok with the index of the first equal value,
+    // or Err with the index it would be inserted at.
+    pub fn search(&self, value: &T) -> Result<usize, usize> {
+        let index = self
+            .list
+            .position(|other| other >= value)
+            .unwrap_or(self.list.len());
+        match self.list.get(index) {
+            Some(other) if *other == *value => Ok(index),
+            _ => Err(index),
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.search(value).is_ok()
+    }
+
+    // The smallest value.
+    pub fn first(&self) -> Option<ValueRef<'_, T>> {
+        self.list.front()
+    }
+
+    // The largest value.
+    pub fn last(&self) -> Option<ValueRef<'_, T>> {
+        self.list.back()
+    }
+
+    pub fn as_list(&self) -> &LinkedList<T> {
+        &self.list
+    }
+
+    pub fn into_list(self) -> LinkedList<T> {
+        self.list
+    }
+}
+
+impl<T: Ord + Clone> SortedLinkedList<T> {
+    pub fn pop_first(&mut self) -> Option<T> {
+        self.list.pop_front()
+    }
+
+    // O(n) like `LinkedList::pop_back`.
+    pub fn pop_last(&mut self) -> Option<T> {
+        self.list.pop_back()
+    }
+
+    // Removes the first value equal to `value`, if any.
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let index = self.search(value).ok()?;
+        self.list.remove_nth(index).ok()
+    }
+}
+
+impl<T: Ord> Default for SortedLinkedList<T> {
+    fn default() -> Self {
+        SortedLinkedList::new()
+    }
+}
+
+// Sorts the list once, stable, so equal values keep their order.
+impl<T: Ord> From<LinkedList<T>> for SortedLinkedList<T> {
+    fn from(mut list: LinkedList<T>) -> Self {
+        list.sort();
+        SortedLinkedList { list }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SortedLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        SortedLinkedList::from(iter.into_iter().collect::<LinkedList<T>>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stays_sorted() {
+        let mut tasks = SortedLinkedList::default();
+        assert_eq!(tasks.insert(5), 0);
+        assert_eq!(tasks.insert(2), 0);
+        assert_eq!(tasks.insert(8), 2);
+        assert_eq!(tasks.insert(5), 2);
+        assert_eq!(*tasks.as_list(), [2, 5, 5, 8]);
+        assert_eq!(*tasks.first().unwrap(), 2);
+        assert_eq!(*tasks.last().unwrap(), 8);
+
+        assert_eq!(tasks.search(&5), Ok(1));
+        assert_eq!(tasks.search(&3), Err(1));
+        assert_eq!(tasks.search(&9), Err(4));
+        assert!(tasks.contains(&8) && !tasks.contains(&1));
+
+        assert_eq!(tasks.remove(&5), Some(5));
+        assert_eq!(tasks.remove(&4), None);
+        assert_eq!(tasks.pop_first(), Some(2));
+        assert_eq!(tasks.pop_last(), Some(8));
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks.into_list(), [5]);
+    }
+
+    #[test]
+    fn test_from_unsorted() {
+        let sorted: SortedLinkedList<_> = [(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd')]
+            .into_iter()
+            .collect();
+        assert_eq!(*sorted.as_list(), [(1, 'b'), (2, 'd'), (3, 'a'), (3, 'c')]);
+        let empty = SortedLinkedList::<i32>::from(LinkedList::new());
+        assert!(empty.is_empty() && empty.first().is_none());
+        assert_eq!(empty.search(&1), Err(0));
+    }
+}
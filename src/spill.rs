@@ -0,0 +1,209 @@
+use std::fs::{self, DirBuilder, File};
+use std::hash::{BuildHasher, RandomState};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::de::IoRead;
+use serde_json::StreamDeserializer;
+
+use crate::{LinkedList, LinkedListNodeIter};
+
+static SPILL_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+// List that keeps at most `window` of its newest nodes in memory. When the
+// window overflows, the oldest half of it is written to a segment file in a
+// private temporary directory; iteration streams the segments back one element
+// at a time before continuing with the in-memory nodes.
+pub struct SpillingList<T> {
+    hot: LinkedList<T>,
+    hot_len: usize,
+    window: usize,
+    segments: Vec<PathBuf>,
+    cold_len: usize,
+    dir: PathBuf,
+}
+
+pub struct SpillingListIter<'a, T> {
+    segments: std::slice::Iter<'a, PathBuf>,
+    segment: Option<StreamDeserializer<'static, IoRead<BufReader<File>>, T>>,
+    hot: LinkedListNodeIter<T>,
+}
+
+impl<T: DeserializeOwned + Clone> Iterator for SpillingListIter<'_, T> {
+    type Item = io::Result<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(segment) = &mut self.segment {
+                match segment.next() {
+                    Some(value) => return Some(value.map_err(io::Error::from)),
+                    None => self.segment = None,
+                }
+            }
+            match self.segments.next() {
+                None => break,
+                Some(path) => match File::open(path) {
+                    Err(err) => return Some(Err(err)),
+                    Ok(file) => {
+                        self.segment = Some(
+                            serde_json::Deserializer::from_reader(BufReader::new(file)).into_iter(),
+                        )
+                    }
+                },
+            }
+        }
+        let node = self.hot.next()??;
+        let value = node.borrow().value.clone();
+        Some(Ok(value))
+    }
+}
+
+// Makes a new directory with an unguessable name in the temporary directory.
+// `create` fails if the path already exists, so another user can't plant a
+// directory or symlink there for the segments to be written into.
+fn create_private_dir() -> io::Result<PathBuf> {
+    let mut builder = DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    let mut attempts = 0;
+    loop {
+        // std seeds every RandomState from the OS random number generator
+        let suffix = RandomState::new().hash_one(SPILL_DIRS.fetch_add(1, Ordering::Relaxed));
+        let dir = std::env::temp_dir().join(format!("rll-spill-{suffix:016x}"));
+        match builder.create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && attempts < 16 => attempts += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+impl<T: Serialize> SpillingList<T> {
+    pub fn new(window: usize) -> io::Result<SpillingList<T>> {
+        assert!(window > 0, "window must be non-zero");
+        let dir = create_private_dir()?;
+        Ok(SpillingList {
+            hot: LinkedList::new(),
+            hot_len: 0,
+            window,
+            segments: Vec::new(),
+            cold_len: 0,
+            dir,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.cold_len + self.hot_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Number of elements currently held in memory.
+    pub fn hot_len(&self) -> usize {
+        self.hot_len
+    }
+
+    pub fn push_back(&mut self, value: T) -> io::Result<()> {
+        self.hot.push_back(value);
+        self.hot_len += 1;
+        if self.hot_len > self.window {
+            self.spill(self.hot_len - self.window / 2)?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self, count: usize) -> io::Result<()> {
+        let path = self
+            .dir
+            .join(format!("segment-{}.jsonl", self.segments.len()));
+        let mut out = BufWriter::new(File::create(&path)?);
+        for node in self.hot.iter().flatten().take(count) {
+            serde_json::to_writer(&mut out, &node.borrow().value)?;
+            out.write_all(b"\n")?;
+        }
+        out.into_inner()
+            .map_err(|err| err.into_error())?
+            .sync_data()?;
+
+        // only unlink the nodes once the segment is safely on disk
+        for _ in 0..count {
+            self.hot.pop_front_node();
+        }
+        self.segments.push(path);
+        self.hot_len -= count;
+        self.cold_len += count;
+        Ok(())
+    }
+
+    pub fn iter(&self) -> SpillingListIter<'_, T> {
+        SpillingListIter {
+            segments: self.segments.iter(),
+            segment: None,
+            hot: self.hot.iter(),
+        }
+    }
+}
+
+impl<T> Drop for SpillingList<T> {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spills_past_window() {
+        let mut list = SpillingList::new(4).unwrap();
+        for value in 0..10 {
+            list.push_back(value).unwrap();
+            assert!(list.hot_len() <= 4);
+        }
+
+        assert_eq!(list.len(), 10);
+        assert!(!list.segments.is_empty());
+        let values: Vec<i32> = list.iter().map(Result::unwrap).collect();
+        assert_eq!(values, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_drop_removes_segments() {
+        let mut list = SpillingList::new(1).unwrap();
+        list.push_back("a".to_string()).unwrap();
+        list.push_back("b\nc".to_string()).unwrap();
+        list.push_back("d".to_string()).unwrap();
+        let values: Vec<String> = list.iter().map(Result::unwrap).collect();
+        assert_eq!(values, ["a", "b\nc", "d"]);
+
+        let dir = list.dir.clone();
+        assert!(dir.exists());
+        drop(list);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_empty() {
+        let list = SpillingList::<u8>::new(2).unwrap();
+        assert!(list.is_empty());
+        assert_eq!(list.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_private_dirs() {
+        let first = SpillingList::<u8>::new(1).unwrap();
+        let second = SpillingList::<u8>::new(1).unwrap();
+        assert_ne!(first.dir, second.dir);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first.dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+    }
+}
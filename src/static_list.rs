@@ -0,0 +1,232 @@
+use core::fmt::{Debug, Display, Formatter};
+use core::mem::MaybeUninit;
+
+use crate::ListError;
+
+// Nodes link to each other by slot index, free slots are chained through the
+// same `next` field so no allocator is needed at all.
+struct StaticNode<T> {
+    value: MaybeUninit<T>,
+    next: Option<usize>,
+}
+
+pub struct StaticList<T, const N: usize> {
+    nodes: [StaticNode<T>; N],
+    head: Option<usize>,
+    tail: Option<usize>,
+    free: Option<usize>,
+    len: usize,
+}
+
+pub struct StaticListIter<'a, T, const N: usize> {
+    list: &'a StaticList<T, N>,
+    current: Option<usize>,
+}
+
+impl<'a, T, const N: usize> Iterator for StaticListIter<'a, T, N> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = &self.list.nodes[self.current?];
+        self.current = node.next;
+        // every slot reachable from head holds an initialized value
+        Some(unsafe { node.value.assume_init_ref() })
+    }
+}
+
+impl<T, const N: usize> StaticList<T, N> {
+    pub fn new() -> StaticList<T, N> {
+        StaticList {
+            nodes: core::array::from_fn(|i| StaticNode {
+                value: MaybeUninit::uninit(),
+                next: if i + 1 < N { Some(i + 1) } else { None },
+            }),
+            head: None,
+            tail: None,
+            free: if N > 0 { Some(0) } else { None },
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    fn alloc(&mut self, value: T, next: Option<usize>) -> Result<usize, ListError> {
+        let slot = self
+            .free
+            .ok_or(ListError::CapacityExceeded { capacity: N })?;
+        let node = &mut self.nodes[slot];
+        self.free = node.next;
+        node.value.write(value);
+        node.next = next;
+        self.len += 1;
+        Ok(slot)
+    }
+
+    fn release(&mut self, slot: usize) -> T {
+        let node = &mut self.nodes[slot];
+        // the slot is unlinked by the caller and goes back to the free chain,
+        // so the value is read out exactly once
+        let value = unsafe { node.value.assume_init_read() };
+        node.next = self.free;
+        self.free = Some(slot);
+        self.len -= 1;
+        value
+    }
+
+    pub fn push_back(&mut self, value: T) -> Result<(), ListError> {
+        let slot = self.alloc(value, None)?;
+        match self.tail {
+            None => self.head = Some(slot),
+            Some(tail) => self.nodes[tail].next = Some(slot),
+        }
+        self.tail = Some(slot);
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, value: T) -> Result<(), ListError> {
+        let slot = self.alloc(value, self.head)?;
+        if self.head.is_none() {
+            self.tail = Some(slot);
+        }
+        self.head = Some(slot);
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let slot = self.head?;
+        self.head = self.nodes[slot].next;
+        if self.head.is_none() {
+            self.tail = None;
+        }
+        Some(self.release(slot))
+    }
+
+    pub fn iter(&self) -> StaticListIter<'_, T, N> {
+        StaticListIter {
+            list: self,
+            current: self.head,
+        }
+    }
+
+    pub fn get_nth(&self, nth: usize) -> Result<&T, ListError> {
+        self.iter().nth(nth).ok_or(ListError::IndexOutOfBounds {
+            index: nth,
+            len: self.len,
+        })
+    }
+}
+
+impl<T, const N: usize> Default for StaticList<T, N> {
+    fn default() -> Self {
+        StaticList::new()
+    }
+}
+
+impl<T, const N: usize> Drop for StaticList<T, N> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T: Debug, const N: usize> Display for StaticList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?
+            }
+            write!(f, "{:?}", value)?
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, const N: usize> defmt::Format for StaticList<T, N> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "[");
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", value);
+        }
+        defmt::write!(f, "]");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_until_full() {
+        let mut list = StaticList::<i32, 3>::new();
+        list.push_back(2).unwrap();
+        list.push_front(1).unwrap();
+        list.push_back(3).unwrap();
+
+        assert_eq!(list.len(), list.capacity());
+        assert_eq!(
+            list.push_back(4),
+            Err(ListError::CapacityExceeded { capacity: 3 })
+        );
+        assert_eq!(
+            list.push_front(0),
+            Err(ListError::CapacityExceeded { capacity: 3 })
+        );
+
+        let targets = [1, 2, 3];
+        for (value, target) in list.iter().zip(targets) {
+            assert_eq!(*value, target)
+        }
+        assert_eq!(*list.get_nth(2).unwrap(), 3);
+        assert_eq!(
+            list.get_nth(3),
+            Err(ListError::IndexOutOfBounds { index: 3, len: 3 })
+        );
+        assert!(StaticList::<i32, 0>::new().push_back(1).is_err());
+    }
+
+    #[test]
+    fn test_pop_front_reuses_slots() {
+        let mut list = StaticList::<i32, 2>::new();
+        list.push_back(1).unwrap();
+        list.push_back(2).unwrap();
+
+        assert_eq!(list.pop_front(), Some(1));
+        list.push_back(3).unwrap();
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(3));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+
+        list.push_front(4).unwrap();
+        list.push_front(5).unwrap();
+        assert_eq!(list.to_string(), "[5, 4]");
+    }
+
+    #[test]
+    fn test_drop_values() {
+        let value = Rc::new(1);
+        let mut list = StaticList::<Rc<i32>, 4>::new();
+        list.push_back(value.clone()).unwrap();
+        list.push_back(value.clone()).unwrap();
+        list.push_back(value.clone()).unwrap();
+        list.pop_front();
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,89 @@
+use core::ops::Add;
+
+use crate::LinkedList;
+
+// Element types `stats` works on.
+pub trait Numeric: Copy + PartialOrd + Add<Output = Self> {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! numeric {
+    ($($number:ty),*) => {
+        $(
+            impl Numeric for $number {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats<T> {
+    pub count: usize,
+    pub min: T,
+    pub max: T,
+    pub sum: T,
+    pub mean: f64,
+}
+
+impl<T: Numeric> LinkedList<T> {
+    // One traversal, None for an empty list. The sum has the element type, so
+    // it overflows like adding the elements would.
+    pub fn stats(&self) -> Option<Stats<T>> {
+        let mut nodes = self.iter().flatten();
+        let first = nodes.next()?.borrow().value;
+        let mut stats = Stats {
+            count: 1,
+            min: first,
+            max: first,
+            sum: first,
+            mean: 0.0,
+        };
+        for node in nodes {
+            let value = node.borrow().value;
+            if value < stats.min {
+                stats.min = value;
+            }
+            if value > stats.max {
+                stats.max = value;
+            }
+            stats.sum = stats.sum + value;
+            stats.count += 1;
+        }
+        stats.mean = stats.sum.to_f64() / stats.count as f64;
+        Some(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut list = LinkedList::<i32>::new();
+        assert_eq!(list.stats(), None);
+        for value in [4, -2, 9, 1] {
+            list.push_back(value);
+        }
+
+        let stats = list.stats().unwrap();
+        assert_eq!(
+            (stats.count, stats.min, stats.max, stats.sum),
+            (4, -2, 9, 12)
+        );
+        assert_eq!(stats.mean, 3.0);
+
+        let mut floats = LinkedList::<f64>::new();
+        floats.push_back(0.5);
+        let stats = floats.stats().unwrap();
+        assert_eq!(
+            (stats.min, stats.max, stats.sum, stats.mean),
+            (0.5, 0.5, 0.5, 0.5)
+        );
+    }
+}
@@ -0,0 +1,507 @@
+use alloc::boxed::Box;
+use core::fmt::{Debug, Formatter};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::{self, NonNull};
+use core::slice;
+
+use crate::ListError;
+
+type ChunkLink<T, const N: usize> = Option<NonNull<Chunk<T, N>>>;
+
+// Up to N values kept in order at the start of an inline array, so a chunk is
+// a single allocation.
+struct Chunk<T, const N: usize> {
+    values: [MaybeUninit<T>; N],
+    // never 0 while linked
+    len: usize,
+    next: ChunkLink<T, N>,
+}
+
+impl<T, const N: usize> Chunk<T, N> {
+    // Leaks a new empty chunk, the list frees it again through `UnrolledList::free`.
+    fn alloc(next: ChunkLink<T, N>) -> NonNull<Chunk<T, N>> {
+        NonNull::from(Box::leak(Box::new(Chunk {
+            values: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+            next,
+        })))
+    }
+
+    fn with_value(value: T, next: ChunkLink<T, N>) -> NonNull<Chunk<T, N>> {
+        let mut chunk = Chunk::alloc(next);
+        // the new chunk isn't reachable from anywhere else yet
+        unsafe { chunk.as_mut() }.insert(0, value);
+        chunk
+    }
+
+    fn as_slice(&self) -> &[T] {
+        // the first `len` values are initialized
+        unsafe { slice::from_raw_parts(self.values.as_ptr().cast(), self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.values.as_mut_ptr().cast(), self.len) }
+    }
+
+    // The caller checks there is room, shifting at most N values.
+    fn insert(&mut self, index: usize, value: T) {
+        let base = self.values.as_mut_ptr().cast::<T>();
+        unsafe {
+            ptr::copy(base.add(index), base.add(index + 1), self.len - index);
+            base.add(index).write(value);
+        }
+        self.len += 1;
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        let base = self.values.as_mut_ptr().cast::<T>();
+        self.len -= 1;
+        unsafe {
+            let value = base.add(index).read();
+            ptr::copy(base.add(index + 1), base.add(index), self.len - index);
+            value
+        }
+    }
+
+    // Moves the values from `at` on into a new chunk linked after this one.
+    fn split_off(&mut self, at: usize) -> NonNull<Chunk<T, N>> {
+        let mut second = Chunk::alloc(self.next);
+        let moved = self.len - at;
+        unsafe {
+            let values = self.values.as_ptr().add(at);
+            ptr::copy_nonoverlapping(values, second.as_mut().values.as_mut_ptr(), moved);
+            second.as_mut().len = moved;
+        }
+        self.len = at;
+        self.next = Some(second);
+        second
+    }
+
+    // Moves all values of `other` behind the ones here, the caller checks they fit.
+    fn append(&mut self, other: &mut Chunk<T, N>) {
+        unsafe {
+            let end = self.values.as_mut_ptr().add(self.len);
+            ptr::copy_nonoverlapping(other.values.as_ptr(), end, other.len);
+        }
+        self.len += other.len;
+        other.len = 0;
+    }
+}
+
+impl<T, const N: usize> Drop for Chunk<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+// Linked list of chunks holding up to N values each, so iterating follows one
+// pointer per N elements instead of one per element. Inserting into a full
+// chunk splits it in half, removing merges a chunk that fell under half full
+// with its successor when both fit. Indexing walks the chunks, O(n / N).
+//
+// Chunks are linked by raw pointers, like std's LinkedList does, so the values
+// are reached without Rc or RefCell and the tail can point into the chain.
+pub struct UnrolledList<T, const N: usize = 32> {
+    head: ChunkLink<T, N>,
+    tail: ChunkLink<T, N>,
+    len: usize,
+    // the chunks the links point to are owned by the list
+    _chunks: PhantomData<Box<Chunk<T, N>>>,
+}
+
+// chunks are only reached through the list that owns them
+unsafe impl<T: Send, const N: usize> Send for UnrolledList<T, N> {}
+unsafe impl<T: Sync, const N: usize> Sync for UnrolledList<T, N> {}
+
+pub struct UnrolledListIter<'a, T, const N: usize = 32> {
+    chunk: ChunkLink<T, N>,
+    values: core::slice::Iter<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for UnrolledListIter<'a, T, N> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.values.next() {
+                self.remaining -= 1;
+                return Some(value);
+            }
+            // the list is borrowed for 'a, so its chunks stay as they are
+            let chunk = unsafe { self.chunk?.as_ref() };
+            self.chunk = chunk.next;
+            self.values = chunk.as_slice().iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for UnrolledListIter<'_, T, N> {}
+
+impl<T, const N: usize> UnrolledList<T, N> {
+    pub fn new() -> UnrolledList<T, N> {
+        const { assert!(N >= 2, "chunks must hold at least 2 values to be split") };
+        UnrolledList {
+            head: None,
+            tail: None,
+            len: 0,
+            _chunks: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn out_of_bounds(&self, index: usize) -> ListError {
+        ListError::IndexOutOfBounds {
+            index,
+            len: self.len,
+        }
+    }
+
+    // Linked chunks are only reached through the list, so a borrow of one
+    // follows the borrow of the list.
+    fn chunk(&self, chunk: NonNull<Chunk<T, N>>) -> &Chunk<T, N> {
+        unsafe { chunk.as_ref() }
+    }
+
+    fn chunk_mut(&mut self, mut chunk: NonNull<Chunk<T, N>>) -> &mut Chunk<T, N> {
+        unsafe { chunk.as_mut() }
+    }
+
+    // Takes back ownership of a chunk that was just unlinked.
+    fn free(chunk: NonNull<Chunk<T, N>>) -> Box<Chunk<T, N>> {
+        unsafe { Box::from_raw(chunk.as_ptr()) }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.len += 1;
+        if let Some(tail) = self.tail {
+            let tail = self.chunk_mut(tail);
+            if tail.len < N {
+                tail.insert(tail.len, value);
+                return;
+            }
+        }
+        let new = Chunk::with_value(value, None);
+        match self.tail {
+            None => self.head = Some(new),
+            Some(tail) => self.chunk_mut(tail).next = Some(new),
+        }
+        self.tail = Some(new);
+    }
+
+    // Shifts the values of the first chunk, O(N).
+    pub fn push_front(&mut self, value: T) {
+        self.len += 1;
+        if let Some(head) = self.head {
+            let head = self.chunk_mut(head);
+            if head.len < N {
+                head.insert(0, value);
+                return;
+            }
+        }
+        let new = Chunk::with_value(value, self.head);
+        if self.tail.is_none() {
+            self.tail = Some(new);
+        }
+        self.head = Some(new);
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        let chunk = self.chunk_mut(head);
+        let value = chunk.remove(0);
+        if chunk.len == 0 {
+            self.head = chunk.next;
+            if self.head.is_none() {
+                self.tail = None;
+            }
+            drop(Self::free(head));
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    // O(1) unless the last chunk runs empty, its predecessor is then found
+    // from the head.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        let chunk = self.chunk_mut(tail);
+        let value = chunk.remove(chunk.len - 1);
+        if chunk.len == 0 {
+            self.unlink_tail();
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn unlink_tail(&mut self) {
+        let tail = self.tail.unwrap();
+        let mut prev = None;
+        let mut current = self.head;
+        while current != Some(tail) {
+            prev = current;
+            current = self.chunk(current.unwrap()).next;
+        }
+        match prev {
+            None => self.head = None,
+            Some(prev) => self.chunk_mut(prev).next = None,
+        }
+        self.tail = prev;
+        drop(Self::free(tail));
+    }
+
+    // The chunk holding `index` and the offset in it, index must be in bounds.
+    fn locate(&self, index: usize) -> (NonNull<Chunk<T, N>>, usize) {
+        let mut chunk = self.head.unwrap();
+        let mut offset = index;
+        while offset >= self.chunk(chunk).len {
+            offset -= self.chunk(chunk).len;
+            chunk = self.chunk(chunk).next.unwrap();
+        }
+        (chunk, offset)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let (chunk, offset) = self.locate(index);
+        Some(&self.chunk(chunk).as_slice()[offset])
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let (chunk, offset) = self.locate(index);
+        Some(&mut self.chunk_mut(chunk).as_mut_slice()[offset])
+    }
+
+    // Like `LinkedList::insert`, a full chunk is split in half first.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), ListError> {
+        if index > self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        if index == self.len {
+            self.push_back(value);
+            return Ok(());
+        }
+        let (at, offset) = self.locate(index);
+        let chunk = self.chunk_mut(at);
+        if chunk.len < N {
+            chunk.insert(offset, value);
+        } else {
+            let second = chunk.split_off(N / 2);
+            if offset <= N / 2 {
+                chunk.insert(offset, value);
+            } else {
+                self.chunk_mut(second).insert(offset - N / 2, value);
+            }
+            if self.tail == Some(at) {
+                self.tail = Some(second);
+            }
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, index: usize) -> Result<T, ListError> {
+        if index >= self.len {
+            return Err(self.out_of_bounds(index));
+        }
+        if index == 0 {
+            return Ok(self.pop_front().unwrap());
+        }
+        let (at, offset) = self.locate(index);
+        let chunk = self.chunk_mut(at);
+        let value = chunk.remove(offset);
+        let (left, next) = (chunk.len, chunk.next);
+        match next {
+            // an emptied chunk isn't the head, index 0 is handled above
+            None if left == 0 => self.unlink_tail(),
+            Some(next) if left < N / 2 && left + self.chunk(next).len <= N => {
+                let mut merged = Self::free(next);
+                let chunk = self.chunk_mut(at);
+                chunk.append(&mut merged);
+                chunk.next = merged.next;
+                if self.tail == Some(next) {
+                    self.tail = Some(at);
+                }
+            }
+            _ => {}
+        }
+        self.len -= 1;
+        Ok(value)
+    }
+
+    pub fn iter(&self) -> UnrolledListIter<'_, T, N> {
+        UnrolledListIter {
+            chunk: self.head,
+            values: [].iter(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for UnrolledList<T, N> {
+    fn default() -> Self {
+        UnrolledList::new()
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for UnrolledList<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        for value in iter {
+            list.push_back(value);
+        }
+        list
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for UnrolledList<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize> Drop for UnrolledList<T, N> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        while let Some(chunk) = current {
+            current = Self::free(chunk).next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::VecDeque;
+    use alloc::rc::Rc;
+    use alloc::vec::Vec;
+
+    fn chunk_lens<T, const N: usize>(list: &UnrolledList<T, N>) -> Vec<usize> {
+        let mut lens = Vec::new();
+        let mut chunk = list.head;
+        while let Some(current) = chunk {
+            lens.push(list.chunk(current).len);
+            chunk = list.chunk(current).next;
+        }
+        lens
+    }
+
+    fn assert_tail<T, const N: usize>(list: &UnrolledList<T, N>) {
+        let mut last = None;
+        let mut chunk = list.head;
+        while let Some(current) = chunk {
+            last = chunk;
+            chunk = list.chunk(current).next;
+        }
+        assert_eq!(last, list.tail);
+    }
+
+    #[test]
+    fn test_split_and_merge() {
+        let mut list: UnrolledList<i32, 4> = (0..8).collect();
+        assert_eq!(chunk_lens(&list), [4, 4]);
+        list.insert(1, 10).unwrap();
+        assert_eq!(chunk_lens(&list), [3, 2, 4]);
+        list.insert(9, 11).unwrap();
+        assert_eq!(chunk_lens(&list), [3, 2, 4, 1]);
+        list.insert(8, 12).unwrap();
+        assert_eq!(chunk_lens(&list), [3, 2, 2, 3, 1]);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [0, 10, 1, 2, 3, 4, 5, 6, 12, 7, 11]
+        );
+        assert_tail(&list);
+
+        // the second chunk falls under half and merges with the third
+        assert_eq!(list.remove(3), Ok(2));
+        assert_eq!(chunk_lens(&list), [3, 3, 3, 1]);
+        assert_eq!(
+            list.remove(10),
+            Err(ListError::IndexOutOfBounds { index: 10, len: 10 })
+        );
+        assert_eq!(list.remove(9), Ok(11));
+        assert_eq!(chunk_lens(&list), [3, 3, 3]);
+        assert_tail(&list);
+        list.push_back(13);
+        assert_eq!(*list.get(9).unwrap(), 13);
+        *list.get_mut(0).unwrap() = -1;
+        assert_eq!(list.get(0), Some(&-1));
+        assert_eq!(list.get(10), None);
+        assert_eq!(list.len(), 10);
+    }
+
+    #[test]
+    fn test_matches_vecdeque() {
+        let mut list = UnrolledList::<u32, 3>::default();
+        let mut model = VecDeque::new();
+        let mut seed = 7u32;
+        for _ in 0..3000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let pick = (seed >> 16) as usize;
+            match pick % 6 {
+                0 => {
+                    list.push_back(seed);
+                    model.push_back(seed);
+                }
+                1 => {
+                    list.push_front(seed);
+                    model.push_front(seed);
+                }
+                2 => assert_eq!(list.pop_front(), model.pop_front()),
+                3 => assert_eq!(list.pop_back(), model.pop_back()),
+                4 => {
+                    let index = pick % (model.len() + 1);
+                    list.insert(index, seed).unwrap();
+                    model.insert(index, seed);
+                }
+                _ if !model.is_empty() => {
+                    let index = pick % model.len();
+                    assert_eq!(list.remove(index).ok(), model.remove(index));
+                }
+                _ => assert!(list.remove(0).is_err()),
+            }
+            assert_eq!(list.len(), model.len());
+            assert!(chunk_lens(&list).iter().all(|&len| (1..=3).contains(&len)));
+            assert_tail(&list);
+        }
+        assert!(list.iter().eq(model.iter()));
+        assert_eq!(list.iter().len(), model.len());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let list: UnrolledList<u32, 2> = (0..1_000_000).collect();
+        assert_eq!(format!("{:?}", UnrolledList::<u8>::new()), "[]");
+        drop(list);
+    }
+
+    #[test]
+    fn test_drops_values_once() {
+        let value = Rc::new(());
+        let mut list: UnrolledList<Rc<()>, 4> = (0..8).map(|_| value.clone()).collect();
+        // split a full chunk, then merge it back
+        list.insert(1, value.clone()).unwrap();
+        drop(list.remove(2));
+        drop(list.remove(2));
+        drop(list.pop_back());
+        assert_eq!(Rc::strong_count(&value), 7);
+        drop(list);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::rc::{Rc, Weak};
+
+use crate::{Link, LinkedList};
+
+// Registry of weakly held entries, e.g. observers that unsubscribe simply by
+// being dropped. Dead entries are unlinked while iterating.
+pub struct WeakList<T> {
+    list: LinkedList<Weak<T>>,
+}
+
+pub struct WeakListIter<'a, T> {
+    list: &'a mut LinkedList<Weak<T>>,
+    prev: Link<Weak<T>>,
+    current: Link<Weak<T>>,
+}
+
+impl<T> Iterator for WeakListIter<'_, T> {
+    type Item = Rc<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.current.take() {
+            self.current = node.borrow().next.clone();
+            let live = node.borrow().value.upgrade();
+            match live {
+                Some(item) => {
+                    self.prev = Some(node);
+                    return Some(item);
+                }
+                None => {
+                    match &self.prev {
+                        None => self.list.head = self.current.clone(),
+                        Some(prev) => prev.borrow_mut().next = self.current.clone(),
+                    }
+                    if self.current.is_none() {
+                        self.list.set_tail(self.prev.as_ref());
+                    }
+                    self.list.len -= 1;
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T> WeakList<T> {
+    pub fn new() -> WeakList<T> {
+        WeakList {
+            list: LinkedList::new(),
+        }
+    }
+
+    pub fn push(&mut self, item: &Rc<T>) {
+        self.list.push_back(Rc::downgrade(item));
+    }
+
+    // Yields the live entries in insertion order, unlinking dead ones on the way.
+    pub fn iter(&mut self) -> WeakListIter<'_, T> {
+        WeakListIter {
+            current: self.list.head.clone(),
+            prev: None,
+            list: &mut self.list,
+        }
+    }
+
+    // Unlinks all dead entries, returns how many live ones are left.
+    pub fn prune(&mut self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl<T> Default for WeakList<T> {
+    fn default() -> Self {
+        WeakList::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_skips_dropped() {
+        let first = Rc::new(1);
+        let sec = Rc::new(2);
+        let third = Rc::new(3);
+        let mut list = WeakList::new();
+        list.push(&first);
+        list.push(&sec);
+        list.push(&third);
+
+        drop(sec);
+        let live: Vec<i32> = list.iter().map(|item| *item).collect();
+        assert_eq!(live, [1, 3]);
+        assert_eq!(list.list.iter().count(), 2);
+        assert_eq!(list.list.len(), 2);
+    }
+
+    #[test]
+    fn test_prune_fixes_ends() {
+        let first = Rc::new(1);
+        let sec = Rc::new(2);
+        let third = Rc::new(3);
+        let mut list = WeakList::new();
+        list.push(&first);
+        list.push(&sec);
+        list.push(&third);
+
+        drop(first);
+        drop(third);
+        assert_eq!(list.prune(), 1);
+        assert!(Rc::ptr_eq(
+            &list.list.head.clone().unwrap(),
+            &list.list.tail_node().unwrap()
+        ));
+
+        let fourth = Rc::new(4);
+        list.push(&fourth);
+        let live: Vec<i32> = list.iter().map(|item| *item).collect();
+        assert_eq!(live, [2, 4]);
+
+        drop(sec);
+        drop(fourth);
+        assert_eq!(list.prune(), 0);
+        assert!(list.list.head.is_none());
+        assert!(list.list.tail.is_none());
+        assert!(list.list.is_empty());
+    }
+}